@@ -1,12 +1,56 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20250817_034433_create_table_todos;
+mod m20261017_000001_add_version_to_todos;
+mod m20261017_000002_add_deleted_at_to_todos;
+mod m20261017_000003_add_due_dates_to_todos;
+mod m20261017_000004_add_priority_and_position_to_todos;
+mod m20261017_000005_create_tables_tags_and_todo_tags;
+mod m20261017_000006_create_table_checklist_items;
+mod m20261017_000007_create_table_projects;
+mod m20261017_000008_add_recurrence_to_todos;
+mod m20261017_000009_create_table_todo_dependencies;
+mod m20261017_000010_add_search_vector_to_todos;
+mod m20261017_000011_create_table_users;
+mod m20261017_000012_create_table_api_keys;
+mod m20261017_000013_create_table_share_grants;
+mod m20261017_000014_create_table_audit_events;
+mod m20261017_000015_create_table_outbox;
+mod m20261017_000016_create_tables_webhooks_and_deliveries;
+mod m20261017_000017_add_todo_changes_notify_trigger;
+mod m20261017_000018_create_table_idempotency_keys;
+mod m20261017_000019_add_owner_to_tags;
+mod m20261017_000020_add_owner_to_projects;
+mod m20261017_000021_restrict_project_deletion;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20250817_034433_create_table_todos::Migration)]
+        vec![
+            Box::new(m20250817_034433_create_table_todos::Migration),
+            Box::new(m20261017_000001_add_version_to_todos::Migration),
+            Box::new(m20261017_000002_add_deleted_at_to_todos::Migration),
+            Box::new(m20261017_000003_add_due_dates_to_todos::Migration),
+            Box::new(m20261017_000004_add_priority_and_position_to_todos::Migration),
+            Box::new(m20261017_000005_create_tables_tags_and_todo_tags::Migration),
+            Box::new(m20261017_000006_create_table_checklist_items::Migration),
+            Box::new(m20261017_000007_create_table_projects::Migration),
+            Box::new(m20261017_000008_add_recurrence_to_todos::Migration),
+            Box::new(m20261017_000009_create_table_todo_dependencies::Migration),
+            Box::new(m20261017_000010_add_search_vector_to_todos::Migration),
+            Box::new(m20261017_000011_create_table_users::Migration),
+            Box::new(m20261017_000012_create_table_api_keys::Migration),
+            Box::new(m20261017_000013_create_table_share_grants::Migration),
+            Box::new(m20261017_000014_create_table_audit_events::Migration),
+            Box::new(m20261017_000015_create_table_outbox::Migration),
+            Box::new(m20261017_000016_create_tables_webhooks_and_deliveries::Migration),
+            Box::new(m20261017_000017_add_todo_changes_notify_trigger::Migration),
+            Box::new(m20261017_000018_create_table_idempotency_keys::Migration),
+            Box::new(m20261017_000019_add_owner_to_tags::Migration),
+            Box::new(m20261017_000020_add_owner_to_projects::Migration),
+            Box::new(m20261017_000021_restrict_project_deletion::Migration),
+        ]
     }
 }
@@ -0,0 +1,101 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(TodoPriority::Enum)
+                    .values([
+                        TodoPriority::Low,
+                        TodoPriority::Medium,
+                        TodoPriority::High,
+                        TodoPriority::Urgent,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todos::Table)
+                    .add_column(
+                        ColumnDef::new(Todos::Priority)
+                            .custom(TodoPriority::Enum)
+                            .default(Expr::cust("'medium'"))
+                            .not_null(),
+                    )
+                    .add_column(
+                        ColumnDef::new(Todos::Position)
+                            .big_integer()
+                            .default(0)
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        // Existing todos keep their creation order.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE todos SET position = ordered.row_number \
+                 FROM (SELECT id, row_number() OVER (ORDER BY created_at, id) FROM todos) AS ordered \
+                 WHERE todos.id = ordered.id",
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todos_position")
+                    .table(Todos::Table)
+                    .col(Todos::Position)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_todos_position")
+                    .table(Todos::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todos::Table)
+                    .drop_column(Todos::Priority)
+                    .drop_column(Todos::Position)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_type(Type::drop().name(TodoPriority::Enum).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Todos {
+    Table,
+    Priority,
+    Position,
+}
+
+#[derive(DeriveIden)]
+enum TodoPriority {
+    #[sea_orm(iden = "todo_priority")]
+    Enum,
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
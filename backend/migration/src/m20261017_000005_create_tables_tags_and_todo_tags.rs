@@ -0,0 +1,115 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tags::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Tags::Id)
+                            .uuid()
+                            .extra("DEFAULT gen_random_uuid()")
+                            .primary_key()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Tags::Name).string_len(50).not_null())
+                    .col(ColumnDef::new(Tags::Color).string_len(7))
+                    .col(
+                        ColumnDef::new(Tags::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .extra("DEFAULT now()")
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Tags::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .extra("DEFAULT now()")
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tags_name")
+                    .table(Tags::Table)
+                    .col(Tags::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(TodoTags::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(TodoTags::TodoId).uuid().not_null())
+                    .col(ColumnDef::new(TodoTags::TagId).uuid().not_null())
+                    .primary_key(Index::create().col(TodoTags::TodoId).col(TodoTags::TagId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_todo_tags_todo_id")
+                            .from(TodoTags::Table, TodoTags::TodoId)
+                            .to(Todos::Table, Todos::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_todo_tags_tag_id")
+                            .from(TodoTags::Table, TodoTags::TagId)
+                            .to(Tags::Table, Tags::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todo_tags_tag_id")
+                    .table(TodoTags::Table)
+                    .col(TodoTags::TagId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TodoTags::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Tags::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Todos {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Tags {
+    Table,
+    Id,
+    Name,
+    Color,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum TodoTags {
+    Table,
+    TodoId,
+    TagId,
+}
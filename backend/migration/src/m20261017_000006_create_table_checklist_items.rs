@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ChecklistItems::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ChecklistItems::Id)
+                            .uuid()
+                            .extra("DEFAULT gen_random_uuid()")
+                            .primary_key()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ChecklistItems::TodoId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ChecklistItems::Title)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ChecklistItems::Completed)
+                            .boolean()
+                            .default(false)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ChecklistItems::Position)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ChecklistItems::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .extra("DEFAULT now()")
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ChecklistItems::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .extra("DEFAULT now()")
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_checklist_items_todo_id")
+                            .from(ChecklistItems::Table, ChecklistItems::TodoId)
+                            .to(Todos::Table, Todos::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_checklist_items_todo_id_position")
+                    .table(ChecklistItems::Table)
+                    .col(ChecklistItems::TodoId)
+                    .col(ChecklistItems::Position)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ChecklistItems::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Todos {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum ChecklistItems {
+    Table,
+    Id,
+    TodoId,
+    Title,
+    Completed,
+    Position,
+    CreatedAt,
+    UpdatedAt,
+}
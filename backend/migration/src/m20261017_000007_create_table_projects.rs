@@ -0,0 +1,111 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Projects::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Projects::Id)
+                            .uuid()
+                            .extra("DEFAULT gen_random_uuid()")
+                            .primary_key()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Projects::Name).string_len(100).not_null())
+                    .col(ColumnDef::new(Projects::Color).string_len(7))
+                    .col(
+                        ColumnDef::new(Projects::Archived)
+                            .boolean()
+                            .default(false)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Projects::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .extra("DEFAULT now()")
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Projects::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .extra("DEFAULT now()")
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todos::Table)
+                    .add_column(ColumnDef::new(Todos::ProjectId).uuid())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_todos_project_id")
+                            .from_tbl(Todos::Table)
+                            .from_col(Todos::ProjectId)
+                            .to_tbl(Projects::Table)
+                            .to_col(Projects::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todos_project_id")
+                    .table(Todos::Table)
+                    .col(Todos::ProjectId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_todos_project_id")
+                    .table(Todos::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todos::Table)
+                    .drop_foreign_key(Alias::new("fk_todos_project_id"))
+                    .drop_column(Todos::ProjectId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Projects::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Todos {
+    Table,
+    ProjectId,
+}
+
+#[derive(DeriveIden)]
+enum Projects {
+    Table,
+    Id,
+    Name,
+    Color,
+    Archived,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TodoDependencies::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(TodoDependencies::TodoId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(TodoDependencies::BlockerId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TodoDependencies::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .default(Expr::current_timestamp())
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(TodoDependencies::TodoId)
+                            .col(TodoDependencies::BlockerId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_todo_dependencies_todo_id")
+                            .from(TodoDependencies::Table, TodoDependencies::TodoId)
+                            .to(Todos::Table, Todos::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_todo_dependencies_blocker_id")
+                            .from(TodoDependencies::Table, TodoDependencies::BlockerId)
+                            .to(Todos::Table, Todos::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .check(
+                        Expr::col(TodoDependencies::TodoId)
+                            .ne(Expr::col(TodoDependencies::BlockerId)),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todo_dependencies_blocker_id")
+                    .table(TodoDependencies::Table)
+                    .col(TodoDependencies::BlockerId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TodoDependencies::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Todos {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum TodoDependencies {
+    Table,
+    TodoId,
+    BlockerId,
+    CreatedAt,
+}
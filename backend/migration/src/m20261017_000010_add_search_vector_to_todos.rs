@@ -0,0 +1,35 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Generated columns are not expressible through the schema builder, so this
+/// migration is written in SQL. The column is left out of the `todos` entity:
+/// Postgres maintains it and it is only read by the search query.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "ALTER TABLE todos ADD COLUMN search_vector tsvector GENERATED ALWAYS AS ( \
+                 setweight(to_tsvector('english', coalesce(title, '')), 'A') || \
+                 setweight(to_tsvector('english', coalesce(description, '')), 'B') \
+             ) STORED",
+        )
+        .await?;
+        db.execute_unprepared(
+            "CREATE INDEX idx_todos_search_vector ON todos USING GIN (search_vector)",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP INDEX IF EXISTS idx_todos_search_vector")
+            .await?;
+        db.execute_unprepared("ALTER TABLE todos DROP COLUMN search_vector")
+            .await?;
+        Ok(())
+    }
+}
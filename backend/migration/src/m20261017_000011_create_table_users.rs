@@ -0,0 +1,154 @@
+use sea_orm_migration::prelude::*;
+
+/// Gives every todo an owner. Todos created before accounts existed go to a
+/// legacy owner nobody can sign in as; an operator can reset its password or
+/// move the todos to their rightful owners.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+pub(crate) const LEGACY_OWNER_EMAIL: &str = "legacy@example.invalid";
+/// Argon2id hash of a random password that was thrown away.
+const LEGACY_OWNER_PASSWORD_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$/vLUiGNjx5oFIkOzdMW/hA$7jpPAy/O4dTqNg4miaOCKHksnW3dP5BwzmOlVjz9XVU";
+
+/// Gives the rows of `table` without an owner to the legacy owner, creating
+/// it on first use.
+pub(crate) async fn assign_to_legacy_owner(
+    manager: &SchemaManager<'_>,
+    table: &str,
+) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+    db.execute_unprepared(&format!(
+        "INSERT INTO users (email, password_hash) \
+         SELECT '{LEGACY_OWNER_EMAIL}', '{LEGACY_OWNER_PASSWORD_HASH}' \
+         WHERE EXISTS (SELECT 1 FROM {table} WHERE owner_id IS NULL) \
+         ON CONFLICT (email) DO NOTHING"
+    ))
+    .await?;
+    db.execute_unprepared(&format!(
+        "UPDATE {table} SET owner_id = \
+         (SELECT id FROM users WHERE email = '{LEGACY_OWNER_EMAIL}') \
+         WHERE owner_id IS NULL"
+    ))
+    .await?;
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Users::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Users::Id)
+                            .uuid()
+                            .extra("DEFAULT gen_random_uuid()")
+                            .primary_key()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Users::Email)
+                            .string_len(254)
+                            .unique_key()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Users::PasswordHash)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Users::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .extra("DEFAULT now()")
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Users::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .extra("DEFAULT now()")
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todos::Table)
+                    .add_column(ColumnDef::new(Todos::OwnerId).uuid().null())
+                    .to_owned(),
+            )
+            .await?;
+        assign_to_legacy_owner(manager, "todos").await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todos::Table)
+                    .modify_column(ColumnDef::new(Todos::OwnerId).uuid().not_null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_todos_owner_id")
+                            .from_tbl(Todos::Table)
+                            .from_col(Todos::OwnerId)
+                            .to_tbl(Users::Table)
+                            .to_col(Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_todos_owner_id_position")
+                    .table(Todos::Table)
+                    .col(Todos::OwnerId)
+                    .col(Todos::Position)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_todos_owner_id_position")
+                    .table(Todos::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Todos::Table)
+                    .drop_foreign_key(Alias::new("fk_todos_owner_id"))
+                    .drop_column(Todos::OwnerId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Users::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Todos {
+    Table,
+    OwnerId,
+    Position,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+    Email,
+    PasswordHash,
+    CreatedAt,
+    UpdatedAt,
+}
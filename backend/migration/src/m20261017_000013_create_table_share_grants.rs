@@ -0,0 +1,166 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(ShareRole::Enum)
+                    .values([ShareRole::Viewer, ShareRole::Editor, ShareRole::Owner])
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(ShareGrants::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ShareGrants::Id)
+                            .uuid()
+                            .extra("DEFAULT gen_random_uuid()")
+                            .primary_key()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ShareGrants::OwnerId).uuid().not_null())
+                    .col(ColumnDef::new(ShareGrants::UserId).uuid().not_null())
+                    .col(ColumnDef::new(ShareGrants::TodoId).uuid())
+                    .col(ColumnDef::new(ShareGrants::ProjectId).uuid())
+                    .col(
+                        ColumnDef::new(ShareGrants::Role)
+                            .custom(ShareRole::Enum)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ShareGrants::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .extra("DEFAULT now()")
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ShareGrants::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .extra("DEFAULT now()")
+                            .not_null(),
+                    )
+                    // A grant covers either a single todo or a project, never both.
+                    .check(Expr::cust("(todo_id IS NULL) <> (project_id IS NULL)"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_share_grants_owner_id")
+                            .from(ShareGrants::Table, ShareGrants::OwnerId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_share_grants_user_id")
+                            .from(ShareGrants::Table, ShareGrants::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_share_grants_todo_id")
+                            .from(ShareGrants::Table, ShareGrants::TodoId)
+                            .to(Todos::Table, Todos::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_share_grants_project_id")
+                            .from(ShareGrants::Table, ShareGrants::ProjectId)
+                            .to(Projects::Table, Projects::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_share_grants_todo_id_user_id")
+                    .table(ShareGrants::Table)
+                    .col(ShareGrants::TodoId)
+                    .col(ShareGrants::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_share_grants_project_id_owner_id_user_id")
+                    .table(ShareGrants::Table)
+                    .col(ShareGrants::ProjectId)
+                    .col(ShareGrants::OwnerId)
+                    .col(ShareGrants::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_share_grants_user_id")
+                    .table(ShareGrants::Table)
+                    .col(ShareGrants::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ShareGrants::Table).to_owned())
+            .await?;
+        manager
+            .drop_type(Type::drop().name(ShareRole::Enum).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Todos {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Projects {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum ShareRole {
+    #[sea_orm(iden = "share_role")]
+    Enum,
+    Viewer,
+    Editor,
+    Owner,
+}
+
+#[derive(DeriveIden)]
+enum ShareGrants {
+    Table,
+    Id,
+    OwnerId,
+    UserId,
+    TodoId,
+    ProjectId,
+    Role,
+    CreatedAt,
+    UpdatedAt,
+}
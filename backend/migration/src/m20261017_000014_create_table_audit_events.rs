@@ -0,0 +1,142 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_query::extension::postgres::Type;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_type(
+                Type::create()
+                    .as_enum(AuditAction::Enum)
+                    .values([
+                        AuditAction::Created,
+                        AuditAction::Updated,
+                        AuditAction::Deleted,
+                        AuditAction::Completed,
+                        AuditAction::Uncompleted,
+                        AuditAction::Restored,
+                        AuditAction::Purged,
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditEvents::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuditEvents::Id)
+                            .uuid()
+                            .extra("DEFAULT gen_random_uuid()")
+                            .primary_key()
+                            .not_null(),
+                    )
+                    // No foreign key: the history of a todo outlives its purge.
+                    .col(ColumnDef::new(AuditEvents::TodoId).uuid().not_null())
+                    .col(ColumnDef::new(AuditEvents::OwnerId).uuid().not_null())
+                    .col(ColumnDef::new(AuditEvents::ActorId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(AuditEvents::Action)
+                            .custom(AuditAction::Enum)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AuditEvents::Before).json_binary())
+                    .col(ColumnDef::new(AuditEvents::After).json_binary())
+                    .col(
+                        ColumnDef::new(AuditEvents::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .extra("DEFAULT now()")
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_audit_events_owner_id")
+                            .from(AuditEvents::Table, AuditEvents::OwnerId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_audit_events_actor_id")
+                            .from(AuditEvents::Table, AuditEvents::ActorId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_events_todo_id")
+                    .table(AuditEvents::Table)
+                    .col(AuditEvents::TodoId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_events_owner_id_created_at")
+                    .table(AuditEvents::Table)
+                    .col(AuditEvents::OwnerId)
+                    .col(AuditEvents::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_audit_events_actor_id")
+                    .table(AuditEvents::Table)
+                    .col(AuditEvents::ActorId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuditEvents::Table).to_owned())
+            .await?;
+        manager
+            .drop_type(Type::drop().name(AuditAction::Enum).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum AuditAction {
+    #[sea_orm(iden = "audit_action")]
+    Enum,
+    Created,
+    Updated,
+    Deleted,
+    Completed,
+    Uncompleted,
+    Restored,
+    Purged,
+}
+
+#[derive(DeriveIden)]
+enum AuditEvents {
+    Table,
+    Id,
+    TodoId,
+    OwnerId,
+    ActorId,
+    Action,
+    Before,
+    After,
+    CreatedAt,
+}
@@ -0,0 +1,48 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Every todo event is queued in the outbox, so a trigger there announces
+/// each change on the `todo_changes` channel, in the same JSON form as
+/// `TodoEvent::payload`. Postgres holds notifications back until the
+/// transaction commits, and drops those of rolled-back savepoints.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "CREATE FUNCTION notify_todo_change() RETURNS trigger AS $$ \
+             BEGIN \
+                 PERFORM pg_notify('todo_changes', json_build_object( \
+                     'id', NEW.id, \
+                     'type', NEW.event_type, \
+                     'occurred_at', NEW.occurred_at, \
+                     'data', json_build_object( \
+                         'todo_id', NEW.todo_id, \
+                         'owner_id', NEW.owner_id, \
+                         'project_id', NEW.project_id \
+                     ) \
+                 )::text); \
+                 RETURN NULL; \
+             END; \
+             $$ LANGUAGE plpgsql",
+        )
+        .await?;
+        db.execute_unprepared(
+            "CREATE TRIGGER outbox_notify_todo_change AFTER INSERT ON outbox \
+             FOR EACH ROW EXECUTE FUNCTION notify_todo_change()",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP TRIGGER IF EXISTS outbox_notify_todo_change ON outbox")
+            .await?;
+        db.execute_unprepared("DROP FUNCTION IF EXISTS notify_todo_change()")
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20261017_000011_create_table_users::assign_to_legacy_owner;
+
+/// Gives every tag an owner, so tag names are only unique per user. A tag
+/// stays with the owner of the oldest todo carrying it; every other owner
+/// whose todos carry it gets a copy of their own. Unused tags go to the
+/// legacy owner.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_tags_name")
+                    .table(Tags::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tags::Table)
+                    .add_column(ColumnDef::new(Tags::OwnerId).uuid().null())
+                    .to_owned(),
+            )
+            .await?;
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "UPDATE tags SET owner_id = ( \
+                 SELECT todos.owner_id FROM todo_tags \
+                 JOIN todos ON todos.id = todo_tags.todo_id \
+                 WHERE todo_tags.tag_id = tags.id \
+                 ORDER BY todos.created_at, todos.id LIMIT 1 \
+             )",
+        )
+        .await?;
+        db.execute_unprepared(
+            "INSERT INTO tags (owner_id, name, color, created_at, updated_at) \
+             SELECT DISTINCT todos.owner_id, tags.name, tags.color, tags.created_at, tags.updated_at \
+             FROM tags \
+             JOIN todo_tags ON todo_tags.tag_id = tags.id \
+             JOIN todos ON todos.id = todo_tags.todo_id \
+             WHERE todos.owner_id <> tags.owner_id",
+        )
+        .await?;
+        db.execute_unprepared(
+            "UPDATE todo_tags SET tag_id = copy.id \
+             FROM todos, tags AS original, tags AS copy \
+             WHERE todos.id = todo_tags.todo_id \
+               AND original.id = todo_tags.tag_id \
+               AND original.owner_id <> todos.owner_id \
+               AND copy.owner_id = todos.owner_id \
+               AND copy.name = original.name",
+        )
+        .await?;
+        assign_to_legacy_owner(manager, "tags").await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tags::Table)
+                    .modify_column(ColumnDef::new(Tags::OwnerId).uuid().not_null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_tags_owner_id")
+                            .from_tbl(Tags::Table)
+                            .from_col(Tags::OwnerId)
+                            .to_tbl(Users::Table)
+                            .to_col(Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tags_owner_id_name")
+                    .table(Tags::Table)
+                    .col(Tags::OwnerId)
+                    .col(Tags::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    /// Fails while two users have a tag with the same name.
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_tags_owner_id_name")
+                    .table(Tags::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tags::Table)
+                    .drop_foreign_key(Alias::new("fk_tags_owner_id"))
+                    .drop_column(Tags::OwnerId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_tags_name")
+                    .table(Tags::Table)
+                    .col(Tags::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Tags {
+    Table,
+    OwnerId,
+    Name,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
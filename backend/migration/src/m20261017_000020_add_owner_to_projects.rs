@@ -0,0 +1,122 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20261017_000011_create_table_users::assign_to_legacy_owner;
+
+/// Gives every project an owner. A project stays with the owner of its oldest
+/// todo, or of its oldest grant when it holds no todos; every other user with
+/// todos or grants in it gets a copy of their own. Unused projects go to the
+/// legacy owner.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Users with todos or grants in a project, and when they first used it.
+const PROJECT_USERS: &str = "SELECT project_id, owner_id, created_at FROM todos \
+                             WHERE project_id IS NOT NULL \
+                             UNION ALL \
+                             SELECT project_id, owner_id, created_at FROM share_grants \
+                             WHERE project_id IS NOT NULL";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Projects::Table)
+                    .add_column(ColumnDef::new(Projects::OwnerId).uuid().null())
+                    .add_column(ColumnDef::new(Projects::CopiedFrom).uuid().null())
+                    .to_owned(),
+            )
+            .await?;
+        let db = manager.get_connection();
+        db.execute_unprepared(&format!(
+            "UPDATE projects SET owner_id = ( \
+                 SELECT users.owner_id FROM ({PROJECT_USERS}) AS users \
+                 WHERE users.project_id = projects.id \
+                 ORDER BY users.created_at LIMIT 1 \
+             )"
+        ))
+        .await?;
+        db.execute_unprepared(&format!(
+            "INSERT INTO projects (owner_id, copied_from, name, color, archived, created_at, updated_at) \
+             SELECT DISTINCT users.owner_id, projects.id, projects.name, projects.color, \
+                 projects.archived, projects.created_at, projects.updated_at \
+             FROM projects \
+             JOIN ({PROJECT_USERS}) AS users ON users.project_id = projects.id \
+             WHERE users.owner_id <> projects.owner_id"
+        ))
+        .await?;
+        for table in ["todos", "share_grants"] {
+            db.execute_unprepared(&format!(
+                "UPDATE {table} SET project_id = copy.id \
+                 FROM projects AS copy \
+                 WHERE copy.copied_from = {table}.project_id \
+                   AND copy.owner_id = {table}.owner_id"
+            ))
+            .await?;
+        }
+        assign_to_legacy_owner(manager, "projects").await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Projects::Table)
+                    .drop_column(Projects::CopiedFrom)
+                    .modify_column(ColumnDef::new(Projects::OwnerId).uuid().not_null())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_projects_owner_id")
+                            .from_tbl(Projects::Table)
+                            .from_col(Projects::OwnerId)
+                            .to_tbl(Users::Table)
+                            .to_col(Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_projects_owner_id_name")
+                    .table(Projects::Table)
+                    .col(Projects::OwnerId)
+                    .col(Projects::Name)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_projects_owner_id_name")
+                    .table(Projects::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Projects::Table)
+                    .drop_foreign_key(Alias::new("fk_projects_owner_id"))
+                    .drop_column(Projects::OwnerId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Projects {
+    Table,
+    OwnerId,
+    CopiedFrom,
+    Name,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,52 @@
+use sea_orm_migration::prelude::*;
+
+/// Projects still holding todos can no longer be deleted, instead of silently
+/// taking their todos out of the project.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        replace_project_foreign_key(manager, ForeignKeyAction::NoAction).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        replace_project_foreign_key(manager, ForeignKeyAction::SetNull).await
+    }
+}
+
+async fn replace_project_foreign_key(
+    manager: &SchemaManager<'_>,
+    on_delete: ForeignKeyAction,
+) -> Result<(), DbErr> {
+    manager
+        .alter_table(
+            Table::alter()
+                .table(Todos::Table)
+                .drop_foreign_key(Alias::new("fk_todos_project_id"))
+                .add_foreign_key(
+                    TableForeignKey::new()
+                        .name("fk_todos_project_id")
+                        .from_tbl(Todos::Table)
+                        .from_col(Todos::ProjectId)
+                        .to_tbl(Projects::Table)
+                        .to_col(Projects::Id)
+                        .on_delete(on_delete),
+                )
+                .to_owned(),
+        )
+        .await
+}
+
+#[derive(DeriveIden)]
+enum Todos {
+    Table,
+    ProjectId,
+}
+
+#[derive(DeriveIden)]
+enum Projects {
+    Table,
+    Id,
+}
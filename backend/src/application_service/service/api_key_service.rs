@@ -0,0 +1,17 @@
+/// A freshly generated API key.
+#[derive(Debug, Clone)]
+pub struct GeneratedApiKey {
+    /// The full key, handed to the client once and never stored.
+    pub key: String,
+    pub prefix: String,
+    pub hash: String,
+}
+
+pub trait ApiKeyService: Send + Sync {
+    fn generate(&self) -> GeneratedApiKey;
+    /// Extracts the prefix of a presented key, or `None` when it is not shaped
+    /// like one of our keys.
+    fn prefix_of<'a>(&self, key: &'a str) -> Option<&'a str>;
+    /// Whether `key` is the key `hash` was computed from.
+    fn verify(&self, key: &str, hash: &str) -> bool;
+}
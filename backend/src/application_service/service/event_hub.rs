@@ -0,0 +1,42 @@
+use std::collections::VecDeque;
+
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::domain::models::todo_event::TodoEvent;
+
+/// Fans committed todo events out to the live subscribers of this process.
+/// Unlike the outbox, delivery is best-effort: subscribers that fall behind
+/// lose their subscription and resume from the replay buffer.
+pub trait EventHub: Send + Sync {
+    /// Ignores events published recently, since each one reaches the hub
+    /// both from this process and from the database.
+    fn publish(&self, event: TodoEvent);
+    /// Starts with the buffered events that followed `last_event_id`, or with
+    /// the whole buffer when that event is no longer in it.
+    fn subscribe(&self, last_event_id: Option<Uuid>) -> EventSubscription;
+}
+
+pub struct EventSubscription {
+    replay: VecDeque<TodoEvent>,
+    receiver: broadcast::Receiver<TodoEvent>,
+}
+
+impl EventSubscription {
+    pub fn new(replay: Vec<TodoEvent>, receiver: broadcast::Receiver<TodoEvent>) -> Self {
+        Self {
+            replay: replay.into(),
+            receiver,
+        }
+    }
+
+    /// The next event, or `None` once the hub is gone or events were dropped
+    /// because the subscriber fell behind. It should then subscribe again
+    /// from the last event it saw.
+    pub async fn recv(&mut self) -> Option<TodoEvent> {
+        if let Some(event) = self.replay.pop_front() {
+            return Some(event);
+        }
+        self.receiver.recv().await.ok()
+    }
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+use crate::{
+    application_service::usecase::errors::UsecaseError,
+    domain::{models::todo_event::TodoEvent, repositories::conn::Conn},
+};
+
+/// Destination of the events drained from the outbox. Delivery is
+/// at-least-once, so implementations may see the same event more than once.
+#[async_trait]
+pub trait EventPublisher: Send + Sync {
+    /// `conn` is the dispatcher's transaction, for publishers that hand the
+    /// event on to other tables: their writes commit with the outbox update.
+    async fn publish<C>(&self, conn: &C, event: &TodoEvent) -> Result<(), UsecaseError>
+    where
+        C: Conn;
+}
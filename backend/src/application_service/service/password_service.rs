@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+
+use crate::application_service::usecase::errors::UsecaseError;
+
+#[async_trait]
+pub trait PasswordService: Send + Sync {
+    /// Hashes the password with a fresh salt into a self-describing string.
+    async fn hash(&self, password: String) -> Result<String, UsecaseError>;
+    /// Whether `password` matches `hash`; errors only when `hash` is malformed.
+    async fn verify(&self, password: String, hash: String) -> Result<bool, UsecaseError>;
+}
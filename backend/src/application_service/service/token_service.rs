@@ -0,0 +1,26 @@
+use uuid::Uuid;
+
+use crate::application_service::usecase::errors::UsecaseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Short-lived; authenticates API requests.
+    Access,
+    /// Long-lived; only good for obtaining a new token pair.
+    Refresh,
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenPair {
+    pub access_token: String,
+    pub refresh_token: String,
+    /// Lifetime of the access token in seconds.
+    pub expires_in: u64,
+}
+
+pub trait TokenService: Send + Sync {
+    fn issue(&self, user_id: Uuid) -> Result<TokenPair, UsecaseError>;
+    /// Returns the user the token was issued to. Expired, tampered or foreign
+    /// tokens and tokens of the other kind fail with `Unauthorized`.
+    fn verify(&self, token: &str, kind: TokenKind) -> Result<Uuid, UsecaseError>;
+}
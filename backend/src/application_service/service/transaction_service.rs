@@ -15,6 +15,12 @@ pub enum TransactionError {
     Conflict(String),
     #[error("TransactionError: NotFound({0})")]
     NotFound(String),
+    #[error("TransactionError: PreconditionFailed({0})")]
+    PreconditionFailed(String),
+    #[error("TransactionError: Forbidden({0})")]
+    Forbidden(String),
+    #[error("TransactionError: Validation({0})")]
+    Validation(String),
 }
 
 impl From<RepositoryError> for TransactionError {
@@ -30,7 +36,9 @@ impl From<RepositoryError> for TransactionError {
 impl From<DomainError> for TransactionError {
     fn from(err: DomainError) -> Self {
         match err {
+            DomainError::NotFound(msg) => TransactionError::NotFound(msg),
             DomainError::Conflict(msg) => TransactionError::Conflict(msg),
+            DomainError::Validation(msg) => TransactionError::Validation(msg),
             DomainError::Unexpected(msg) => TransactionError::Unexpected(msg),
         }
     }
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+use crate::{
+    application_service::usecase::errors::UsecaseError,
+    domain::models::webhook::{Webhook, WebhookDelivery},
+};
+
+#[async_trait]
+pub trait WebhookSender: Send + Sync {
+    /// Posts the delivery's payload to the webhook, signed with its secret.
+    /// Returns the response status, whatever it is; errors mean no response came back.
+    async fn send(
+        &self,
+        webhook: &Webhook,
+        delivery: &WebhookDelivery,
+    ) -> Result<u16, UsecaseError>;
+}
@@ -1,2 +1,12 @@
+pub mod api_key_usecase;
+pub mod audit_usecase;
+pub mod auth_usecase;
+pub mod dependency_usecase;
 pub mod errors;
+pub mod idempotency_usecase;
+pub mod outbox_usecase;
+pub mod project_usecase;
+pub mod share_usecase;
+pub mod tag_usecase;
 pub mod todo_usecase;
+pub mod webhook_usecase;
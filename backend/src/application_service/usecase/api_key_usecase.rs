@@ -0,0 +1,429 @@
+use std::sync::Arc;
+
+use crate::{
+    application_service::{
+        service::{
+            api_key_service::ApiKeyService,
+            transaction_service::{TransactionError, TransactionService},
+        },
+        usecase::errors::UsecaseError,
+    },
+    domain::{
+        models::api_key::{ApiKey, ApiKeyScope},
+        repositories::{api_key_repository::ApiKeyRepository, conn::Conn, errors::RepositoryError},
+    },
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+const INVALID_API_KEY: &str = "API key is invalid";
+
+#[derive(Debug, Clone)]
+pub struct ApiKeyInput {
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// A new API key together with the only copy of its secret.
+#[derive(Debug, Clone)]
+pub struct CreatedApiKey {
+    pub api_key: ApiKey,
+    pub key: String,
+}
+
+#[async_trait]
+pub trait ApiKeyUsecase: Send + Sync + 'static {
+    async fn get_api_keys<C>(&self, conn: &C, user_id: Uuid) -> Result<Vec<ApiKey>, UsecaseError>
+    where
+        C: Conn;
+    async fn create_api_key<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        input: ApiKeyInput,
+    ) -> Result<CreatedApiKey, UsecaseError>
+    where
+        C: Conn;
+    async fn revoke_api_key<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn;
+    /// Resolves a presented key to the key it belongs to and records the use.
+    /// Unknown, revoked and expired keys fail with `Unauthorized`.
+    async fn authenticate<C>(&self, conn: &C, key: &str) -> Result<ApiKey, UsecaseError>
+    where
+        C: Conn;
+}
+
+#[derive(Clone)]
+pub struct ApiKeyUsecaseImpl<A, K, T> {
+    api_key_repository: Arc<A>,
+    api_key_service: Arc<K>,
+    transaction_service: Arc<T>,
+}
+
+impl<A, K, T> ApiKeyUsecaseImpl<A, K, T> {
+    pub fn new(api_key_repository: A, api_key_service: K, transaction_service: T) -> Self {
+        Self {
+            api_key_repository: Arc::new(api_key_repository),
+            api_key_service: Arc::new(api_key_service),
+            transaction_service: Arc::new(transaction_service),
+        }
+    }
+}
+
+#[async_trait]
+impl<A, K, T> ApiKeyUsecase for ApiKeyUsecaseImpl<A, K, T>
+where
+    A: ApiKeyRepository + Send + Sync + 'static,
+    K: ApiKeyService + Send + Sync + 'static,
+    T: TransactionService + Send + Sync + 'static,
+{
+    async fn get_api_keys<C>(&self, conn: &C, user_id: Uuid) -> Result<Vec<ApiKey>, UsecaseError>
+    where
+        C: Conn,
+    {
+        let api_keys = self.api_key_repository.find_all(conn, user_id).await?;
+        Ok(api_keys)
+    }
+
+    async fn create_api_key<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        input: ApiKeyInput,
+    ) -> Result<CreatedApiKey, UsecaseError>
+    where
+        C: Conn,
+    {
+        let generated = self.api_key_service.generate();
+        let api_key = ApiKey::new(
+            user_id,
+            input.name,
+            generated.prefix,
+            generated.hash,
+            input.scopes,
+            input.expires_at,
+        )?;
+        let api_key = self.api_key_repository.create(conn, api_key).await?;
+        Ok(CreatedApiKey {
+            api_key,
+            key: generated.key,
+        })
+    }
+
+    async fn revoke_api_key<C>(&self, conn: &C, user_id: Uuid, id: Uuid) -> Result<(), UsecaseError>
+    where
+        C: Conn,
+    {
+        let api_key_repository = self.api_key_repository.clone();
+        self.transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let mut api_key = api_key_repository.find_by_id(tx, user_id, id).await?;
+                    api_key.revoke()?;
+                    api_key_repository.update(tx, api_key).await?;
+                    Ok::<(), TransactionError>(())
+                })
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn authenticate<C>(&self, conn: &C, key: &str) -> Result<ApiKey, UsecaseError>
+    where
+        C: Conn,
+    {
+        let prefix = self
+            .api_key_service
+            .prefix_of(key)
+            .ok_or(UsecaseError::Unauthorized(INVALID_API_KEY.into()))?;
+        let mut api_key = match self.api_key_repository.find_by_prefix(conn, prefix).await {
+            Ok(api_key) => api_key,
+            Err(RepositoryError::NotFound(_)) => {
+                return Err(UsecaseError::Unauthorized(INVALID_API_KEY.into()));
+            }
+            Err(err) => return Err(err.into()),
+        };
+        if !self.api_key_service.verify(key, &api_key.key_hash) {
+            return Err(UsecaseError::Unauthorized(INVALID_API_KEY.into()));
+        }
+        let now = Utc::now();
+        if api_key.is_revoked() {
+            return Err(UsecaseError::Unauthorized(
+                "API key has been revoked".into(),
+            ));
+        }
+        if api_key.is_expired(now) {
+            return Err(UsecaseError::Unauthorized("API key has expired".into()));
+        }
+        self.api_key_repository.touch(conn, api_key.id, now).await?;
+        api_key.last_used_at = Some(now);
+        Ok(api_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{
+        application_service::service::api_key_service::GeneratedApiKey,
+        domain::repositories::conn::tests::MockConn,
+    };
+
+    use super::*;
+    use std::sync::{
+        Mutex,
+        atomic::{AtomicU32, Ordering},
+    };
+
+    #[derive(Default)]
+    struct MockApiKeyRepository {
+        api_keys: Mutex<Vec<ApiKey>>,
+    }
+
+    #[async_trait]
+    impl ApiKeyRepository for MockApiKeyRepository {
+        async fn find_all<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+        ) -> Result<Vec<ApiKey>, RepositoryError> {
+            Ok(self
+                .api_keys
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|api_key| api_key.user_id == user_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_id<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            id: Uuid,
+        ) -> Result<ApiKey, RepositoryError> {
+            self.api_keys
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|api_key| api_key.user_id == user_id && api_key.id == id)
+                .cloned()
+                .ok_or(RepositoryError::NotFound(format!(
+                    "API key with id {} not found",
+                    id
+                )))
+        }
+
+        async fn find_by_prefix<C>(
+            &self,
+            _conn: &C,
+            prefix: &str,
+        ) -> Result<ApiKey, RepositoryError> {
+            self.api_keys
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|api_key| api_key.prefix == prefix)
+                .cloned()
+                .ok_or(RepositoryError::NotFound(format!(
+                    "API key with prefix {} not found",
+                    prefix
+                )))
+        }
+
+        async fn create<C>(&self, _conn: &C, api_key: ApiKey) -> Result<ApiKey, RepositoryError> {
+            self.api_keys.lock().unwrap().push(api_key.clone());
+            Ok(api_key)
+        }
+
+        async fn update<C>(&self, _conn: &C, api_key: ApiKey) -> Result<ApiKey, RepositoryError> {
+            let mut api_keys = self.api_keys.lock().unwrap();
+            let stored = api_keys
+                .iter_mut()
+                .find(|stored| stored.id == api_key.id)
+                .ok_or(RepositoryError::NotFound(format!(
+                    "API key with id {} not found",
+                    api_key.id
+                )))?;
+            *stored = api_key.clone();
+            Ok(api_key)
+        }
+
+        async fn touch<C>(
+            &self,
+            _conn: &C,
+            id: Uuid,
+            used_at: DateTime<Utc>,
+        ) -> Result<(), RepositoryError> {
+            if let Some(api_key) = self
+                .api_keys
+                .lock()
+                .unwrap()
+                .iter_mut()
+                .find(|api_key| api_key.id == id)
+            {
+                api_key.last_used_at = Some(used_at);
+            }
+            Ok(())
+        }
+    }
+
+    /// Keys are `key-<n>` with prefix `<n>` and hash `hash:<key>`.
+    #[derive(Default)]
+    struct MockApiKeyService {
+        counter: AtomicU32,
+    }
+
+    impl ApiKeyService for MockApiKeyService {
+        fn generate(&self) -> GeneratedApiKey {
+            let n = self.counter.fetch_add(1, Ordering::SeqCst);
+            let key = format!("key-{}", n);
+            GeneratedApiKey {
+                hash: format!("hash:{}", key),
+                key,
+                prefix: n.to_string(),
+            }
+        }
+
+        fn prefix_of<'a>(&self, key: &'a str) -> Option<&'a str> {
+            key.strip_prefix("key-")
+        }
+
+        fn verify(&self, key: &str, hash: &str) -> bool {
+            hash == format!("hash:{}", key)
+        }
+    }
+
+    struct MockTransactionService;
+
+    #[async_trait]
+    impl TransactionService for MockTransactionService {
+        type Tx<'a>
+            = MockConn
+        where
+            Self: 'a;
+        async fn run<C, F, R>(&self, _: &C, f: F) -> Result<R, TransactionError>
+        where
+            C: Conn,
+            F: for<'tx> FnOnce(
+                    &'tx Self::Tx<'tx>,
+                ) -> std::pin::Pin<
+                    Box<dyn std::future::Future<Output = Result<R, TransactionError>> + Send + 'tx>,
+                > + Send,
+            R: Send,
+        {
+            f(&MockConn).await
+        }
+    }
+
+    fn usecase()
+    -> ApiKeyUsecaseImpl<MockApiKeyRepository, MockApiKeyService, MockTransactionService> {
+        ApiKeyUsecaseImpl::new(
+            MockApiKeyRepository::default(),
+            MockApiKeyService::default(),
+            MockTransactionService,
+        )
+    }
+
+    fn input(scopes: Vec<ApiKeyScope>) -> ApiKeyInput {
+        ApiKeyInput {
+            name: "CI".into(),
+            scopes,
+            expires_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_api_key_usecase_impl_create_and_list() {
+        let usecase = usecase();
+        let user_id = Uuid::now_v7();
+
+        let created = usecase
+            .create_api_key(&MockConn, user_id, input(vec![ApiKeyScope::TodosRead]))
+            .await
+            .unwrap();
+        assert_eq!(created.key, "key-0");
+        assert_eq!(created.api_key.prefix, "0");
+        assert_eq!(created.api_key.key_hash, "hash:key-0");
+
+        let api_keys = usecase.get_api_keys(&MockConn, user_id).await.unwrap();
+        assert_eq!(api_keys.len(), 1);
+        assert!(
+            usecase
+                .get_api_keys(&MockConn, Uuid::now_v7())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let result = usecase
+            .create_api_key(&MockConn, user_id, input(vec![]))
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_api_key_usecase_impl_authenticate() {
+        let usecase = usecase();
+        let user_id = Uuid::now_v7();
+        let created = usecase
+            .create_api_key(&MockConn, user_id, input(vec![ApiKeyScope::TodosWrite]))
+            .await
+            .unwrap();
+
+        let api_key = usecase.authenticate(&MockConn, &created.key).await.unwrap();
+        assert_eq!(api_key.user_id, user_id);
+        assert!(api_key.last_used_at.is_some());
+        let stored = usecase.get_api_keys(&MockConn, user_id).await.unwrap();
+        assert_eq!(stored[0].last_used_at, api_key.last_used_at);
+
+        for key in ["key-7", "key-", "garbage"] {
+            let result = usecase.authenticate(&MockConn, key).await;
+            assert!(
+                matches!(result, Err(UsecaseError::Unauthorized(msg)) if msg == INVALID_API_KEY)
+            );
+        }
+
+        let mut expired = stored[0].clone();
+        expired.expires_at = Some(Utc::now() - chrono::Duration::seconds(1));
+        usecase
+            .api_key_repository
+            .update(&MockConn, expired)
+            .await
+            .unwrap();
+        let result = usecase.authenticate(&MockConn, &created.key).await;
+        assert!(matches!(result, Err(UsecaseError::Unauthorized(msg)) if msg.contains("expired")));
+    }
+
+    #[tokio::test]
+    async fn test_api_key_usecase_impl_revoke() {
+        let usecase = usecase();
+        let user_id = Uuid::now_v7();
+        let created = usecase
+            .create_api_key(&MockConn, user_id, input(vec![ApiKeyScope::TodosRead]))
+            .await
+            .unwrap();
+        let id = created.api_key.id;
+
+        let result = usecase.revoke_api_key(&MockConn, Uuid::now_v7(), id).await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+
+        usecase
+            .revoke_api_key(&MockConn, user_id, id)
+            .await
+            .unwrap();
+        let result = usecase.authenticate(&MockConn, &created.key).await;
+        assert!(matches!(result, Err(UsecaseError::Unauthorized(msg)) if msg.contains("revoked")));
+        let result = usecase.revoke_api_key(&MockConn, user_id, id).await;
+        assert!(matches!(result, Err(UsecaseError::Conflict(_))));
+    }
+}
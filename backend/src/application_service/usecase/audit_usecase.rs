@@ -0,0 +1,346 @@
+use std::sync::Arc;
+
+use crate::{
+    application_service::usecase::errors::UsecaseError,
+    domain::{
+        models::audit::{AuditEvent, AuditQuery},
+        repositories::{
+            audit_repository::AuditRepository, conn::Conn, errors::RepositoryError,
+            todo_repository::TodoRepository,
+        },
+    },
+};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct AuditPage {
+    pub events: Vec<AuditEvent>,
+    pub next_cursor: Option<Uuid>,
+}
+
+fn into_page(mut events: Vec<AuditEvent>, limit: u64) -> AuditPage {
+    let next_cursor = if events.len() as u64 > limit {
+        events.truncate(limit as usize);
+        events.last().map(|event| event.id)
+    } else {
+        None
+    };
+    AuditPage {
+        events,
+        next_cursor,
+    }
+}
+
+/// Read side of the audit log written by the todo usecase.
+#[async_trait]
+pub trait AuditUsecase: Send + Sync + 'static {
+    /// Events on a todo the user can see, including todos in the trash.
+    async fn get_todo_history<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        todo_id: Uuid,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<AuditPage, UsecaseError>
+    where
+        C: Conn;
+    /// Events on the user's own todos, whoever made them; purged todos included.
+    async fn get_audit_events<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        query: AuditQuery,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<AuditPage, UsecaseError>
+    where
+        C: Conn;
+}
+
+#[derive(Clone)]
+pub struct AuditUsecaseImpl<A, R> {
+    audit_repository: Arc<A>,
+    todo_repository: Arc<R>,
+}
+
+impl<A, R> AuditUsecaseImpl<A, R> {
+    pub fn new(audit_repository: A, todo_repository: R) -> Self {
+        Self {
+            audit_repository: Arc::new(audit_repository),
+            todo_repository: Arc::new(todo_repository),
+        }
+    }
+}
+
+#[async_trait]
+impl<A, R> AuditUsecase for AuditUsecaseImpl<A, R>
+where
+    A: AuditRepository + Send + Sync + 'static,
+    R: TodoRepository + Send + Sync + 'static,
+{
+    async fn get_todo_history<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        todo_id: Uuid,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<AuditPage, UsecaseError>
+    where
+        C: Conn,
+    {
+        match self
+            .todo_repository
+            .find_by_id(conn, user_id, todo_id)
+            .await
+        {
+            Ok(_) => {}
+            Err(RepositoryError::NotFound(_)) => {
+                self.todo_repository
+                    .find_trashed_by_id(conn, user_id, todo_id)
+                    .await?;
+            }
+            Err(err) => return Err(err.into()),
+        }
+        // Fetch one extra row to find out whether another page follows.
+        let events = self
+            .audit_repository
+            .find_by_todo(conn, todo_id, after, limit + 1)
+            .await?;
+        Ok(into_page(events, limit))
+    }
+
+    async fn get_audit_events<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        query: AuditQuery,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<AuditPage, UsecaseError>
+    where
+        C: Conn,
+    {
+        if let (Some(from), Some(to)) = (query.from, query.to)
+            && from >= to
+        {
+            return Err(UsecaseError::Validation(
+                "The start of the time range must be before its end".into(),
+            ));
+        }
+        let events = self
+            .audit_repository
+            .find_by_query(conn, user_id, query, after, limit + 1)
+            .await?;
+        Ok(into_page(events, limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::domain::models::audit::AuditAction;
+    use crate::domain::models::todo::Todo;
+    use crate::domain::repositories::conn::tests::MockConn;
+    use crate::domain::repositories::todo_repository::tests::MockTodoRepository;
+
+    use super::*;
+    use chrono::{Duration, Utc};
+    use std::sync::Mutex;
+
+    const OWNER_ID: Uuid = Uuid::from_u128(0xe1e2e3e4_f1f2_a1a2_b1b2_b3b4b5b6b7b8);
+
+    /// Shared by both mock repositories.
+    #[derive(Clone)]
+    struct Store {
+        todo: Todo,
+        trashed: Todo,
+        events: Arc<Mutex<Vec<AuditEvent>>>,
+    }
+
+    impl Store {
+        fn new() -> Self {
+            let todo = Todo::new(OWNER_ID, "Audited".into(), None);
+            let mut trashed = Todo::new(OWNER_ID, "Trashed".into(), None);
+            trashed.trash().unwrap();
+            let mut events = vec![];
+            for _ in 0..3 {
+                events.push(AuditEvent::new(
+                    OWNER_ID,
+                    AuditAction::Updated,
+                    Some(&todo),
+                    &todo,
+                ));
+            }
+            events.push(AuditEvent::new(
+                OWNER_ID,
+                AuditAction::Deleted,
+                None,
+                &trashed,
+            ));
+            Self {
+                todo,
+                trashed,
+                events: Arc::new(Mutex::new(events)),
+            }
+        }
+    }
+
+    struct MockAuditRepository(Store);
+
+    #[async_trait]
+    impl AuditRepository for MockAuditRepository {
+        async fn find_by_todo<C>(
+            &self,
+            _conn: &C,
+            todo_id: Uuid,
+            after: Option<Uuid>,
+            limit: u64,
+        ) -> Result<Vec<AuditEvent>, RepositoryError> {
+            Ok(self
+                .0
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|event| event.todo_id == todo_id)
+                .filter(|event| after.is_none_or(|after| event.id > after))
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_query<C>(
+            &self,
+            _conn: &C,
+            owner_id: Uuid,
+            query: AuditQuery,
+            after: Option<Uuid>,
+            limit: u64,
+        ) -> Result<Vec<AuditEvent>, RepositoryError> {
+            Ok(self
+                .0
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|event| event.owner_id == owner_id)
+                .filter(|event| query.actor_id.is_none_or(|actor| event.actor_id == actor))
+                .filter(|event| query.from.is_none_or(|from| event.created_at >= from))
+                .filter(|event| query.to.is_none_or(|to| event.created_at < to))
+                .filter(|event| after.is_none_or(|after| event.id > after))
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+
+        async fn create<C>(
+            &self,
+            _conn: &C,
+            event: AuditEvent,
+        ) -> Result<AuditEvent, RepositoryError> {
+            self.0.events.lock().unwrap().push(event.clone());
+            Ok(event)
+        }
+    }
+
+    fn usecase(store: &Store) -> AuditUsecaseImpl<MockAuditRepository, MockTodoRepository> {
+        AuditUsecaseImpl::new(
+            MockAuditRepository(store.clone()),
+            MockTodoRepository::new(vec![store.todo.clone(), store.trashed.clone()]),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_audit_usecase_impl_get_todo_history() {
+        let store = Store::new();
+        let usecase = usecase(&store);
+        let conn = MockConn {};
+
+        let page = usecase
+            .get_todo_history(&conn, OWNER_ID, store.todo.id, None, 2)
+            .await
+            .unwrap();
+        assert_eq!(page.events.len(), 2);
+        let next = usecase
+            .get_todo_history(&conn, OWNER_ID, store.todo.id, page.next_cursor, 2)
+            .await
+            .unwrap();
+        assert_eq!(next.events.len(), 1);
+        assert!(next.next_cursor.is_none());
+
+        // Todos in the trash keep their history
+        let page = usecase
+            .get_todo_history(&conn, OWNER_ID, store.trashed.id, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(page.events[0].action, AuditAction::Deleted);
+
+        // Other users cannot read the history of todos they cannot see
+        let result = usecase
+            .get_todo_history(&conn, Uuid::now_v7(), store.todo.id, None, 10)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_audit_usecase_impl_get_audit_events() {
+        let store = Store::new();
+        let usecase = usecase(&store);
+        let conn = MockConn {};
+        let editor_id = Uuid::now_v7();
+        store.events.lock().unwrap().push(AuditEvent::new(
+            editor_id,
+            AuditAction::Completed,
+            Some(&store.todo),
+            &store.todo,
+        ));
+
+        let page = usecase
+            .get_audit_events(&conn, OWNER_ID, AuditQuery::default(), None, 10)
+            .await
+            .unwrap();
+        assert_eq!(page.events.len(), 5);
+
+        let query = AuditQuery {
+            actor_id: Some(editor_id),
+            ..AuditQuery::default()
+        };
+        let page = usecase
+            .get_audit_events(&conn, OWNER_ID, query, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(page.events.len(), 1);
+        assert_eq!(page.events[0].action, AuditAction::Completed);
+
+        let query = AuditQuery {
+            from: Some(Utc::now() + Duration::hours(1)),
+            ..AuditQuery::default()
+        };
+        let page = usecase
+            .get_audit_events(&conn, OWNER_ID, query, None, 10)
+            .await
+            .unwrap();
+        assert!(page.events.is_empty());
+
+        // Events on other users' todos stay hidden
+        let page = usecase
+            .get_audit_events(&conn, editor_id, AuditQuery::default(), None, 10)
+            .await
+            .unwrap();
+        assert!(page.events.is_empty());
+
+        let now = Utc::now();
+        let query = AuditQuery {
+            from: Some(now),
+            to: Some(now),
+            ..AuditQuery::default()
+        };
+        let result = usecase
+            .get_audit_events(&conn, OWNER_ID, query, None, 10)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+}
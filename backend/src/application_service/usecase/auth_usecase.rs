@@ -0,0 +1,350 @@
+use std::sync::Arc;
+
+use crate::{
+    application_service::{
+        service::{
+            password_service::PasswordService,
+            token_service::{TokenKind, TokenPair, TokenService},
+        },
+        usecase::errors::UsecaseError,
+    },
+    domain::{
+        models::user::{User, normalize_email},
+        repositories::{conn::Conn, errors::RepositoryError, user_repository::UserRepository},
+    },
+};
+use async_trait::async_trait;
+use tokio::sync::OnceCell;
+
+const INVALID_CREDENTIALS: &str = "Invalid email or password";
+/// Checked against when the email is unknown, so the reply takes as long as
+/// for a wrong password.
+const DUMMY_PASSWORD: &str = "dummy-password";
+
+#[async_trait]
+pub trait AuthUsecase: Send + Sync + 'static {
+    async fn register<C>(
+        &self,
+        conn: &C,
+        email: String,
+        password: String,
+    ) -> Result<User, UsecaseError>
+    where
+        C: Conn;
+    /// Unknown emails and wrong passwords fail alike, without telling which.
+    async fn login<C>(
+        &self,
+        conn: &C,
+        email: String,
+        password: String,
+    ) -> Result<TokenPair, UsecaseError>
+    where
+        C: Conn;
+    /// Trades a refresh token for a new token pair.
+    async fn refresh<C>(&self, conn: &C, refresh_token: String) -> Result<TokenPair, UsecaseError>
+    where
+        C: Conn;
+}
+
+#[derive(Clone)]
+pub struct AuthUsecaseImpl<U, P, K> {
+    user_repository: Arc<U>,
+    password_service: Arc<P>,
+    token_service: Arc<K>,
+    /// Hash of `DUMMY_PASSWORD`, made on the first unknown email.
+    dummy_hash: Arc<OnceCell<String>>,
+}
+
+impl<U, P, K> AuthUsecaseImpl<U, P, K> {
+    pub fn new(user_repository: U, password_service: P, token_service: K) -> Self {
+        Self {
+            user_repository: Arc::new(user_repository),
+            password_service: Arc::new(password_service),
+            token_service: Arc::new(token_service),
+            dummy_hash: Arc::new(OnceCell::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl<U, P, K> AuthUsecase for AuthUsecaseImpl<U, P, K>
+where
+    U: UserRepository + Send + Sync + 'static,
+    P: PasswordService + Send + Sync + 'static,
+    K: TokenService + Send + Sync + 'static,
+{
+    async fn register<C>(
+        &self,
+        conn: &C,
+        email: String,
+        password: String,
+    ) -> Result<User, UsecaseError>
+    where
+        C: Conn,
+    {
+        let password_hash = self.password_service.hash(password).await?;
+        let user = User::new(&email, password_hash)?;
+        let user = self.user_repository.create(conn, user).await?;
+        Ok(user)
+    }
+
+    async fn login<C>(
+        &self,
+        conn: &C,
+        email: String,
+        password: String,
+    ) -> Result<TokenPair, UsecaseError>
+    where
+        C: Conn,
+    {
+        let user = match self
+            .user_repository
+            .find_by_email(conn, &normalize_email(&email))
+            .await
+        {
+            Ok(user) => user,
+            Err(RepositoryError::NotFound(_)) => {
+                let dummy_hash = self
+                    .dummy_hash
+                    .get_or_try_init(|| self.password_service.hash(DUMMY_PASSWORD.into()))
+                    .await?;
+                self.password_service
+                    .verify(password, dummy_hash.clone())
+                    .await?;
+                return Err(UsecaseError::Unauthorized(INVALID_CREDENTIALS.into()));
+            }
+            Err(err) => return Err(err.into()),
+        };
+        if !self
+            .password_service
+            .verify(password, user.password_hash)
+            .await?
+        {
+            return Err(UsecaseError::Unauthorized(INVALID_CREDENTIALS.into()));
+        }
+        self.token_service.issue(user.id)
+    }
+
+    async fn refresh<C>(&self, conn: &C, refresh_token: String) -> Result<TokenPair, UsecaseError>
+    where
+        C: Conn,
+    {
+        let user_id = self
+            .token_service
+            .verify(&refresh_token, TokenKind::Refresh)?;
+        // Tokens outlive deleted accounts.
+        let user = match self.user_repository.find_by_id(conn, user_id).await {
+            Ok(user) => user,
+            Err(RepositoryError::NotFound(_)) => {
+                return Err(UsecaseError::Unauthorized("User no longer exists".into()));
+            }
+            Err(err) => return Err(err.into()),
+        };
+        self.token_service.issue(user.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::domain::repositories::conn::tests::MockConn;
+
+    use super::*;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    #[derive(Default)]
+    struct MockUserRepository {
+        users: Mutex<Vec<User>>,
+    }
+
+    #[async_trait]
+    impl UserRepository for MockUserRepository {
+        async fn find_by_id<C>(&self, _conn: &C, id: Uuid) -> Result<User, RepositoryError> {
+            self.users
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|user| user.id == id)
+                .cloned()
+                .ok_or(RepositoryError::NotFound(format!(
+                    "User with id {} not found",
+                    id
+                )))
+        }
+
+        async fn find_by_email<C>(&self, _conn: &C, email: &str) -> Result<User, RepositoryError> {
+            self.users
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|user| user.email == email)
+                .cloned()
+                .ok_or(RepositoryError::NotFound(format!(
+                    "User with email {} not found",
+                    email
+                )))
+        }
+
+        async fn create<C>(&self, _conn: &C, user: User) -> Result<User, RepositoryError> {
+            let mut users = self.users.lock().unwrap();
+            if users.iter().any(|existing| existing.email == user.email) {
+                return Err(RepositoryError::Conflict(format!(
+                    "User with email {} already exists",
+                    user.email
+                )));
+            }
+            users.push(user.clone());
+            Ok(user)
+        }
+    }
+
+    /// Records the hashes passwords were checked against.
+    #[derive(Default)]
+    struct MockPasswordService {
+        verified: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl PasswordService for MockPasswordService {
+        async fn hash(&self, password: String) -> Result<String, UsecaseError> {
+            Ok(format!("hashed:{}", password))
+        }
+
+        async fn verify(&self, password: String, hash: String) -> Result<bool, UsecaseError> {
+            let matches = hash == format!("hashed:{}", password);
+            self.verified.lock().unwrap().push(hash);
+            Ok(matches)
+        }
+    }
+
+    /// Tokens are `<kind>:<user id>`.
+    struct MockTokenService;
+
+    impl TokenService for MockTokenService {
+        fn issue(&self, user_id: Uuid) -> Result<TokenPair, UsecaseError> {
+            Ok(TokenPair {
+                access_token: format!("access:{}", user_id),
+                refresh_token: format!("refresh:{}", user_id),
+                expires_in: 900,
+            })
+        }
+
+        fn verify(&self, token: &str, kind: TokenKind) -> Result<Uuid, UsecaseError> {
+            let prefix = match kind {
+                TokenKind::Access => "access:",
+                TokenKind::Refresh => "refresh:",
+            };
+            token
+                .strip_prefix(prefix)
+                .and_then(|id| id.parse().ok())
+                .ok_or(UsecaseError::Unauthorized("Token is invalid".into()))
+        }
+    }
+
+    fn usecase() -> AuthUsecaseImpl<MockUserRepository, MockPasswordService, MockTokenService> {
+        AuthUsecaseImpl::new(
+            MockUserRepository::default(),
+            MockPasswordService::default(),
+            MockTokenService,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_auth_usecase_impl_register() {
+        let usecase = usecase();
+
+        let user = usecase
+            .register(
+                &MockConn,
+                "Alice@Example.com".into(),
+                "secret-password".into(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(user.email, "alice@example.com");
+        assert_eq!(user.password_hash, "hashed:secret-password");
+
+        let duplicate = usecase
+            .register(
+                &MockConn,
+                "alice@example.com".into(),
+                "other-password".into(),
+            )
+            .await;
+        assert!(matches!(duplicate, Err(UsecaseError::Conflict(_))));
+
+        let invalid = usecase
+            .register(&MockConn, "alice".into(), "secret-password".into())
+            .await;
+        assert!(matches!(invalid, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_auth_usecase_impl_login() {
+        let usecase = usecase();
+        let user = usecase
+            .register(
+                &MockConn,
+                "alice@example.com".into(),
+                "secret-password".into(),
+            )
+            .await
+            .unwrap();
+
+        let tokens = usecase
+            .login(
+                &MockConn,
+                " ALICE@example.com".into(),
+                "secret-password".into(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(tokens.access_token, format!("access:{}", user.id));
+
+        for (email, password) in [
+            ("alice@example.com", "wrong-password"),
+            ("bob@example.com", "secret-password"),
+        ] {
+            let result = usecase
+                .login(&MockConn, email.into(), password.into())
+                .await;
+            assert!(
+                matches!(result, Err(UsecaseError::Unauthorized(msg)) if msg == INVALID_CREDENTIALS)
+            );
+        }
+        // The unknown email still had a password checked, against the dummy hash.
+        let verified = usecase.password_service.verified.lock().unwrap().clone();
+        assert_eq!(verified.len(), 3);
+        assert_eq!(verified[2], format!("hashed:{}", DUMMY_PASSWORD));
+    }
+
+    #[tokio::test]
+    async fn test_auth_usecase_impl_refresh() {
+        let usecase = usecase();
+        let user = usecase
+            .register(
+                &MockConn,
+                "alice@example.com".into(),
+                "secret-password".into(),
+            )
+            .await
+            .unwrap();
+
+        let tokens = usecase
+            .refresh(&MockConn, format!("refresh:{}", user.id))
+            .await
+            .unwrap();
+        assert_eq!(tokens.refresh_token, format!("refresh:{}", user.id));
+
+        let result = usecase
+            .refresh(&MockConn, format!("access:{}", user.id))
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Unauthorized(_))));
+
+        let result = usecase
+            .refresh(&MockConn, format!("refresh:{}", Uuid::now_v7()))
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Unauthorized(_))));
+    }
+}
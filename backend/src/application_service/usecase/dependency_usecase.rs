@@ -0,0 +1,493 @@
+use std::sync::Arc;
+
+use crate::{
+    application_service::{
+        service::transaction_service::{TransactionError, TransactionService},
+        usecase::{errors::UsecaseError, share_usecase::ensure_owner},
+    },
+    domain::{
+        models::{
+            dependency::{Dependency, DependencyGraph},
+            todo::Todo,
+        },
+        repositories::{
+            conn::Conn, dependency_repository::DependencyRepository,
+            todo_repository::TodoRepository,
+        },
+    },
+};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait DependencyUsecase: Send + Sync + 'static {
+    async fn get_blockers<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        todo_id: Uuid,
+    ) -> Result<Vec<Todo>, UsecaseError>
+    where
+        C: Conn;
+    /// Returns the todo's blockers after adding the edge. Edges that would close
+    /// a cycle are rejected with a conflict.
+    async fn add_blocker<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        todo_id: Uuid,
+        blocker_id: Uuid,
+    ) -> Result<Vec<Todo>, UsecaseError>
+    where
+        C: Conn;
+    async fn remove_blocker<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        todo_id: Uuid,
+        blocker_id: Uuid,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn;
+    async fn get_graph<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        todo_id: Uuid,
+    ) -> Result<DependencyGraph, UsecaseError>
+    where
+        C: Conn;
+}
+
+#[derive(Clone)]
+pub struct DependencyUsecaseImpl<D, R, T> {
+    dependency_repository: Arc<D>,
+    todo_repository: Arc<R>,
+    transaction_service: Arc<T>,
+}
+
+impl<D, R, T> DependencyUsecaseImpl<D, R, T> {
+    pub fn new(dependency_repository: D, todo_repository: R, transaction_service: T) -> Self {
+        Self {
+            dependency_repository: Arc::new(dependency_repository),
+            todo_repository: Arc::new(todo_repository),
+            transaction_service: Arc::new(transaction_service),
+        }
+    }
+}
+
+#[async_trait]
+impl<D, R, T> DependencyUsecase for DependencyUsecaseImpl<D, R, T>
+where
+    D: DependencyRepository + Send + Sync + 'static,
+    R: TodoRepository + Send + Sync + 'static,
+    T: TransactionService + Send + Sync + 'static,
+{
+    async fn get_blockers<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        todo_id: Uuid,
+    ) -> Result<Vec<Todo>, UsecaseError>
+    where
+        C: Conn,
+    {
+        self.todo_repository
+            .find_by_id(conn, owner_id, todo_id)
+            .await?;
+        let blockers = self
+            .dependency_repository
+            .find_blockers(conn, owner_id, todo_id)
+            .await?;
+        Ok(blockers)
+    }
+
+    async fn add_blocker<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        todo_id: Uuid,
+        blocker_id: Uuid,
+    ) -> Result<Vec<Todo>, UsecaseError>
+    where
+        C: Conn,
+    {
+        let dependency = Dependency::new(todo_id, blocker_id)?;
+        let dependency_repository = self.dependency_repository.clone();
+        let todo_repository = self.todo_repository.clone();
+        let blockers = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let todo = todo_repository.find_by_id(tx, owner_id, todo_id).await?;
+                    ensure_owner(&todo, owner_id)?;
+                    let blocker = todo_repository.find_by_id(tx, owner_id, blocker_id).await?;
+                    ensure_owner(&blocker, owner_id)?;
+                    dependency_repository.lock(tx).await?;
+                    let graph = dependency_repository
+                        .find_graph(tx, owner_id, blocker_id)
+                        .await?;
+                    graph.ensure_acyclic(&dependency)?;
+                    dependency_repository.create(tx, dependency).await?;
+                    let blockers = dependency_repository
+                        .find_blockers(tx, owner_id, todo_id)
+                        .await?;
+                    Ok::<Vec<Todo>, TransactionError>(blockers)
+                })
+            })
+            .await?;
+        Ok(blockers)
+    }
+
+    async fn remove_blocker<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        todo_id: Uuid,
+        blocker_id: Uuid,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn,
+    {
+        let todo = self
+            .todo_repository
+            .find_by_id(conn, owner_id, todo_id)
+            .await?;
+        ensure_owner(&todo, owner_id)?;
+        self.dependency_repository
+            .delete(conn, todo_id, blocker_id)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_graph<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        todo_id: Uuid,
+    ) -> Result<DependencyGraph, UsecaseError>
+    where
+        C: Conn,
+    {
+        self.todo_repository
+            .find_by_id(conn, owner_id, todo_id)
+            .await?;
+        let graph = self
+            .dependency_repository
+            .find_graph(conn, owner_id, todo_id)
+            .await?;
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::domain::models::share::{self, ShareGrant, ShareRole, ShareTarget};
+    use crate::domain::repositories::conn::tests::MockConn;
+    use crate::domain::repositories::errors::RepositoryError;
+    use crate::domain::repositories::todo_repository::tests::MockTodoRepository;
+
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    const OWNER_ID: Uuid = Uuid::from_u128(0xe1e2e3e4_f1f2_a1a2_b1b2_b3b4b5b6b7b8);
+
+    /// Shared by both mock repositories.
+    #[derive(Clone)]
+    struct Store {
+        todos: Arc<Mutex<Vec<Todo>>>,
+        grants: Arc<Mutex<Vec<ShareGrant>>>,
+        edges: Arc<Mutex<Vec<Dependency>>>,
+    }
+
+    impl Store {
+        fn new(count: usize) -> Self {
+            Self {
+                todos: Arc::new(Mutex::new(
+                    (0..count)
+                        .map(|i| Todo::new(OWNER_ID, format!("Todo {}", i), None))
+                        .collect(),
+                )),
+                grants: Arc::new(Mutex::new(vec![])),
+                edges: Arc::new(Mutex::new(vec![])),
+            }
+        }
+
+        fn id(&self, index: usize) -> Uuid {
+            self.todos.lock().unwrap()[index].id
+        }
+
+        /// Todos visible to the user, like the todo repository.
+        fn visible_todos(&self, user_id: Uuid) -> Vec<Todo> {
+            let grants = self.grants.lock().unwrap();
+            self.todos
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|todo| share::role_of(todo, user_id, &grants).is_some())
+                .cloned()
+                .collect()
+        }
+    }
+
+    struct MockDependencyRepository(Store);
+
+    #[async_trait]
+    impl DependencyRepository for MockDependencyRepository {
+        async fn find_blockers<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            todo_id: Uuid,
+        ) -> Result<Vec<Todo>, RepositoryError> {
+            let edges = self.0.edges.lock().unwrap();
+            Ok(self
+                .0
+                .visible_todos(user_id)
+                .into_iter()
+                .filter(|todo| {
+                    edges
+                        .iter()
+                        .any(|edge| edge.todo_id == todo_id && edge.blocker_id == todo.id)
+                })
+                .collect())
+        }
+
+        async fn find_graph<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            todo_id: Uuid,
+        ) -> Result<DependencyGraph, RepositoryError> {
+            let all_edges = self.0.edges.lock().unwrap().clone();
+            let mut edges: Vec<Dependency> = vec![];
+            let mut pending = vec![todo_id];
+            while let Some(id) = pending.pop() {
+                for edge in all_edges.iter().filter(|edge| edge.todo_id == id) {
+                    if !edges.contains(edge) {
+                        edges.push(edge.clone());
+                        pending.push(edge.blocker_id);
+                    }
+                }
+            }
+            let todos: Vec<Todo> = self
+                .0
+                .visible_todos(user_id)
+                .into_iter()
+                .filter(|todo| todo.id == todo_id || edges.iter().any(|e| e.blocker_id == todo.id))
+                .collect();
+            let visible = |id: Uuid| todos.iter().any(|todo| todo.id == id);
+            edges.retain(|edge| visible(edge.todo_id) && visible(edge.blocker_id));
+            Ok(DependencyGraph {
+                root: todo_id,
+                todos,
+                edges,
+            })
+        }
+
+        async fn create<C>(
+            &self,
+            _conn: &C,
+            dependency: Dependency,
+        ) -> Result<(), RepositoryError> {
+            let mut edges = self.0.edges.lock().unwrap();
+            if !edges.iter().any(|edge| {
+                edge.todo_id == dependency.todo_id && edge.blocker_id == dependency.blocker_id
+            }) {
+                edges.push(dependency);
+            }
+            Ok(())
+        }
+
+        async fn delete<C>(
+            &self,
+            _conn: &C,
+            todo_id: Uuid,
+            blocker_id: Uuid,
+        ) -> Result<(), RepositoryError> {
+            let mut edges = self.0.edges.lock().unwrap();
+            let index = edges
+                .iter()
+                .position(|edge| edge.todo_id == todo_id && edge.blocker_id == blocker_id)
+                .ok_or(RepositoryError::NotFound(format!(
+                    "Todo with id {} is not blocked by todo with id {}",
+                    todo_id, blocker_id
+                )))?;
+            edges.remove(index);
+            Ok(())
+        }
+
+        async fn lock<C>(&self, _conn: &C) -> Result<(), RepositoryError> {
+            Ok(())
+        }
+    }
+
+    struct MockTransactionService;
+
+    #[async_trait]
+    impl TransactionService for MockTransactionService {
+        type Tx<'a>
+            = MockConn
+        where
+            Self: 'a;
+        async fn run<C, F, T>(&self, _: &C, f: F) -> Result<T, TransactionError>
+        where
+            C: Conn,
+            F: for<'tx> FnOnce(
+                    &'tx Self::Tx<'tx>,
+                ) -> std::pin::Pin<
+                    Box<dyn std::future::Future<Output = Result<T, TransactionError>> + Send + 'tx>,
+                > + Send,
+            T: Send,
+        {
+            f(&MockConn).await
+        }
+    }
+
+    fn usecase(
+        store: &Store,
+    ) -> DependencyUsecaseImpl<MockDependencyRepository, MockTodoRepository, MockTransactionService>
+    {
+        DependencyUsecaseImpl::new(
+            MockDependencyRepository(store.clone()),
+            MockTodoRepository {
+                todos: store.todos.clone(),
+                grants: store.grants.clone(),
+            },
+            MockTransactionService,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_dependency_usecase_impl_add_blocker() {
+        let store = Store::new(3);
+        let usecase = usecase(&store);
+        let (a, b) = (store.id(0), store.id(1));
+
+        let blockers = usecase
+            .add_blocker(&MockConn, OWNER_ID, a, b)
+            .await
+            .unwrap();
+        assert_eq!(blockers.len(), 1);
+        assert_eq!(blockers[0].id, b);
+
+        // Adding the same edge twice is a no-op.
+        let blockers = usecase
+            .add_blocker(&MockConn, OWNER_ID, a, b)
+            .await
+            .unwrap();
+        assert_eq!(blockers.len(), 1);
+
+        let result = usecase.add_blocker(&MockConn, OWNER_ID, a, a).await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+        let result = usecase
+            .add_blocker(&MockConn, OWNER_ID, a, Uuid::now_v7())
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_dependency_usecase_impl_add_blocker_rejects_cycle() {
+        let store = Store::new(3);
+        let usecase = usecase(&store);
+        let (a, b, c) = (store.id(0), store.id(1), store.id(2));
+
+        usecase
+            .add_blocker(&MockConn, OWNER_ID, a, b)
+            .await
+            .unwrap();
+        usecase
+            .add_blocker(&MockConn, OWNER_ID, b, c)
+            .await
+            .unwrap();
+        let result = usecase.add_blocker(&MockConn, OWNER_ID, c, a).await;
+        assert!(matches!(result, Err(UsecaseError::Conflict(_))));
+        assert_eq!(store.edges.lock().unwrap().len(), 2);
+
+        // A diamond is not a cycle.
+        usecase
+            .add_blocker(&MockConn, OWNER_ID, a, c)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_dependency_usecase_impl_get_graph() {
+        let store = Store::new(3);
+        let usecase = usecase(&store);
+        let (a, b, c) = (store.id(0), store.id(1), store.id(2));
+        usecase
+            .add_blocker(&MockConn, OWNER_ID, a, b)
+            .await
+            .unwrap();
+        usecase
+            .add_blocker(&MockConn, OWNER_ID, b, c)
+            .await
+            .unwrap();
+
+        let graph = usecase.get_graph(&MockConn, OWNER_ID, a).await.unwrap();
+        assert_eq!(graph.root, a);
+        assert_eq!(graph.todos.len(), 3);
+        assert_eq!(graph.edges.len(), 2);
+
+        let graph = usecase.get_graph(&MockConn, OWNER_ID, c).await.unwrap();
+        assert_eq!(graph.todos.len(), 1);
+        assert!(graph.edges.is_empty());
+
+        // Viewers only see the part of the graph shared with them.
+        let viewer = Uuid::now_v7();
+        *store.grants.lock().unwrap() = [a, c]
+            .into_iter()
+            .map(|id| {
+                ShareGrant::new(OWNER_ID, viewer, ShareTarget::Todo(id), ShareRole::Viewer).unwrap()
+            })
+            .collect();
+        let graph = usecase.get_graph(&MockConn, viewer, a).await.unwrap();
+        assert_eq!(graph.todos.len(), 2);
+        assert!(graph.todos.iter().all(|todo| todo.id != b));
+        assert!(graph.edges.is_empty());
+        let blockers = usecase.get_blockers(&MockConn, viewer, a).await.unwrap();
+        assert!(blockers.is_empty());
+        let result = usecase.get_blockers(&MockConn, viewer, b).await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_dependency_usecase_impl_remove_blocker() {
+        let store = Store::new(2);
+        let usecase = usecase(&store);
+        let (a, b) = (store.id(0), store.id(1));
+        usecase
+            .add_blocker(&MockConn, OWNER_ID, a, b)
+            .await
+            .unwrap();
+
+        // Dependencies stay with the owner, even for editors of the todos.
+        let editor = Uuid::now_v7();
+        *usecase.todo_repository.grants.lock().unwrap() = [a, b]
+            .into_iter()
+            .map(|id| {
+                ShareGrant::new(OWNER_ID, editor, ShareTarget::Todo(id), ShareRole::Editor).unwrap()
+            })
+            .collect();
+        let result = usecase.remove_blocker(&MockConn, editor, a, b).await;
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+        let result = usecase.add_blocker(&MockConn, editor, b, a).await;
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+
+        usecase
+            .remove_blocker(&MockConn, OWNER_ID, a, b)
+            .await
+            .unwrap();
+        assert!(
+            usecase
+                .get_blockers(&MockConn, OWNER_ID, a)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let result = usecase.remove_blocker(&MockConn, OWNER_ID, a, b).await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+}
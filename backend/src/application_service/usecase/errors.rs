@@ -11,6 +11,14 @@ pub enum UsecaseError {
     NotFound(String),
     #[error("UsecaseError: Conflict({0})")]
     Conflict(String),
+    #[error("UsecaseError: Validation({0})")]
+    Validation(String),
+    #[error("UsecaseError: PreconditionFailed({0})")]
+    PreconditionFailed(String),
+    #[error("UsecaseError: Unauthorized({0})")]
+    Unauthorized(String),
+    #[error("UsecaseError: Forbidden({0})")]
+    Forbidden(String),
     #[error("UsecaseError: Unexpected({0})")]
     Unexpected(String),
 }
@@ -18,7 +26,9 @@ pub enum UsecaseError {
 impl From<DomainError> for UsecaseError {
     fn from(err: DomainError) -> Self {
         match err {
+            DomainError::NotFound(msg) => UsecaseError::NotFound(msg),
             DomainError::Conflict(msg) => UsecaseError::Conflict(msg),
+            DomainError::Validation(msg) => UsecaseError::Validation(msg),
             DomainError::Unexpected(msg) => UsecaseError::Unexpected(msg),
         }
     }
@@ -40,6 +50,9 @@ impl From<TransactionError> for UsecaseError {
             TransactionError::Conflict(msg) => UsecaseError::Conflict(msg),
             TransactionError::Unexpected(msg) => UsecaseError::Unexpected(msg),
             TransactionError::NotFound(msg) => UsecaseError::NotFound(msg),
+            TransactionError::PreconditionFailed(msg) => UsecaseError::PreconditionFailed(msg),
+            TransactionError::Forbidden(msg) => UsecaseError::Forbidden(msg),
+            TransactionError::Validation(msg) => UsecaseError::Validation(msg),
         }
     }
 }
@@ -0,0 +1,341 @@
+use std::sync::Arc;
+
+use crate::{
+    application_service::usecase::errors::UsecaseError,
+    domain::{
+        models::idempotency::{IdempotencyRecord, StoredResponse},
+        repositories::{
+            conn::Conn, errors::RepositoryError, idempotency_repository::IdempotencyRepository,
+        },
+    },
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+/// What to do with a request carrying an idempotency key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotencyOutcome {
+    /// The key is new; run the request and then complete or abandon the key.
+    Proceed,
+    /// The request already ran; send its response again.
+    Replay(StoredResponse),
+    /// The same request is still running, or never finished.
+    InProgress,
+    /// The key was used for a different request.
+    Mismatch,
+}
+
+#[async_trait]
+pub trait IdempotencyUsecase: Send + Sync + 'static {
+    /// Claims `key` for the request identified by `fingerprint`, unless an
+    /// earlier request holds it.
+    async fn begin<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        key: String,
+        fingerprint: String,
+    ) -> Result<IdempotencyOutcome, UsecaseError>
+    where
+        C: Conn;
+    /// Keeps the response of a claimed request for replay.
+    async fn complete<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        key: &str,
+        response: StoredResponse,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn;
+    /// Frees the key of a request that failed, so it can be retried.
+    async fn abandon<C>(&self, conn: &C, user_id: Uuid, key: &str) -> Result<(), UsecaseError>
+    where
+        C: Conn;
+    /// Removes expired keys and returns how many there were.
+    async fn purge_expired<C>(&self, conn: &C) -> Result<u64, UsecaseError>
+    where
+        C: Conn;
+}
+
+#[derive(Clone)]
+pub struct IdempotencyUsecaseImpl<R> {
+    idempotency_repository: Arc<R>,
+}
+
+impl<R> IdempotencyUsecaseImpl<R> {
+    pub fn new(idempotency_repository: R) -> Self {
+        Self {
+            idempotency_repository: Arc::new(idempotency_repository),
+        }
+    }
+}
+
+#[async_trait]
+impl<R> IdempotencyUsecase for IdempotencyUsecaseImpl<R>
+where
+    R: IdempotencyRepository + Send + Sync + 'static,
+{
+    async fn begin<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        key: String,
+        fingerprint: String,
+    ) -> Result<IdempotencyOutcome, UsecaseError>
+    where
+        C: Conn,
+    {
+        let now = Utc::now();
+        let record = IdempotencyRecord::new(user_id, key.clone(), fingerprint, now)?;
+        let fingerprint = record.fingerprint.clone();
+        if self.idempotency_repository.claim(conn, record, now).await? {
+            return Ok(IdempotencyOutcome::Proceed);
+        }
+        let existing = match self.idempotency_repository.find(conn, user_id, &key).await {
+            Ok(existing) => existing,
+            // Expired and purged since the claim failed; the client can retry.
+            Err(RepositoryError::NotFound(_)) => return Ok(IdempotencyOutcome::InProgress),
+            Err(err) => return Err(err.into()),
+        };
+        if existing.fingerprint != fingerprint {
+            return Ok(IdempotencyOutcome::Mismatch);
+        }
+        Ok(match existing.response {
+            Some(response) => IdempotencyOutcome::Replay(response),
+            None => IdempotencyOutcome::InProgress,
+        })
+    }
+
+    async fn complete<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        key: &str,
+        response: StoredResponse,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn,
+    {
+        let mut record = self.idempotency_repository.find(conn, user_id, key).await?;
+        record.complete(response, Utc::now());
+        self.idempotency_repository.update(conn, record).await?;
+        Ok(())
+    }
+
+    async fn abandon<C>(&self, conn: &C, user_id: Uuid, key: &str) -> Result<(), UsecaseError>
+    where
+        C: Conn,
+    {
+        self.idempotency_repository
+            .delete(conn, user_id, key)
+            .await?;
+        Ok(())
+    }
+
+    async fn purge_expired<C>(&self, conn: &C) -> Result<u64, UsecaseError>
+    where
+        C: Conn,
+    {
+        let purged = self
+            .idempotency_repository
+            .delete_expired(conn, Utc::now())
+            .await?;
+        Ok(purged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::conn::tests::MockConn;
+    use chrono::{DateTime, Duration};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockIdempotencyRepository {
+        records: Mutex<Vec<IdempotencyRecord>>,
+    }
+
+    #[async_trait]
+    impl IdempotencyRepository for MockIdempotencyRepository {
+        async fn claim<C>(
+            &self,
+            _conn: &C,
+            record: IdempotencyRecord,
+            now: DateTime<Utc>,
+        ) -> Result<bool, RepositoryError> {
+            let mut records = self.records.lock().unwrap();
+            let held = |stored: &IdempotencyRecord| {
+                stored.user_id == record.user_id && stored.key == record.key
+            };
+            match records.iter_mut().find(|stored| held(stored)) {
+                Some(stored) if !stored.is_expired(now) => Ok(false),
+                Some(stored) => {
+                    *stored = record;
+                    Ok(true)
+                }
+                None => {
+                    records.push(record);
+                    Ok(true)
+                }
+            }
+        }
+
+        async fn find<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            key: &str,
+        ) -> Result<IdempotencyRecord, RepositoryError> {
+            self.records
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|stored| stored.user_id == user_id && stored.key == key)
+                .cloned()
+                .ok_or(RepositoryError::NotFound(format!(
+                    "Idempotency key {} not found",
+                    key
+                )))
+        }
+
+        async fn update<C>(
+            &self,
+            _conn: &C,
+            record: IdempotencyRecord,
+        ) -> Result<IdempotencyRecord, RepositoryError> {
+            let mut records = self.records.lock().unwrap();
+            let stored = records
+                .iter_mut()
+                .find(|stored| stored.user_id == record.user_id && stored.key == record.key)
+                .ok_or(RepositoryError::NotFound(format!(
+                    "Idempotency key {} not found",
+                    record.key
+                )))?;
+            *stored = record.clone();
+            Ok(record)
+        }
+
+        async fn delete<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            key: &str,
+        ) -> Result<(), RepositoryError> {
+            self.records
+                .lock()
+                .unwrap()
+                .retain(|stored| stored.user_id != user_id || stored.key != key);
+            Ok(())
+        }
+
+        async fn delete_expired<C>(
+            &self,
+            _conn: &C,
+            now: DateTime<Utc>,
+        ) -> Result<u64, RepositoryError> {
+            let mut records = self.records.lock().unwrap();
+            let before = records.len();
+            records.retain(|stored| !stored.is_expired(now));
+            Ok((before - records.len()) as u64)
+        }
+    }
+
+    fn response() -> StoredResponse {
+        StoredResponse {
+            status: 201,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: b"{}".to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_usecase() {
+        let usecase = IdempotencyUsecaseImpl::new(MockIdempotencyRepository::default());
+        let conn = MockConn;
+        let user_id = Uuid::now_v7();
+        let begin = |key: &str, fingerprint: &str| {
+            usecase.begin(&conn, user_id, key.into(), fingerprint.into())
+        };
+
+        assert_eq!(
+            begin("retry-1", "a").await.unwrap(),
+            IdempotencyOutcome::Proceed
+        );
+        assert_eq!(
+            begin("retry-1", "a").await.unwrap(),
+            IdempotencyOutcome::InProgress
+        );
+        assert_eq!(
+            begin("retry-1", "b").await.unwrap(),
+            IdempotencyOutcome::Mismatch
+        );
+
+        usecase
+            .complete(&conn, user_id, "retry-1", response())
+            .await
+            .unwrap();
+        assert_eq!(
+            begin("retry-1", "a").await.unwrap(),
+            IdempotencyOutcome::Replay(response())
+        );
+        assert_eq!(
+            begin("retry-1", "b").await.unwrap(),
+            IdempotencyOutcome::Mismatch
+        );
+
+        // Keys are per user.
+        assert_eq!(
+            usecase
+                .begin(&conn, Uuid::now_v7(), "retry-1".into(), "b".into())
+                .await
+                .unwrap(),
+            IdempotencyOutcome::Proceed
+        );
+
+        // Abandoned keys can be used again.
+        assert_eq!(
+            begin("retry-2", "a").await.unwrap(),
+            IdempotencyOutcome::Proceed
+        );
+        usecase.abandon(&conn, user_id, "retry-2").await.unwrap();
+        assert_eq!(
+            begin("retry-2", "b").await.unwrap(),
+            IdempotencyOutcome::Proceed
+        );
+
+        assert!(matches!(
+            begin("", "a").await,
+            Err(UsecaseError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_usecase_expiry() {
+        let usecase = IdempotencyUsecaseImpl::new(MockIdempotencyRepository::default());
+        let conn = MockConn;
+        let user_id = Uuid::now_v7();
+        usecase
+            .begin(&conn, user_id, "retry-1".into(), "a".into())
+            .await
+            .unwrap();
+        usecase
+            .idempotency_repository
+            .records
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .for_each(|record| record.expires_at = Utc::now() - Duration::seconds(1));
+
+        assert_eq!(usecase.purge_expired(&conn).await.unwrap(), 1);
+        assert_eq!(
+            usecase
+                .begin(&conn, user_id, "retry-1".into(), "b".into())
+                .await
+                .unwrap(),
+            IdempotencyOutcome::Proceed
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use std::sync::Arc;
+
+use crate::{
+    application_service::{
+        service::{
+            event_publisher::EventPublisher,
+            transaction_service::{TransactionError, TransactionService},
+        },
+        usecase::errors::UsecaseError,
+    },
+    domain::repositories::{conn::Conn, outbox_repository::OutboxRepository},
+};
+use async_trait::async_trait;
+use chrono::Utc;
+
+#[async_trait]
+pub trait OutboxUsecase: Send + Sync + 'static {
+    /// Publishes up to `limit` due messages and returns how many were attempted.
+    /// Failed messages are rescheduled with backoff rather than reported as errors.
+    async fn dispatch_pending<C>(&self, conn: &C, limit: u64) -> Result<usize, UsecaseError>
+    where
+        C: Conn;
+}
+
+#[derive(Clone)]
+pub struct OutboxUsecaseImpl<O, E, T> {
+    outbox_repository: Arc<O>,
+    publisher: Arc<E>,
+    transaction_service: Arc<T>,
+}
+
+impl<O, E, T> OutboxUsecaseImpl<O, E, T> {
+    pub fn new(outbox_repository: O, publisher: E, transaction_service: T) -> Self {
+        Self {
+            outbox_repository: Arc::new(outbox_repository),
+            publisher: Arc::new(publisher),
+            transaction_service: Arc::new(transaction_service),
+        }
+    }
+}
+
+/// The batch stays locked for the whole transaction, so concurrent dispatchers
+/// never publish the same message at the same time.
+#[async_trait]
+impl<O, E, T> OutboxUsecase for OutboxUsecaseImpl<O, E, T>
+where
+    O: OutboxRepository + Send + Sync + 'static,
+    E: EventPublisher + 'static,
+    T: TransactionService + Send + Sync + 'static,
+{
+    async fn dispatch_pending<C>(&self, conn: &C, limit: u64) -> Result<usize, UsecaseError>
+    where
+        C: Conn,
+    {
+        let outbox_repository = self.outbox_repository.clone();
+        let publisher = self.publisher.clone();
+        let dispatched = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let messages = outbox_repository.find_due(tx, Utc::now(), limit).await?;
+                    let dispatched = messages.len();
+                    for mut message in messages {
+                        match publisher.publish(tx, &message.event).await {
+                            Ok(()) => message.mark_published(Utc::now()),
+                            Err(err) => message.mark_failed(err.to_string(), Utc::now()),
+                        }
+                        outbox_repository.update(tx, message).await?;
+                    }
+                    Ok::<usize, TransactionError>(dispatched)
+                })
+            })
+            .await?;
+        Ok(dispatched)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::todo::Todo;
+    use crate::domain::models::todo_event::{OutboxMessage, TodoEvent};
+    use crate::domain::repositories::conn::tests::MockConn;
+    use crate::domain::repositories::errors::RepositoryError;
+    use chrono::{DateTime, Duration};
+    use std::sync::Mutex;
+    use uuid::Uuid;
+
+    #[derive(Clone, Default)]
+    struct MockOutboxRepository {
+        messages: Arc<Mutex<Vec<OutboxMessage>>>,
+    }
+
+    #[async_trait]
+    impl OutboxRepository for MockOutboxRepository {
+        async fn create<C>(&self, _: &C, events: Vec<TodoEvent>) -> Result<(), RepositoryError>
+        where
+            C: Conn,
+        {
+            let mut messages = self.messages.lock().unwrap();
+            messages.extend(events.into_iter().map(OutboxMessage::new));
+            Ok(())
+        }
+
+        async fn find_due<C>(
+            &self,
+            _: &C,
+            now: DateTime<Utc>,
+            limit: u64,
+        ) -> Result<Vec<OutboxMessage>, RepositoryError>
+        where
+            C: Conn,
+        {
+            let messages = self.messages.lock().unwrap();
+            Ok(messages
+                .iter()
+                .filter(|message| message.published_at.is_none() && message.next_attempt_at <= now)
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+
+        async fn update<C>(
+            &self,
+            _: &C,
+            message: OutboxMessage,
+        ) -> Result<OutboxMessage, RepositoryError>
+        where
+            C: Conn,
+        {
+            let mut messages = self.messages.lock().unwrap();
+            let stored = messages
+                .iter_mut()
+                .find(|stored| stored.event.id == message.event.id)
+                .ok_or_else(|| RepositoryError::NotFound("Outbox message not found".into()))?;
+            *stored = message.clone();
+            Ok(message)
+        }
+    }
+
+    /// Fails the first attempt for every event, then succeeds.
+    #[derive(Clone, Default)]
+    struct FlakyPublisher {
+        attempted: Arc<Mutex<Vec<Uuid>>>,
+        published: Arc<Mutex<Vec<Uuid>>>,
+    }
+
+    #[async_trait]
+    impl EventPublisher for FlakyPublisher {
+        async fn publish<C>(&self, _conn: &C, event: &TodoEvent) -> Result<(), UsecaseError>
+        where
+            C: Conn,
+        {
+            let mut attempted = self.attempted.lock().unwrap();
+            if !attempted.contains(&event.id) {
+                attempted.push(event.id);
+                return Err(UsecaseError::Unexpected("broker unavailable".into()));
+            }
+            self.published.lock().unwrap().push(event.id);
+            Ok(())
+        }
+    }
+
+    struct MockTransactionService;
+
+    #[async_trait]
+    impl TransactionService for MockTransactionService {
+        type Tx<'a>
+            = MockConn
+        where
+            Self: 'a;
+        async fn run<C, F, T>(&self, _: &C, f: F) -> Result<T, TransactionError>
+        where
+            C: Conn,
+            F: for<'tx> FnOnce(
+                    &'tx Self::Tx<'tx>,
+                ) -> std::pin::Pin<
+                    Box<dyn std::future::Future<Output = Result<T, TransactionError>> + Send + 'tx>,
+                > + Send,
+            T: Send,
+        {
+            f(&MockConn).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_outbox_usecase_impl_dispatch_pending() {
+        let repository = MockOutboxRepository::default();
+        let publisher = FlakyPublisher::default();
+        let usecase = OutboxUsecaseImpl::new(
+            repository.clone(),
+            publisher.clone(),
+            MockTransactionService,
+        );
+
+        let mut todo = Todo::new(Uuid::now_v7(), "Publish me".into(), None);
+        todo.mark_completed(false, &[]).unwrap();
+        repository
+            .create(&MockConn, todo.take_events())
+            .await
+            .unwrap();
+
+        // The first attempt fails and the messages wait for their retry.
+        assert_eq!(usecase.dispatch_pending(&MockConn, 10).await.unwrap(), 2);
+        assert!(publisher.published.lock().unwrap().is_empty());
+        assert_eq!(usecase.dispatch_pending(&MockConn, 10).await.unwrap(), 0);
+        {
+            let messages = repository.messages.lock().unwrap();
+            assert!(messages.iter().all(|message| message.attempts == 1
+                && message.last_error.as_deref()
+                    == Some("UsecaseError: Unexpected(broker unavailable)")));
+        }
+
+        // Once the backoff has elapsed the retry succeeds, oldest first.
+        for message in repository.messages.lock().unwrap().iter_mut() {
+            message.next_attempt_at -= Duration::seconds(1);
+        }
+        assert_eq!(usecase.dispatch_pending(&MockConn, 1).await.unwrap(), 1);
+        assert_eq!(usecase.dispatch_pending(&MockConn, 10).await.unwrap(), 1);
+        let messages = repository.messages.lock().unwrap();
+        assert_eq!(
+            *publisher.published.lock().unwrap(),
+            messages
+                .iter()
+                .map(|message| message.event.id)
+                .collect::<Vec<_>>()
+        );
+        assert!(messages.iter().all(|message| message.attempts == 2
+            && message.published_at.is_some()
+            && message.last_error.is_none()));
+    }
+}
@@ -0,0 +1,549 @@
+use std::sync::Arc;
+
+use crate::{
+    application_service::{
+        service::transaction_service::{TransactionError, TransactionService},
+        usecase::{
+            errors::UsecaseError,
+            share_usecase::ensure_project_owner,
+            todo_usecase::{TodoPage, into_page},
+        },
+    },
+    domain::{
+        models::project::Project,
+        repositories::{
+            conn::Conn,
+            project_repository::ProjectRepository,
+            todo_repository::{TodoQuery, TodoRepository},
+        },
+    },
+};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default)]
+pub struct ProjectInput {
+    pub name: String,
+    pub color: Option<String>,
+    pub archived: bool,
+}
+
+/// `user_id` is the acting user. Reads see their own projects and those shared
+/// with them; only the owner of a project can change or delete it.
+#[async_trait]
+pub trait ProjectUsecase: Send + Sync + 'static {
+    async fn get_all_projects<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        archived: Option<bool>,
+    ) -> Result<Vec<Project>, UsecaseError>
+    where
+        C: Conn;
+    async fn get_project_by_id<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Project, UsecaseError>
+    where
+        C: Conn;
+    async fn create_project<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        input: ProjectInput,
+    ) -> Result<Project, UsecaseError>
+    where
+        C: Conn;
+    async fn update_project<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        input: ProjectInput,
+    ) -> Result<Project, UsecaseError>
+    where
+        C: Conn;
+    /// Fails with `Conflict` while the project still holds todos.
+    async fn delete_project<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn;
+    /// Lists the todos in the project visible to the user; `query.project_id`
+    /// is overridden with `id`.
+    async fn get_project_todos<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        query: TodoQuery,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<TodoPage, UsecaseError>
+    where
+        C: Conn;
+}
+
+#[derive(Clone)]
+pub struct ProjectUsecaseImpl<P, R, T> {
+    project_repository: Arc<P>,
+    todo_repository: Arc<R>,
+    transaction_service: Arc<T>,
+}
+
+impl<P, R, T> ProjectUsecaseImpl<P, R, T> {
+    pub fn new(project_repository: P, todo_repository: R, transaction_service: T) -> Self {
+        Self {
+            project_repository: Arc::new(project_repository),
+            todo_repository: Arc::new(todo_repository),
+            transaction_service: Arc::new(transaction_service),
+        }
+    }
+}
+
+#[async_trait]
+impl<P, R, T> ProjectUsecase for ProjectUsecaseImpl<P, R, T>
+where
+    P: ProjectRepository + Send + Sync + 'static,
+    R: TodoRepository + Send + Sync + 'static,
+    T: TransactionService + Send + Sync + 'static,
+{
+    async fn get_all_projects<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        archived: Option<bool>,
+    ) -> Result<Vec<Project>, UsecaseError>
+    where
+        C: Conn,
+    {
+        let projects = self
+            .project_repository
+            .find_all(conn, user_id, archived)
+            .await?;
+        Ok(projects)
+    }
+
+    async fn get_project_by_id<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Project, UsecaseError>
+    where
+        C: Conn,
+    {
+        let project = self
+            .project_repository
+            .find_by_id(conn, user_id, id)
+            .await?;
+        Ok(project)
+    }
+
+    async fn create_project<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        input: ProjectInput,
+    ) -> Result<Project, UsecaseError>
+    where
+        C: Conn,
+    {
+        let mut project = Project::new(user_id, input.name, input.color)?;
+        project.archived = input.archived;
+        let project = self.project_repository.create(conn, project).await?;
+        Ok(project)
+    }
+
+    async fn update_project<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        input: ProjectInput,
+    ) -> Result<Project, UsecaseError>
+    where
+        C: Conn,
+    {
+        let project_repository = self.project_repository.clone();
+        let project = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let mut project = project_repository.find_by_id(tx, user_id, id).await?;
+                    ensure_project_owner(&project, user_id)?;
+                    project.update(input.name, input.color, input.archived)?;
+                    let updated_project = project_repository.update(tx, project).await?;
+                    Ok::<Project, TransactionError>(updated_project)
+                })
+            })
+            .await?;
+        Ok(project)
+    }
+
+    async fn delete_project<C>(&self, conn: &C, user_id: Uuid, id: Uuid) -> Result<(), UsecaseError>
+    where
+        C: Conn,
+    {
+        let project_repository = self.project_repository.clone();
+        self.transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let project = project_repository.find_by_id(tx, user_id, id).await?;
+                    ensure_project_owner(&project, user_id)?;
+                    project_repository.delete(tx, project).await?;
+                    Ok::<(), TransactionError>(())
+                })
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn get_project_todos<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        query: TodoQuery,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<TodoPage, UsecaseError>
+    where
+        C: Conn,
+    {
+        self.project_repository
+            .find_by_id(conn, user_id, id)
+            .await?;
+        let query = TodoQuery {
+            project_id: Some(id),
+            ..query
+        };
+        let todos = self
+            .todo_repository
+            .find_by_query(conn, user_id, &query, after, limit + 1)
+            .await?;
+        Ok(into_page(todos, limit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::domain::models::todo::Todo;
+    use crate::domain::repositories::conn::tests::MockConn;
+    use crate::domain::repositories::errors::RepositoryError;
+    use crate::domain::repositories::todo_repository::tests::MockTodoRepository;
+
+    use super::*;
+    use chrono::DateTime;
+    use std::sync::{Arc, Mutex};
+
+    const OWNER_ID: Uuid = Uuid::from_u128(0xe1e2e3e4_f1f2_a1a2_b1b2_b3b4b5b6b7b8);
+    const GRANTEE_ID: Uuid = Uuid::from_u128(0xa1a2a3a4_b1b2_c1c2_d1d2_d3d4d5d6d7d8);
+    const STRANGER_ID: Uuid = Uuid::from_u128(0xf1f2f3f4_e1e2_d1d2_c1c2_c3c4c5c6c7c8);
+
+    const PROJECT_ID: &str = "c1c2c3c4d1d2e1e2f1f2f3f4f5f6f7f8";
+
+    /// Projects are visible to their owner and to users in `grantees`. Like
+    /// the foreign key on todos, deleting a project still holding `todos` fails.
+    #[derive(Clone)]
+    struct MockProjectRepository {
+        projects: Arc<Mutex<Vec<Project>>>,
+        grantees: Vec<Uuid>,
+        todos: Arc<Mutex<Vec<Todo>>>,
+    }
+
+    impl MockProjectRepository {
+        pub fn new() -> Self {
+            // Every todo belongs to the mock project.
+            let project_id = Uuid::parse_str(PROJECT_ID).unwrap();
+            let todos = (0..3)
+                .map(|i| {
+                    let mut todo = Todo::new(OWNER_ID, format!("Todo {}", i), None);
+                    todo.move_to_project(Some(project_id));
+                    todo
+                })
+                .collect();
+            Self {
+                projects: Arc::new(Mutex::new(vec![Project {
+                    id: Uuid::parse_str(PROJECT_ID).unwrap(),
+                    owner_id: OWNER_ID,
+                    name: "Work".into(),
+                    color: None,
+                    archived: false,
+                    created_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+                    updated_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+                }])),
+                grantees: vec![GRANTEE_ID],
+                todos: Arc::new(Mutex::new(todos)),
+            }
+        }
+
+        fn is_visible(&self, project: &Project, user_id: Uuid) -> bool {
+            project.owner_id == user_id || self.grantees.contains(&user_id)
+        }
+    }
+
+    #[async_trait]
+    impl ProjectRepository for MockProjectRepository {
+        async fn find_all<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            archived: Option<bool>,
+        ) -> Result<Vec<Project>, RepositoryError> {
+            let mut projects: Vec<Project> = self
+                .projects
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|p| self.is_visible(p, user_id))
+                .filter(|p| archived.is_none_or(|a| p.archived == a))
+                .cloned()
+                .collect();
+            projects.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(projects)
+        }
+
+        async fn find_by_id<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            id: Uuid,
+        ) -> Result<Project, RepositoryError> {
+            self.projects
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|p| p.id == id && self.is_visible(p, user_id))
+                .cloned()
+                .ok_or(RepositoryError::NotFound(format!(
+                    "Project with id {} not found",
+                    id
+                )))
+        }
+
+        async fn create<C>(&self, _conn: &C, project: Project) -> Result<Project, RepositoryError> {
+            self.projects.lock().unwrap().push(project.clone());
+            Ok(project)
+        }
+
+        async fn update<C>(&self, _conn: &C, project: Project) -> Result<Project, RepositoryError> {
+            let mut projects = self.projects.lock().unwrap();
+            let stored = projects.iter_mut().find(|p| p.id == project.id).ok_or(
+                RepositoryError::NotFound(format!("Project with id {} not found", project.id)),
+            )?;
+            *stored = project.clone();
+            Ok(project)
+        }
+
+        async fn delete<C>(&self, _conn: &C, project: Project) -> Result<(), RepositoryError> {
+            let todos = self.todos.lock().unwrap();
+            if todos.iter().any(|t| t.project_id == Some(project.id)) {
+                return Err(RepositoryError::Conflict(format!(
+                    "Project with id {} still holds todos",
+                    project.id
+                )));
+            }
+            self.projects.lock().unwrap().retain(|p| p.id != project.id);
+            Ok(())
+        }
+    }
+
+    struct MockTransactionService;
+
+    #[async_trait]
+    impl TransactionService for MockTransactionService {
+        type Tx<'a>
+            = MockConn
+        where
+            Self: 'a;
+        async fn run<C, F, T>(&self, _: &C, f: F) -> Result<T, TransactionError>
+        where
+            C: Conn,
+            F: for<'tx> FnOnce(
+                    &'tx Self::Tx<'tx>,
+                ) -> std::pin::Pin<
+                    Box<dyn std::future::Future<Output = Result<T, TransactionError>> + Send + 'tx>,
+                > + Send,
+            T: Send,
+        {
+            f(&MockConn).await
+        }
+    }
+
+    fn usecase(
+        repository: MockProjectRepository,
+    ) -> ProjectUsecaseImpl<MockProjectRepository, MockTodoRepository, MockTransactionService> {
+        let todos = MockTodoRepository {
+            todos: repository.todos.clone(),
+            ..MockTodoRepository::default()
+        };
+        ProjectUsecaseImpl::new(repository, todos, MockTransactionService)
+    }
+
+    #[tokio::test]
+    async fn test_project_usecase_impl_create_project() {
+        let repository = MockProjectRepository::new();
+        let usecase = usecase(repository.clone());
+
+        let project = usecase
+            .create_project(
+                &MockConn,
+                OWNER_ID,
+                ProjectInput {
+                    name: "Home".into(),
+                    color: Some("#00ff00".into()),
+                    archived: false,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(project.name, "Home");
+        assert_eq!(project.owner_id, OWNER_ID);
+
+        let projects = usecase
+            .get_all_projects(&MockConn, OWNER_ID, None)
+            .await
+            .unwrap();
+        assert_eq!(projects.len(), 2);
+        assert_eq!(projects[0].name, "Home");
+
+        let result = usecase
+            .create_project(
+                &MockConn,
+                OWNER_ID,
+                ProjectInput {
+                    name: "Garden".into(),
+                    color: Some("green".into()),
+                    archived: false,
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_project_usecase_impl_archive_project() {
+        let repository = MockProjectRepository::new();
+        let usecase = usecase(repository.clone());
+        let id = Uuid::parse_str(PROJECT_ID).unwrap();
+
+        let project = usecase
+            .update_project(
+                &MockConn,
+                OWNER_ID,
+                id,
+                ProjectInput {
+                    name: "Work".into(),
+                    color: None,
+                    archived: true,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(project.archived);
+
+        let active = usecase
+            .get_all_projects(&MockConn, OWNER_ID, Some(false))
+            .await
+            .unwrap();
+        assert!(active.is_empty());
+        let archived = usecase
+            .get_all_projects(&MockConn, OWNER_ID, Some(true))
+            .await
+            .unwrap();
+        assert_eq!(archived.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_project_usecase_impl_get_project_todos() {
+        let repository = MockProjectRepository::new();
+        let usecase = usecase(repository.clone());
+        let id = Uuid::parse_str(PROJECT_ID).unwrap();
+
+        let page = usecase
+            .get_project_todos(&MockConn, OWNER_ID, id, TodoQuery::default(), None, 2)
+            .await
+            .unwrap();
+        assert_eq!(page.todos.len(), 2);
+        assert!(page.todos.iter().all(|t| t.project_id == Some(id)));
+        assert!(page.next_cursor.is_some());
+
+        let result = usecase
+            .get_project_todos(
+                &MockConn,
+                OWNER_ID,
+                Uuid::now_v7(),
+                TodoQuery::default(),
+                None,
+                2,
+            )
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_project_usecase_impl_delete_project() {
+        let repository = MockProjectRepository::new();
+        let usecase = usecase(repository.clone());
+        let id = Uuid::parse_str(PROJECT_ID).unwrap();
+
+        let result = usecase.delete_project(&MockConn, OWNER_ID, id).await;
+        assert!(matches!(result, Err(UsecaseError::Conflict(_))));
+        assert_eq!(repository.projects.lock().unwrap().len(), 1);
+
+        for todo in repository.todos.lock().unwrap().iter_mut() {
+            todo.move_to_project(None);
+        }
+        usecase
+            .delete_project(&MockConn, OWNER_ID, id)
+            .await
+            .unwrap();
+        assert!(repository.projects.lock().unwrap().is_empty());
+        let result = usecase.delete_project(&MockConn, OWNER_ID, id).await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_project_usecase_impl_only_owner_changes_project() {
+        let repository = MockProjectRepository::new();
+        let usecase = usecase(repository.clone());
+        let id = Uuid::parse_str(PROJECT_ID).unwrap();
+        let input = ProjectInput {
+            name: "Mine".into(),
+            color: None,
+            archived: true,
+        };
+
+        let project = usecase
+            .get_project_by_id(&MockConn, GRANTEE_ID, id)
+            .await
+            .unwrap();
+        assert_eq!(project.owner_id, OWNER_ID);
+        let result = usecase
+            .update_project(&MockConn, GRANTEE_ID, id, input.clone())
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+        let result = usecase.delete_project(&MockConn, GRANTEE_ID, id).await;
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+
+        let result = usecase.get_project_by_id(&MockConn, STRANGER_ID, id).await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+        let result = usecase
+            .update_project(&MockConn, STRANGER_ID, id, input)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+        let projects = usecase
+            .get_all_projects(&MockConn, STRANGER_ID, None)
+            .await
+            .unwrap();
+        assert!(projects.is_empty());
+        assert_eq!(repository.projects.lock().unwrap()[0].name, "Work");
+    }
+}
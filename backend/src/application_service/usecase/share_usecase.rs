@@ -0,0 +1,685 @@
+use std::sync::Arc;
+
+use crate::{
+    application_service::{
+        service::transaction_service::{TransactionError, TransactionService},
+        usecase::errors::UsecaseError,
+    },
+    domain::{
+        models::{
+            project::Project,
+            share::{self, ShareGrant, ShareRole, ShareTarget},
+            todo::Todo,
+        },
+        repositories::{
+            conn::Conn, project_repository::ProjectRepository, share_repository::ShareRepository,
+            todo_repository::TodoRepository,
+        },
+    },
+};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Fails with `Forbidden` unless `user_id` holds at least the `required` role
+/// on the todo, and with `NotFound` when the todo is not shared with them.
+pub(crate) async fn ensure_role<S, C>(
+    shares: &S,
+    conn: &C,
+    user_id: Uuid,
+    todo: &Todo,
+    required: ShareRole,
+) -> Result<(), TransactionError>
+where
+    S: ShareRepository,
+    C: Conn,
+{
+    let grants = if todo.owner_id == user_id {
+        vec![]
+    } else {
+        shares.find_for_todo(conn, user_id, todo).await?
+    };
+    match share::role_of(todo, user_id, &grants) {
+        Some(role) if role >= required => Ok(()),
+        Some(role) => Err(TransactionError::Forbidden(format!(
+            "Todo with id {} requires the {} role, not {}",
+            todo.id, required, role
+        ))),
+        None => Err(TransactionError::NotFound(format!(
+            "Todo with id {} not found",
+            todo.id
+        ))),
+    }
+}
+
+/// Fails with `Forbidden` unless `user_id` owns the todo. Guards the writes
+/// sharing does not extend to, such as tags and dependencies.
+pub(crate) fn ensure_owner(todo: &Todo, user_id: Uuid) -> Result<(), TransactionError> {
+    if todo.owner_id != user_id {
+        return Err(TransactionError::Forbidden(format!(
+            "Only the owner of todo with id {} can change it this way",
+            todo.id
+        )));
+    }
+    Ok(())
+}
+
+/// Fails with `Forbidden` unless `user_id` owns the project. Project grants
+/// only extend to the todos in the project, not to the project itself.
+pub(crate) fn ensure_project_owner(
+    project: &Project,
+    user_id: Uuid,
+) -> Result<(), TransactionError> {
+    if project.owner_id != user_id {
+        return Err(TransactionError::Forbidden(format!(
+            "Only the owner of project with id {} can manage it",
+            project.id
+        )));
+    }
+    Ok(())
+}
+
+/// Grants on a todo can be managed by anyone holding the owner role on it.
+/// Grants on a project share the todos in it and are managed by the owner of
+/// the project.
+#[async_trait]
+pub trait ShareUsecase: Send + Sync + 'static {
+    async fn get_shares<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        target: ShareTarget,
+    ) -> Result<Vec<ShareGrant>, UsecaseError>
+    where
+        C: Conn;
+    async fn share<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        target: ShareTarget,
+        grantee_id: Uuid,
+        role: ShareRole,
+    ) -> Result<ShareGrant, UsecaseError>
+    where
+        C: Conn;
+    async fn change_share_role<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        target: ShareTarget,
+        id: Uuid,
+        role: ShareRole,
+    ) -> Result<ShareGrant, UsecaseError>
+    where
+        C: Conn;
+    async fn revoke_share<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        target: ShareTarget,
+        id: Uuid,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn;
+}
+
+/// Resolves whose todos the target shares, checking that `user_id` may manage its grants.
+async fn owner_of<S, R, P, C>(
+    shares: &S,
+    todos: &R,
+    projects: &P,
+    conn: &C,
+    user_id: Uuid,
+    target: ShareTarget,
+) -> Result<Uuid, TransactionError>
+where
+    S: ShareRepository,
+    R: TodoRepository,
+    P: ProjectRepository,
+    C: Conn,
+{
+    match target {
+        ShareTarget::Todo(todo_id) => {
+            let todo = todos.find_by_id(conn, user_id, todo_id).await?;
+            ensure_role(shares, conn, user_id, &todo, ShareRole::Owner).await?;
+            Ok(todo.owner_id)
+        }
+        ShareTarget::Project(project_id) => {
+            let project = projects.find_by_id(conn, user_id, project_id).await?;
+            ensure_project_owner(&project, user_id)?;
+            Ok(project.owner_id)
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ShareUsecaseImpl<S, R, P, T> {
+    share_repository: Arc<S>,
+    todo_repository: Arc<R>,
+    project_repository: Arc<P>,
+    transaction_service: Arc<T>,
+}
+
+impl<S, R, P, T> ShareUsecaseImpl<S, R, P, T> {
+    pub fn new(
+        share_repository: S,
+        todo_repository: R,
+        project_repository: P,
+        transaction_service: T,
+    ) -> Self {
+        Self {
+            share_repository: Arc::new(share_repository),
+            todo_repository: Arc::new(todo_repository),
+            project_repository: Arc::new(project_repository),
+            transaction_service: Arc::new(transaction_service),
+        }
+    }
+}
+
+#[async_trait]
+impl<S, R, P, T> ShareUsecase for ShareUsecaseImpl<S, R, P, T>
+where
+    S: ShareRepository + Send + Sync + 'static,
+    R: TodoRepository + Send + Sync + 'static,
+    P: ProjectRepository + Send + Sync + 'static,
+    T: TransactionService + Send + Sync + 'static,
+{
+    async fn get_shares<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        target: ShareTarget,
+    ) -> Result<Vec<ShareGrant>, UsecaseError>
+    where
+        C: Conn,
+    {
+        let owner_id = owner_of(
+            self.share_repository.as_ref(),
+            self.todo_repository.as_ref(),
+            self.project_repository.as_ref(),
+            conn,
+            user_id,
+            target,
+        )
+        .await?;
+        let grants = self
+            .share_repository
+            .find_all(conn, owner_id, target)
+            .await?;
+        Ok(grants)
+    }
+
+    async fn share<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        target: ShareTarget,
+        grantee_id: Uuid,
+        role: ShareRole,
+    ) -> Result<ShareGrant, UsecaseError>
+    where
+        C: Conn,
+    {
+        let share_repository = self.share_repository.clone();
+        let todo_repository = self.todo_repository.clone();
+        let project_repository = self.project_repository.clone();
+        let grant = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let owner_id = owner_of(
+                        share_repository.as_ref(),
+                        todo_repository.as_ref(),
+                        project_repository.as_ref(),
+                        tx,
+                        user_id,
+                        target,
+                    )
+                    .await?;
+                    let grant = ShareGrant::new(owner_id, grantee_id, target, role)?;
+                    let grant = share_repository.create(tx, grant).await?;
+                    Ok::<ShareGrant, TransactionError>(grant)
+                })
+            })
+            .await?;
+        Ok(grant)
+    }
+
+    async fn change_share_role<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        target: ShareTarget,
+        id: Uuid,
+        role: ShareRole,
+    ) -> Result<ShareGrant, UsecaseError>
+    where
+        C: Conn,
+    {
+        let share_repository = self.share_repository.clone();
+        let todo_repository = self.todo_repository.clone();
+        let project_repository = self.project_repository.clone();
+        let grant = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let owner_id = owner_of(
+                        share_repository.as_ref(),
+                        todo_repository.as_ref(),
+                        project_repository.as_ref(),
+                        tx,
+                        user_id,
+                        target,
+                    )
+                    .await?;
+                    let mut grant = share_repository
+                        .find_by_id(tx, owner_id, target, id)
+                        .await?;
+                    grant.change_role(role);
+                    let grant = share_repository.update(tx, grant).await?;
+                    Ok::<ShareGrant, TransactionError>(grant)
+                })
+            })
+            .await?;
+        Ok(grant)
+    }
+
+    async fn revoke_share<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        target: ShareTarget,
+        id: Uuid,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn,
+    {
+        let share_repository = self.share_repository.clone();
+        let todo_repository = self.todo_repository.clone();
+        let project_repository = self.project_repository.clone();
+        self.transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let owner_id = owner_of(
+                        share_repository.as_ref(),
+                        todo_repository.as_ref(),
+                        project_repository.as_ref(),
+                        tx,
+                        user_id,
+                        target,
+                    )
+                    .await?;
+                    let grant = share_repository
+                        .find_by_id(tx, owner_id, target, id)
+                        .await?;
+                    share_repository.delete(tx, grant).await?;
+                    Ok::<(), TransactionError>(())
+                })
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::domain::models::project::Project;
+    use crate::domain::repositories::conn::tests::MockConn;
+    use crate::domain::repositories::errors::RepositoryError;
+    use crate::domain::repositories::todo_repository::tests::MockTodoRepository;
+
+    use super::*;
+    use std::sync::Mutex;
+
+    const OWNER_ID: Uuid = Uuid::from_u128(0xe1e2e3e4_f1f2_a1a2_b1b2_b3b4b5b6b7b8);
+
+    /// Shared by all mock repositories.
+    #[derive(Clone)]
+    struct Store {
+        todo: Todo,
+        project: Project,
+        grants: Arc<Mutex<Vec<ShareGrant>>>,
+    }
+
+    impl Store {
+        fn new() -> Self {
+            Self {
+                todo: Todo::new(OWNER_ID, "Shared".into(), None),
+                project: Project::new(OWNER_ID, "Home".into(), None).unwrap(),
+                grants: Arc::new(Mutex::new(vec![])),
+            }
+        }
+    }
+
+    struct MockShareRepository(Store);
+
+    #[async_trait]
+    impl ShareRepository for MockShareRepository {
+        async fn find_all<C>(
+            &self,
+            _conn: &C,
+            owner_id: Uuid,
+            target: ShareTarget,
+        ) -> Result<Vec<ShareGrant>, RepositoryError> {
+            Ok(self
+                .0
+                .grants
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|grant| grant.owner_id == owner_id && grant.target == target)
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_id<C>(
+            &self,
+            _conn: &C,
+            owner_id: Uuid,
+            target: ShareTarget,
+            id: Uuid,
+        ) -> Result<ShareGrant, RepositoryError> {
+            self.0
+                .grants
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|grant| {
+                    grant.owner_id == owner_id && grant.target == target && grant.id == id
+                })
+                .cloned()
+                .ok_or(RepositoryError::NotFound(format!(
+                    "Share grant with id {} not found",
+                    id
+                )))
+        }
+
+        async fn find_for_todo<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            _todo: &Todo,
+        ) -> Result<Vec<ShareGrant>, RepositoryError> {
+            Ok(self
+                .0
+                .grants
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|grant| grant.user_id == user_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn find_for_user<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+        ) -> Result<Vec<ShareGrant>, RepositoryError> {
+            Ok(self
+                .0
+                .grants
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|grant| grant.user_id == user_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn create<C>(
+            &self,
+            _conn: &C,
+            grant: ShareGrant,
+        ) -> Result<ShareGrant, RepositoryError> {
+            let mut grants = self.0.grants.lock().unwrap();
+            if grants
+                .iter()
+                .any(|g| g.user_id == grant.user_id && g.target == grant.target)
+            {
+                return Err(RepositoryError::Conflict(format!(
+                    "User with id {} already has a grant here",
+                    grant.user_id
+                )));
+            }
+            grants.push(grant.clone());
+            Ok(grant)
+        }
+
+        async fn update<C>(
+            &self,
+            _conn: &C,
+            grant: ShareGrant,
+        ) -> Result<ShareGrant, RepositoryError> {
+            let mut grants = self.0.grants.lock().unwrap();
+            let stored =
+                grants
+                    .iter_mut()
+                    .find(|g| g.id == grant.id)
+                    .ok_or(RepositoryError::NotFound(format!(
+                        "Share grant with id {} not found",
+                        grant.id
+                    )))?;
+            *stored = grant.clone();
+            Ok(grant)
+        }
+
+        async fn delete<C>(&self, _conn: &C, grant: ShareGrant) -> Result<(), RepositoryError> {
+            self.0.grants.lock().unwrap().retain(|g| g.id != grant.id);
+            Ok(())
+        }
+    }
+
+    /// Only `find_by_id` is needed by the share usecase. The project is visible
+    /// to its owner and to the users granted access to it.
+    struct MockProjectRepository(Store);
+
+    #[async_trait]
+    impl ProjectRepository for MockProjectRepository {
+        async fn find_all<C>(
+            &self,
+            _conn: &C,
+            _user_id: Uuid,
+            _archived: Option<bool>,
+        ) -> Result<Vec<Project>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn find_by_id<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            id: Uuid,
+        ) -> Result<Project, RepositoryError> {
+            let project = &self.0.project;
+            let granted = self
+                .0
+                .grants
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|g| g.target == ShareTarget::Project(project.id) && g.user_id == user_id);
+            if project.id == id && (project.owner_id == user_id || granted) {
+                Ok(self.0.project.clone())
+            } else {
+                Err(RepositoryError::NotFound(format!(
+                    "Project with id {} not found",
+                    id
+                )))
+            }
+        }
+
+        async fn create<C>(
+            &self,
+            _conn: &C,
+            _project: Project,
+        ) -> Result<Project, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn update<C>(
+            &self,
+            _conn: &C,
+            _project: Project,
+        ) -> Result<Project, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn delete<C>(&self, _conn: &C, _project: Project) -> Result<(), RepositoryError> {
+            unimplemented!()
+        }
+    }
+
+    struct MockTransactionService;
+
+    #[async_trait]
+    impl TransactionService for MockTransactionService {
+        type Tx<'a>
+            = MockConn
+        where
+            Self: 'a;
+        async fn run<C, F, T>(&self, _: &C, f: F) -> Result<T, TransactionError>
+        where
+            C: Conn,
+            F: for<'tx> FnOnce(
+                    &'tx Self::Tx<'tx>,
+                ) -> std::pin::Pin<
+                    Box<dyn std::future::Future<Output = Result<T, TransactionError>> + Send + 'tx>,
+                > + Send,
+            T: Send,
+        {
+            f(&MockConn).await
+        }
+    }
+
+    fn usecase(
+        store: &Store,
+    ) -> ShareUsecaseImpl<
+        MockShareRepository,
+        MockTodoRepository,
+        MockProjectRepository,
+        MockTransactionService,
+    > {
+        ShareUsecaseImpl::new(
+            MockShareRepository(store.clone()),
+            MockTodoRepository {
+                todos: Arc::new(Mutex::new(vec![store.todo.clone()])),
+                grants: store.grants.clone(),
+            },
+            MockProjectRepository(store.clone()),
+            MockTransactionService,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_share_usecase_impl_share_todo() {
+        let store = Store::new();
+        let usecase = usecase(&store);
+        let target = ShareTarget::Todo(store.todo.id);
+        let guest = Uuid::now_v7();
+
+        let grant = usecase
+            .share(&MockConn, OWNER_ID, target, guest, ShareRole::Viewer)
+            .await
+            .unwrap();
+        assert_eq!(grant.owner_id, OWNER_ID);
+        let result = usecase
+            .share(&MockConn, OWNER_ID, target, guest, ShareRole::Editor)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Conflict(_))));
+        let result = usecase
+            .share(&MockConn, OWNER_ID, target, OWNER_ID, ShareRole::Editor)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+
+        let grant = usecase
+            .change_share_role(&MockConn, OWNER_ID, target, grant.id, ShareRole::Editor)
+            .await
+            .unwrap();
+        assert_eq!(grant.role, ShareRole::Editor);
+        let grants = usecase
+            .get_shares(&MockConn, OWNER_ID, target)
+            .await
+            .unwrap();
+        assert_eq!(grants.len(), 1);
+
+        usecase
+            .revoke_share(&MockConn, OWNER_ID, target, grant.id)
+            .await
+            .unwrap();
+        let result = usecase
+            .revoke_share(&MockConn, OWNER_ID, target, grant.id)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_share_usecase_impl_reshare_needs_owner_role() {
+        let store = Store::new();
+        let usecase = usecase(&store);
+        let target = ShareTarget::Todo(store.todo.id);
+        let (guest, other) = (Uuid::now_v7(), Uuid::now_v7());
+
+        // Strangers cannot see the todo at all.
+        let result = usecase
+            .share(&MockConn, guest, target, other, ShareRole::Viewer)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+
+        let grant = usecase
+            .share(&MockConn, OWNER_ID, target, guest, ShareRole::Editor)
+            .await
+            .unwrap();
+        let result = usecase
+            .share(&MockConn, guest, target, other, ShareRole::Viewer)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+        let result = usecase.get_shares(&MockConn, guest, target).await;
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+
+        usecase
+            .change_share_role(&MockConn, OWNER_ID, target, grant.id, ShareRole::Owner)
+            .await
+            .unwrap();
+        let reshared = usecase
+            .share(&MockConn, guest, target, other, ShareRole::Viewer)
+            .await
+            .unwrap();
+        // The grant shares the todos of their real owner.
+        assert_eq!(reshared.owner_id, OWNER_ID);
+    }
+
+    #[tokio::test]
+    async fn test_share_usecase_impl_share_project() {
+        let store = Store::new();
+        let usecase = usecase(&store);
+        let guest = Uuid::now_v7();
+
+        let target = ShareTarget::Project(store.project.id);
+        let grant = usecase
+            .share(&MockConn, OWNER_ID, target, guest, ShareRole::Viewer)
+            .await
+            .unwrap();
+        assert_eq!(grant.owner_id, OWNER_ID);
+        // Only the owner of the project manages its grants.
+        let result = usecase.get_shares(&MockConn, guest, target).await;
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+        let result = usecase
+            .share(&MockConn, guest, target, Uuid::now_v7(), ShareRole::Viewer)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+        let result = usecase.get_shares(&MockConn, Uuid::now_v7(), target).await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+
+        let result = usecase
+            .share(
+                &MockConn,
+                OWNER_ID,
+                ShareTarget::Project(Uuid::now_v7()),
+                guest,
+                ShareRole::Viewer,
+            )
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+}
@@ -0,0 +1,597 @@
+use std::sync::Arc;
+
+use crate::{
+    application_service::{
+        service::transaction_service::{TransactionError, TransactionService},
+        usecase::{errors::UsecaseError, share_usecase::ensure_owner},
+    },
+    domain::{
+        models::tag::Tag,
+        repositories::{
+            conn::Conn, tag_repository::TagRepository, todo_repository::TodoRepository,
+        },
+    },
+};
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Default)]
+pub struct TagInput {
+    pub name: String,
+    pub color: Option<String>,
+}
+
+#[async_trait]
+pub trait TagUsecase: Send + Sync + 'static {
+    async fn get_all_tags<C>(&self, conn: &C, owner_id: Uuid) -> Result<Vec<Tag>, UsecaseError>
+    where
+        C: Conn;
+    async fn get_tag_by_id<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        id: Uuid,
+    ) -> Result<Tag, UsecaseError>
+    where
+        C: Conn;
+    async fn create_tag<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        input: TagInput,
+    ) -> Result<Tag, UsecaseError>
+    where
+        C: Conn;
+    async fn update_tag<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        id: Uuid,
+        input: TagInput,
+    ) -> Result<Tag, UsecaseError>
+    where
+        C: Conn;
+    async fn delete_tag<C>(&self, conn: &C, owner_id: Uuid, id: Uuid) -> Result<(), UsecaseError>
+    where
+        C: Conn;
+    async fn get_todo_tags<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        todo_id: Uuid,
+    ) -> Result<Vec<Tag>, UsecaseError>
+    where
+        C: Conn;
+    /// Returns every tag on the todo after attaching.
+    async fn attach_tag<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        todo_id: Uuid,
+        tag_id: Uuid,
+    ) -> Result<Vec<Tag>, UsecaseError>
+    where
+        C: Conn;
+    async fn detach_tag<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        todo_id: Uuid,
+        tag_id: Uuid,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn;
+}
+
+#[derive(Clone)]
+pub struct TagUsecaseImpl<G, R, T> {
+    tag_repository: Arc<G>,
+    todo_repository: Arc<R>,
+    transaction_service: Arc<T>,
+}
+
+impl<G, R, T> TagUsecaseImpl<G, R, T> {
+    pub fn new(tag_repository: G, todo_repository: R, transaction_service: T) -> Self {
+        Self {
+            tag_repository: Arc::new(tag_repository),
+            todo_repository: Arc::new(todo_repository),
+            transaction_service: Arc::new(transaction_service),
+        }
+    }
+}
+
+#[async_trait]
+impl<G, R, T> TagUsecase for TagUsecaseImpl<G, R, T>
+where
+    G: TagRepository + Send + Sync + 'static,
+    R: TodoRepository + Send + Sync + 'static,
+    T: TransactionService + Send + Sync + 'static,
+{
+    async fn get_all_tags<C>(&self, conn: &C, owner_id: Uuid) -> Result<Vec<Tag>, UsecaseError>
+    where
+        C: Conn,
+    {
+        let tags = self.tag_repository.find_all(conn, owner_id).await?;
+        Ok(tags)
+    }
+
+    async fn get_tag_by_id<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        id: Uuid,
+    ) -> Result<Tag, UsecaseError>
+    where
+        C: Conn,
+    {
+        let tag = self.tag_repository.find_by_id(conn, owner_id, id).await?;
+        Ok(tag)
+    }
+
+    async fn create_tag<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        input: TagInput,
+    ) -> Result<Tag, UsecaseError>
+    where
+        C: Conn,
+    {
+        let tag = Tag::new(owner_id, input.name, input.color)?;
+        let tag = self.tag_repository.create(conn, tag).await?;
+        Ok(tag)
+    }
+
+    async fn update_tag<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        id: Uuid,
+        input: TagInput,
+    ) -> Result<Tag, UsecaseError>
+    where
+        C: Conn,
+    {
+        let tag_repository = self.tag_repository.clone();
+        let tag = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let mut tag = tag_repository.find_by_id(tx, owner_id, id).await?;
+                    tag.update(input.name, input.color)?;
+                    let updated_tag = tag_repository.update(tx, tag).await?;
+                    Ok::<Tag, TransactionError>(updated_tag)
+                })
+            })
+            .await?;
+        Ok(tag)
+    }
+
+    async fn delete_tag<C>(&self, conn: &C, owner_id: Uuid, id: Uuid) -> Result<(), UsecaseError>
+    where
+        C: Conn,
+    {
+        let tag_repository = self.tag_repository.clone();
+        self.transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let tag = tag_repository.find_by_id(tx, owner_id, id).await?;
+                    tag_repository.delete(tx, tag).await?;
+                    Ok::<(), TransactionError>(())
+                })
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn get_todo_tags<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        todo_id: Uuid,
+    ) -> Result<Vec<Tag>, UsecaseError>
+    where
+        C: Conn,
+    {
+        self.todo_repository
+            .find_by_id(conn, owner_id, todo_id)
+            .await?;
+        let tags = self.tag_repository.find_by_todo(conn, todo_id).await?;
+        Ok(tags)
+    }
+
+    async fn attach_tag<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        todo_id: Uuid,
+        tag_id: Uuid,
+    ) -> Result<Vec<Tag>, UsecaseError>
+    where
+        C: Conn,
+    {
+        let tag_repository = self.tag_repository.clone();
+        let todo_repository = self.todo_repository.clone();
+        let tags = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let todo = todo_repository.find_by_id(tx, owner_id, todo_id).await?;
+                    ensure_owner(&todo, owner_id)?;
+                    tag_repository.find_by_id(tx, owner_id, tag_id).await?;
+                    tag_repository.attach(tx, todo_id, tag_id).await?;
+                    let tags = tag_repository.find_by_todo(tx, todo_id).await?;
+                    Ok::<Vec<Tag>, TransactionError>(tags)
+                })
+            })
+            .await?;
+        Ok(tags)
+    }
+
+    async fn detach_tag<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        todo_id: Uuid,
+        tag_id: Uuid,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn,
+    {
+        let tag_repository = self.tag_repository.clone();
+        let todo_repository = self.todo_repository.clone();
+        self.transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let todo = todo_repository.find_by_id(tx, owner_id, todo_id).await?;
+                    ensure_owner(&todo, owner_id)?;
+                    tag_repository.detach(tx, todo_id, tag_id).await?;
+                    Ok::<(), TransactionError>(())
+                })
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::domain::models::todo::Todo;
+    use crate::domain::repositories::conn::tests::MockConn;
+    use crate::domain::repositories::errors::RepositoryError;
+    use crate::domain::repositories::todo_repository::tests::MockTodoRepository;
+
+    use super::*;
+    use chrono::DateTime;
+    use std::sync::{Arc, Mutex};
+
+    const OWNER_ID: Uuid = Uuid::from_u128(0xe1e2e3e4_f1f2_a1a2_b1b2_b3b4b5b6b7b8);
+
+    const TODO_ID: &str = "a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8";
+    const TAG_ID: &str = "c1c2c3c4d1d2e1e2f1f2f3f4f5f6f7f8";
+
+    #[derive(Clone)]
+    struct MockTagRepository {
+        tags: Arc<Mutex<Vec<Tag>>>,
+        todo_tags: Arc<Mutex<Vec<(Uuid, Uuid)>>>,
+    }
+
+    impl MockTagRepository {
+        pub fn new() -> Self {
+            Self {
+                tags: Arc::new(Mutex::new(vec![Tag {
+                    id: Uuid::parse_str(TAG_ID).unwrap(),
+                    owner_id: OWNER_ID,
+                    name: "work".into(),
+                    color: None,
+                    created_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+                    updated_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+                }])),
+                todo_tags: Arc::new(Mutex::new(vec![])),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl TagRepository for MockTagRepository {
+        async fn find_all<C>(
+            &self,
+            _conn: &C,
+            owner_id: Uuid,
+        ) -> Result<Vec<Tag>, RepositoryError> {
+            let mut tags: Vec<Tag> = self
+                .tags
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|tag| tag.owner_id == owner_id)
+                .cloned()
+                .collect();
+            tags.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(tags)
+        }
+
+        async fn find_by_id<C>(
+            &self,
+            _conn: &C,
+            owner_id: Uuid,
+            id: Uuid,
+        ) -> Result<Tag, RepositoryError> {
+            let tags = self.tags.lock().unwrap();
+            tags.iter()
+                .find(|tag| tag.owner_id == owner_id && tag.id == id)
+                .cloned()
+                .ok_or(RepositoryError::NotFound(format!(
+                    "Tag with id {} not found",
+                    id
+                )))
+        }
+
+        async fn find_by_todo<C>(
+            &self,
+            _conn: &C,
+            todo_id: Uuid,
+        ) -> Result<Vec<Tag>, RepositoryError> {
+            let todo_tags = self.todo_tags.lock().unwrap();
+            let tags = self.tags.lock().unwrap();
+            Ok(tags
+                .iter()
+                .filter(|tag| todo_tags.contains(&(todo_id, tag.id)))
+                .cloned()
+                .collect())
+        }
+
+        async fn create<C>(&self, _conn: &C, tag: Tag) -> Result<Tag, RepositoryError> {
+            let mut tags = self.tags.lock().unwrap();
+            if tags
+                .iter()
+                .any(|t| t.owner_id == tag.owner_id && t.name == tag.name)
+            {
+                return Err(RepositoryError::Conflict(format!(
+                    "Tag with name {} already exists",
+                    tag.name
+                )));
+            }
+            tags.push(tag.clone());
+            Ok(tag)
+        }
+
+        async fn update<C>(&self, _conn: &C, tag: Tag) -> Result<Tag, RepositoryError> {
+            let mut tags = self.tags.lock().unwrap();
+            let stored = tags
+                .iter_mut()
+                .find(|t| t.owner_id == tag.owner_id && t.id == tag.id)
+                .ok_or(RepositoryError::NotFound(format!(
+                    "Tag with id {} not found",
+                    tag.id
+                )))?;
+            *stored = tag.clone();
+            Ok(tag)
+        }
+
+        async fn delete<C>(&self, _conn: &C, tag: Tag) -> Result<(), RepositoryError> {
+            self.tags.lock().unwrap().retain(|t| t.id != tag.id);
+            self.todo_tags
+                .lock()
+                .unwrap()
+                .retain(|(_, tag_id)| *tag_id != tag.id);
+            Ok(())
+        }
+
+        async fn attach<C>(
+            &self,
+            _conn: &C,
+            todo_id: Uuid,
+            tag_id: Uuid,
+        ) -> Result<(), RepositoryError> {
+            let mut todo_tags = self.todo_tags.lock().unwrap();
+            if !todo_tags.contains(&(todo_id, tag_id)) {
+                todo_tags.push((todo_id, tag_id));
+            }
+            Ok(())
+        }
+
+        async fn detach<C>(
+            &self,
+            _conn: &C,
+            todo_id: Uuid,
+            tag_id: Uuid,
+        ) -> Result<(), RepositoryError> {
+            let mut todo_tags = self.todo_tags.lock().unwrap();
+            let index = todo_tags
+                .iter()
+                .position(|pair| *pair == (todo_id, tag_id))
+                .ok_or(RepositoryError::NotFound(format!(
+                    "Tag with id {} is not attached to todo with id {}",
+                    tag_id, todo_id
+                )))?;
+            todo_tags.remove(index);
+            Ok(())
+        }
+    }
+
+    struct MockTransactionService;
+
+    #[async_trait]
+    impl TransactionService for MockTransactionService {
+        type Tx<'a>
+            = MockConn
+        where
+            Self: 'a;
+        async fn run<C, F, T>(&self, _: &C, f: F) -> Result<T, TransactionError>
+        where
+            C: Conn,
+            F: for<'tx> FnOnce(
+                    &'tx Self::Tx<'tx>,
+                ) -> std::pin::Pin<
+                    Box<dyn std::future::Future<Output = Result<T, TransactionError>> + Send + 'tx>,
+                > + Send,
+            T: Send,
+        {
+            f(&MockConn).await
+        }
+    }
+
+    fn usecase(
+        repository: MockTagRepository,
+    ) -> TagUsecaseImpl<MockTagRepository, MockTodoRepository, MockTransactionService> {
+        let mut todo = Todo::new(OWNER_ID, "Test Todo 1".into(), None);
+        todo.id = Uuid::parse_str(TODO_ID).unwrap();
+        TagUsecaseImpl::new(
+            repository,
+            MockTodoRepository::new(vec![todo]),
+            MockTransactionService,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_tag_usecase_impl_create_tag() {
+        let repository = MockTagRepository::new();
+        let usecase = usecase(repository.clone());
+
+        let tag = usecase
+            .create_tag(
+                &MockConn,
+                OWNER_ID,
+                TagInput {
+                    name: "home".into(),
+                    color: Some("#00ff00".into()),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(tag.name, "home");
+
+        let tags = usecase.get_all_tags(&MockConn, OWNER_ID).await.unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].name, "home");
+
+        let work = || TagInput {
+            name: "work".into(),
+            color: None,
+        };
+        let result = usecase.create_tag(&MockConn, OWNER_ID, work()).await;
+        assert!(matches!(result, Err(UsecaseError::Conflict(_))));
+
+        // Names are only unique per user, and users only see their own tags.
+        let other = Uuid::now_v7();
+        usecase.create_tag(&MockConn, other, work()).await.unwrap();
+        let tags = usecase.get_all_tags(&MockConn, other).await.unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].owner_id, other);
+
+        let result = usecase
+            .create_tag(
+                &MockConn,
+                OWNER_ID,
+                TagInput {
+                    name: "garden".into(),
+                    color: Some("green".into()),
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_tag_usecase_impl_update_tag() {
+        let repository = MockTagRepository::new();
+        let usecase = usecase(repository.clone());
+
+        let id = Uuid::parse_str(TAG_ID).unwrap();
+        let input = || TagInput {
+            name: "office".into(),
+            color: None,
+        };
+
+        let result = usecase
+            .update_tag(&MockConn, Uuid::now_v7(), id, input())
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+
+        let tag = usecase
+            .update_tag(&MockConn, OWNER_ID, id, input())
+            .await
+            .unwrap();
+        assert_eq!(tag.name, "office");
+        let name = { repository.tags.lock().unwrap()[0].name.clone() };
+        assert_eq!(name, "office");
+    }
+
+    #[tokio::test]
+    async fn test_tag_usecase_impl_attach_and_detach_tag() {
+        let repository = MockTagRepository::new();
+        let usecase = usecase(repository.clone());
+        let todo_id = Uuid::parse_str(TODO_ID).unwrap();
+        let tag_id = Uuid::parse_str(TAG_ID).unwrap();
+
+        let tags = usecase
+            .attach_tag(&MockConn, OWNER_ID, todo_id, tag_id)
+            .await
+            .unwrap();
+        assert_eq!(tags.len(), 1);
+        let tags = usecase
+            .attach_tag(&MockConn, OWNER_ID, todo_id, tag_id)
+            .await
+            .unwrap();
+        assert_eq!(tags.len(), 1);
+
+        let result = usecase
+            .attach_tag(&MockConn, OWNER_ID, Uuid::now_v7(), tag_id)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+        let result = usecase
+            .attach_tag(&MockConn, OWNER_ID, todo_id, Uuid::now_v7())
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+        // Tags of other users cannot be attached.
+        let foreign = Tag::new(Uuid::now_v7(), "private".into(), None).unwrap();
+        repository.tags.lock().unwrap().push(foreign.clone());
+        let result = usecase
+            .attach_tag(&MockConn, OWNER_ID, todo_id, foreign.id)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+
+        usecase
+            .detach_tag(&MockConn, OWNER_ID, todo_id, tag_id)
+            .await
+            .unwrap();
+        let tags = usecase
+            .get_todo_tags(&MockConn, OWNER_ID, todo_id)
+            .await
+            .unwrap();
+        assert!(tags.is_empty());
+        let result = usecase
+            .detach_tag(&MockConn, OWNER_ID, todo_id, tag_id)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_tag_usecase_impl_delete_tag() {
+        let repository = MockTagRepository::new();
+        let usecase = usecase(repository.clone());
+        let todo_id = Uuid::parse_str(TODO_ID).unwrap();
+        let tag_id = Uuid::parse_str(TAG_ID).unwrap();
+
+        usecase
+            .attach_tag(&MockConn, OWNER_ID, todo_id, tag_id)
+            .await
+            .unwrap();
+        let result = usecase.delete_tag(&MockConn, Uuid::now_v7(), tag_id).await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+        usecase
+            .delete_tag(&MockConn, OWNER_ID, tag_id)
+            .await
+            .unwrap();
+
+        let result = usecase.get_tag_by_id(&MockConn, OWNER_ID, tag_id).await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+        let tags = usecase
+            .get_todo_tags(&MockConn, OWNER_ID, todo_id)
+            .await
+            .unwrap();
+        assert!(tags.is_empty());
+    }
+}
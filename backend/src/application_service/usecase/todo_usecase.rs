@@ -1,272 +1,2068 @@
-use std::sync::Arc;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 use crate::{
     application_service::{
-        service::transaction_service::{TransactionError, TransactionService},
-        usecase::errors::UsecaseError,
+        service::{
+            event_hub::{EventHub, EventSubscription},
+            transaction_service::{TransactionError, TransactionService},
+        },
+        usecase::{errors::UsecaseError, share_usecase::ensure_role},
     },
     domain::{
-        models::todo::Todo,
-        repositories::{conn::Conn, todo_repository::TodoRepository},
+        models::{
+            audit::{AuditAction, AuditEvent},
+            errors::DomainError,
+            recurrence::Recurrence,
+            share::{ShareGrant, ShareRole, role_on_event},
+            todo::{self, Priority, Todo, TodoPatch},
+            todo_event::TodoEvent,
+        },
+        repositories::{
+            audit_repository::AuditRepository,
+            conn::Conn,
+            dependency_repository::DependencyRepository,
+            outbox_repository::OutboxRepository,
+            project_repository::ProjectRepository,
+            share_repository::ShareRepository,
+            todo_repository::{TodoQuery, TodoRepository, TodoSearchHit},
+        },
     },
 };
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+#[derive(Debug, Clone)]
+pub struct TodoPage {
+    pub todos: Vec<Todo>,
+    pub next_cursor: Option<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchPage {
+    pub hits: Vec<TodoSearchHit>,
+    pub next_cursor: Option<Uuid>,
+}
+
+/// Full set of client-editable fields, used by create and full-replacement updates.
+#[derive(Debug, Clone, Default)]
+pub struct TodoInput {
+    pub title: String,
+    pub description: Option<String>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub remind_at: Option<DateTime<Utc>>,
+    pub priority: Priority,
+    /// Project to create the todo in. Updates keep the todo's project; use
+    /// [`TodoUsecase::move_todo_to_project`] to change it.
+    pub project_id: Option<Uuid>,
+    /// RFC 5545 RRULE text such as `FREQ=WEEKLY;BYDAY=MO`.
+    pub recurrence: Option<String>,
+}
+
+impl TodoInput {
+    fn recurrence(&self) -> Result<Option<Recurrence>, DomainError> {
+        self.recurrence.as_deref().map(str::parse).transpose()
+    }
+
+    fn into_todo(self, owner_id: Uuid, position: i64) -> Result<Todo, DomainError> {
+        let recurrence = self.recurrence()?;
+        let mut todo = Todo::new(owner_id, self.title, self.description);
+        todo.reschedule(self.due_at, self.remind_at, recurrence)?;
+        todo.priority = self.priority;
+        todo.position = position;
+        todo.project_id = self.project_id;
+        Ok(todo)
+    }
+
+    fn apply_to(self, todo: &mut Todo) -> Result<(), DomainError> {
+        todo.reschedule(self.due_at, self.remind_at, self.recurrence()?)?;
+        todo.update(self.title, self.description);
+        todo.prioritize(self.priority);
+        Ok(())
+    }
+}
+
+/// One item of a batch request, applied with the same rules as the single-todo methods.
+#[derive(Debug, Clone)]
+pub enum BatchOperation {
+    Create(TodoInput),
+    Update {
+        id: Uuid,
+        input: TodoInput,
+        expected_version: Option<i32>,
+    },
+    Complete {
+        id: Uuid,
+        strict: bool,
+        expected_version: Option<i32>,
+    },
+    Uncomplete {
+        id: Uuid,
+        expected_version: Option<i32>,
+    },
+    Delete {
+        id: Uuid,
+        expected_version: Option<i32>,
+    },
+}
+
+/// `user_id` is the acting user. Reads see their own todos and those shared with
+/// them; writes to a shared todo need the editor role, and deleting, restoring,
+/// purging or moving it to another project need the owner role. New todos
+/// belong to the user creating them.
 #[async_trait]
 pub trait TodoUsecase: Send + Sync + 'static {
-    async fn get_all_todos<C>(&self, conn: &C) -> Result<Vec<Todo>, UsecaseError>
+    async fn get_all_todos<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        query: TodoQuery,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<TodoPage, UsecaseError>
+    where
+        C: Conn;
+    /// Full-text search over titles and descriptions, best matches first.
+    async fn search_todos<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        text: &str,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<SearchPage, UsecaseError>
     where
         C: Conn;
-    async fn get_todo_by_id<C>(&self, conn: &C, id: Uuid) -> Result<Todo, UsecaseError>
+    async fn get_todo_by_id<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Todo, UsecaseError>
     where
         C: Conn;
     async fn create_todo<C>(
         &self,
         conn: &C,
-        title: String,
-        description: Option<String>,
+        user_id: Uuid,
+        input: TodoInput,
     ) -> Result<Todo, UsecaseError>
     where
         C: Conn;
     async fn update_todo<C>(
         &self,
         conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        input: TodoInput,
+        expected_version: Option<i32>,
+    ) -> Result<Todo, UsecaseError>
+    where
+        C: Conn;
+    async fn patch_todo<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        patch: TodoPatch,
+        expected_version: Option<i32>,
+    ) -> Result<Todo, UsecaseError>
+    where
+        C: Conn;
+    async fn delete_todo<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        expected_version: Option<i32>,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn;
+    /// Places the todo directly before `before` and/or after `after`, renumbering
+    /// the positions of every todo that shifts as a result.
+    async fn move_todo<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        before: Option<Uuid>,
+        after: Option<Uuid>,
+        expected_version: Option<i32>,
+    ) -> Result<Todo, UsecaseError>
+    where
+        C: Conn;
+    /// Moves the todo into `project_id`, or out of any project for `None`.
+    /// Neither the current nor the target project may be archived.
+    async fn move_todo_to_project<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        project_id: Option<Uuid>,
+        expected_version: Option<i32>,
+    ) -> Result<Todo, UsecaseError>
+    where
+        C: Conn;
+    /// Runs every operation in one transaction. In strict mode the first failure
+    /// rolls back the whole batch; otherwise each operation gets its own savepoint
+    /// and its outcome is reported individually.
+    async fn batch_todos<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        operations: Vec<BatchOperation>,
+        strict: bool,
+    ) -> Result<Vec<Result<Todo, UsecaseError>>, UsecaseError>
+    where
+        C: Conn;
+    async fn get_trashed_todos<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<TodoPage, UsecaseError>
+    where
+        C: Conn;
+    async fn restore_todo<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        expected_version: Option<i32>,
+    ) -> Result<Todo, UsecaseError>
+    where
+        C: Conn;
+    async fn purge_todo<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        expected_version: Option<i32>,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn;
+    /// Completion is refused while blockers are open and, with `strict` set, while
+    /// checklist items are open. Completing a recurring todo creates its next occurrence in the same transaction.
+    async fn mark_todo_completed<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        strict: bool,
+        expected_version: Option<i32>,
+    ) -> Result<Todo, UsecaseError>
+    where
+        C: Conn;
+    async fn unmark_todo_completed<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        expected_version: Option<i32>,
+    ) -> Result<Todo, UsecaseError>
+    where
+        C: Conn;
+    /// Returns the updated todo together with the id of the new item.
+    async fn add_checklist_item<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        title: String,
+        expected_version: Option<i32>,
+    ) -> Result<(Todo, Uuid), UsecaseError>
+    where
+        C: Conn;
+    #[allow(clippy::too_many_arguments)]
+    async fn update_checklist_item<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
         id: Uuid,
+        item_id: Uuid,
         title: String,
-        description: Option<String>,
+        completed: bool,
+        expected_version: Option<i32>,
     ) -> Result<Todo, UsecaseError>
     where
         C: Conn;
-    async fn delete_todo<C>(&self, conn: &C, id: Uuid) -> Result<(), UsecaseError>
+    async fn remove_checklist_item<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        item_id: Uuid,
+        expected_version: Option<i32>,
+    ) -> Result<(), UsecaseError>
     where
         C: Conn;
-    async fn mark_todo_completed<C>(&self, conn: &C, id: Uuid) -> Result<Todo, UsecaseError>
+    /// Live events on the todos visible to the user, starting after
+    /// `last_event_id` when a client resumes. With `project_id` only the
+    /// events on todos in that project are kept; the project must be visible
+    /// to the user.
+    async fn subscribe_events<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        project_id: Option<Uuid>,
+        last_event_id: Option<Uuid>,
+    ) -> Result<TodoEventFeed, UsecaseError>
     where
         C: Conn;
-    async fn unmark_todo_completed<C>(&self, conn: &C, id: Uuid) -> Result<Todo, UsecaseError>
+    /// The next event the user may still see, or `None` when the feed ended
+    /// and the client should resume from the last event it saw. Cancelling
+    /// the call loses no event.
+    async fn next_event<C>(&self, conn: &C, feed: &mut TodoEventFeed) -> Option<TodoEvent>
     where
         C: Conn;
 }
 
+/// How long a feed trusts the grants it loaded before an event on someone
+/// else's todo makes it load them again.
+const FEED_GRANTS_TTL: Duration = Duration::from_secs(5);
+
+/// The events of one user taken from the hub's subscription.
+pub struct TodoEventFeed {
+    subscription: EventSubscription,
+    user_id: Uuid,
+    project_id: Option<Uuid>,
+    /// Grants of the user as last loaded. They only decide whether an event
+    /// is worth checking: one they cover is checked against fresh grants, so
+    /// a revoked grant stops the events at once.
+    grants: Vec<ShareGrant>,
+    grants_loaded_at: Option<Instant>,
+    /// Event taken from the subscription whose check was interrupted.
+    pending: Option<TodoEvent>,
+}
+
+impl TodoEventFeed {
+    fn grants_stale(&self) -> bool {
+        self.grants_loaded_at
+            .is_none_or(|loaded_at| loaded_at.elapsed() >= FEED_GRANTS_TTL)
+    }
+}
+
+/// Trims the extra row fetched by the caller and derives the cursor for the next page.
+pub(crate) fn into_page(mut todos: Vec<Todo>, limit: u64) -> TodoPage {
+    let next_cursor = if todos.len() as u64 > limit {
+        todos.truncate(limit as usize);
+        todos.last().map(|todo| todo.id)
+    } else {
+        None
+    };
+    TodoPage { todos, next_cursor }
+}
+
+/// Rejects the write when the caller read an older version than the one stored.
+fn ensure_version(todo: &Todo, expected_version: Option<i32>) -> Result<(), TransactionError> {
+    match expected_version {
+        Some(expected) if expected != todo.version => {
+            Err(TransactionError::PreconditionFailed(format!(
+                "Todo with id {} is at version {}, expected {}",
+                todo.id, todo.version, expected
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Rejects writes to todos of an archived project, and todos placed in a
+/// project `owner_id` does not own; `None` means no project.
+async fn ensure_writable<P, C>(
+    projects: &P,
+    conn: &C,
+    owner_id: Uuid,
+    project_id: Option<Uuid>,
+) -> Result<(), TransactionError>
+where
+    P: ProjectRepository,
+    C: Conn,
+{
+    if let Some(project_id) = project_id {
+        let project = projects.find_by_id(conn, owner_id, project_id).await?;
+        if project.owner_id != owner_id {
+            return Err(TransactionError::NotFound(format!(
+                "Project with id {} not found",
+                project_id
+            )));
+        }
+        project.ensure_writable()?;
+    }
+    Ok(())
+}
+
+/// The events of one transaction: queued in the outbox as they are recorded,
+/// and kept to be broadcast once the transaction has committed.
+struct EventQueue<'a, O> {
+    outbox: &'a O,
+    events: Mutex<Vec<TodoEvent>>,
+}
+
+impl<'a, O> EventQueue<'a, O>
+where
+    O: OutboxRepository,
+{
+    fn new(outbox: &'a O) -> Self {
+        Self {
+            outbox,
+            events: Mutex::new(vec![]),
+        }
+    }
+
+    async fn push<C>(&self, conn: &C, events: Vec<TodoEvent>) -> Result<(), TransactionError>
+    where
+        C: Conn,
+    {
+        self.outbox.create(conn, events.clone()).await?;
+        self.events.lock().unwrap().extend(events);
+        Ok(())
+    }
+
+    /// Takes over the events of a savepoint that was released.
+    fn extend(&self, events: Vec<TodoEvent>) {
+        self.events.lock().unwrap().extend(events);
+    }
+
+    fn into_events(self) -> Vec<TodoEvent> {
+        self.events.into_inner().unwrap()
+    }
+}
+
+/// Inserts a new todo and queues the events it recorded.
+async fn insert_todo<R, O, C>(
+    repository: &R,
+    queue: &EventQueue<'_, O>,
+    conn: &C,
+    mut todo: Todo,
+) -> Result<Todo, TransactionError>
+where
+    R: TodoRepository,
+    O: OutboxRepository,
+    C: Conn,
+{
+    let events = todo.take_events();
+    let created = repository.create(conn, todo).await?;
+    queue.push(conn, events).await?;
+    Ok(created)
+}
+
+/// Saves the todo and queues the events it recorded.
+async fn save_todo<R, O, C>(
+    repository: &R,
+    queue: &EventQueue<'_, O>,
+    conn: &C,
+    mut todo: Todo,
+) -> Result<Todo, TransactionError>
+where
+    R: TodoRepository,
+    O: OutboxRepository,
+    C: Conn,
+{
+    let events = todo.take_events();
+    let saved = repository.update(conn, todo).await?;
+    queue.push(conn, events).await?;
+    Ok(saved)
+}
+
+/// Appends the next occurrence of a completed recurring todo to the list.
+async fn create_next_occurrence<R, A, O, C>(
+    repository: &R,
+    audits: &A,
+    queue: &EventQueue<'_, O>,
+    conn: &C,
+    user_id: Uuid,
+    next: Option<Todo>,
+) -> Result<(), TransactionError>
+where
+    R: TodoRepository,
+    A: AuditRepository,
+    O: OutboxRepository,
+    C: Conn,
+{
+    if let Some(mut next) = next {
+        next.position = repository.find_max_position(conn, next.owner_id).await? + 1;
+        let next = insert_todo(repository, queue, conn, next).await?;
+        audits
+            .create(
+                conn,
+                AuditEvent::new(user_id, AuditAction::Created, None, &next),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn apply_operation<R, P, D, S, A, O, C>(
+    repository: &R,
+    projects: &P,
+    dependencies: &D,
+    shares: &S,
+    audits: &A,
+    queue: &EventQueue<'_, O>,
+    conn: &C,
+    user_id: Uuid,
+    operation: BatchOperation,
+) -> Result<Todo, TransactionError>
+where
+    R: TodoRepository,
+    P: ProjectRepository,
+    D: DependencyRepository,
+    S: ShareRepository,
+    A: AuditRepository,
+    O: OutboxRepository,
+    C: Conn,
+{
+    let (todo, event) = match operation {
+        BatchOperation::Create(input) => {
+            ensure_writable(projects, conn, user_id, input.project_id).await?;
+            let position = repository.find_max_position(conn, user_id).await? + 1;
+            let todo =
+                insert_todo(repository, queue, conn, input.into_todo(user_id, position)?).await?;
+            let event = AuditEvent::new(user_id, AuditAction::Created, None, &todo);
+            (todo, event)
+        }
+        BatchOperation::Update {
+            id,
+            input,
+            expected_version,
+        } => {
+            let mut todo = repository.find_by_id(conn, user_id, id).await?;
+            ensure_version(&todo, expected_version)?;
+            ensure_role(shares, conn, user_id, &todo, ShareRole::Editor).await?;
+            ensure_writable(projects, conn, todo.owner_id, todo.project_id).await?;
+            let before = todo.clone();
+            input.apply_to(&mut todo)?;
+            let todo = save_todo(repository, queue, conn, todo).await?;
+            let event = AuditEvent::new(user_id, AuditAction::Updated, Some(&before), &todo);
+            (todo, event)
+        }
+        BatchOperation::Complete {
+            id,
+            strict,
+            expected_version,
+        } => {
+            let mut todo = repository.find_by_id(conn, user_id, id).await?;
+            ensure_version(&todo, expected_version)?;
+            ensure_role(shares, conn, user_id, &todo, ShareRole::Editor).await?;
+            ensure_writable(projects, conn, todo.owner_id, todo.project_id).await?;
+            // Blockers the user cannot see still hold the todo back.
+            let blockers = dependencies.find_blockers(conn, todo.owner_id, id).await?;
+            let before = todo.clone();
+            let next = todo.mark_completed(strict, &blockers)?;
+            let todo = save_todo(repository, queue, conn, todo).await?;
+            let event = AuditEvent::new(user_id, AuditAction::Completed, Some(&before), &todo);
+            create_next_occurrence(repository, audits, queue, conn, user_id, next).await?;
+            (todo, event)
+        }
+        BatchOperation::Uncomplete {
+            id,
+            expected_version,
+        } => {
+            let mut todo = repository.find_by_id(conn, user_id, id).await?;
+            ensure_version(&todo, expected_version)?;
+            ensure_role(shares, conn, user_id, &todo, ShareRole::Editor).await?;
+            ensure_writable(projects, conn, todo.owner_id, todo.project_id).await?;
+            let before = todo.clone();
+            todo.unmark_completed()?;
+            let todo = save_todo(repository, queue, conn, todo).await?;
+            let event = AuditEvent::new(user_id, AuditAction::Uncompleted, Some(&before), &todo);
+            (todo, event)
+        }
+        BatchOperation::Delete {
+            id,
+            expected_version,
+        } => {
+            let mut todo = repository.find_by_id(conn, user_id, id).await?;
+            ensure_version(&todo, expected_version)?;
+            ensure_role(shares, conn, user_id, &todo, ShareRole::Owner).await?;
+            ensure_writable(projects, conn, todo.owner_id, todo.project_id).await?;
+            let before = todo.clone();
+            todo.trash()?;
+            let todo = save_todo(repository, queue, conn, todo).await?;
+            let event = AuditEvent::new(user_id, AuditAction::Deleted, Some(&before), &todo);
+            (todo, event)
+        }
+    };
+    audits.create(conn, event).await?;
+    Ok(todo)
+}
+
+/// Prefixes the error message with the position of the failing batch operation.
+fn at_operation(index: usize, err: TransactionError) -> TransactionError {
+    let prefix = |msg: String| format!("operation {}: {}", index, msg);
+    match err {
+        TransactionError::Unexpected(msg) => TransactionError::Unexpected(prefix(msg)),
+        TransactionError::Conflict(msg) => TransactionError::Conflict(prefix(msg)),
+        TransactionError::NotFound(msg) => TransactionError::NotFound(prefix(msg)),
+        TransactionError::PreconditionFailed(msg) => {
+            TransactionError::PreconditionFailed(prefix(msg))
+        }
+        TransactionError::Validation(msg) => TransactionError::Validation(prefix(msg)),
+        TransactionError::Forbidden(msg) => TransactionError::Forbidden(prefix(msg)),
+    }
+}
+
 #[derive(Clone)]
-pub struct TodoUsecaseImpl<R, T> {
+pub struct TodoUsecaseImpl<R, P, D, S, A, O, H, T> {
     repository: Arc<R>,
+    project_repository: Arc<P>,
+    dependency_repository: Arc<D>,
+    share_repository: Arc<S>,
+    audit_repository: Arc<A>,
+    outbox_repository: Arc<O>,
+    event_hub: Arc<H>,
     transaction_service: Arc<T>,
 }
 
-impl<R, T> TodoUsecaseImpl<R, T> {
-    pub fn new(repository: R, transaction_service: T) -> Self {
+impl<R, P, D, S, A, O, H, T> TodoUsecaseImpl<R, P, D, S, A, O, H, T> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        repository: R,
+        project_repository: P,
+        dependency_repository: D,
+        share_repository: S,
+        audit_repository: A,
+        outbox_repository: O,
+        event_hub: H,
+        transaction_service: T,
+    ) -> Self {
         Self {
             repository: Arc::new(repository),
+            project_repository: Arc::new(project_repository),
+            dependency_repository: Arc::new(dependency_repository),
+            share_repository: Arc::new(share_repository),
+            audit_repository: Arc::new(audit_repository),
+            outbox_repository: Arc::new(outbox_repository),
+            event_hub: Arc::new(event_hub),
             transaction_service: Arc::new(transaction_service),
         }
     }
 }
 
+impl<R, P, D, S, A, O, H, T> TodoUsecaseImpl<R, P, D, S, A, O, H, T>
+where
+    H: EventHub,
+{
+    /// Called once the transaction that recorded the events has committed.
+    fn broadcast(&self, events: Vec<TodoEvent>) {
+        for event in events {
+            self.event_hub.publish(event);
+        }
+    }
+}
+
+/// Every write records an audit event and queues the todo's domain events in
+/// the outbox, both in the same transaction as the change. Once it commits,
+/// the events are also broadcast to live subscribers.
 #[async_trait]
-impl<R, T> TodoUsecase for TodoUsecaseImpl<R, T>
+impl<R, P, D, S, A, O, H, T> TodoUsecase for TodoUsecaseImpl<R, P, D, S, A, O, H, T>
 where
     R: TodoRepository + Send + Sync + 'static,
+    P: ProjectRepository + Send + Sync + 'static,
+    D: DependencyRepository + Send + Sync + 'static,
+    S: ShareRepository + Send + Sync + 'static,
+    A: AuditRepository + Send + Sync + 'static,
+    O: OutboxRepository + Send + Sync + 'static,
+    H: EventHub + 'static,
     T: TransactionService + Send + Sync + 'static,
 {
-    async fn get_all_todos<C>(&self, conn: &C) -> Result<Vec<Todo>, UsecaseError>
+    async fn get_all_todos<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        query: TodoQuery,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<TodoPage, UsecaseError>
+    where
+        C: Conn,
+    {
+        // Fetch one extra row to find out whether another page follows.
+        let todos = self
+            .repository
+            .find_by_query(conn, user_id, &query, after, limit + 1)
+            .await?;
+        Ok(into_page(todos, limit))
+    }
+
+    async fn search_todos<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        text: &str,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<SearchPage, UsecaseError>
     where
         C: Conn,
     {
-        let todos = self.repository.find_all(conn).await?;
-        Ok(todos)
+        let mut hits = self
+            .repository
+            .search(conn, user_id, text, after, limit + 1)
+            .await?;
+        let next_cursor = if hits.len() as u64 > limit {
+            hits.truncate(limit as usize);
+            hits.last().map(|hit| hit.todo.id)
+        } else {
+            None
+        };
+        Ok(SearchPage { hits, next_cursor })
     }
 
-    async fn get_todo_by_id<C>(&self, conn: &C, id: Uuid) -> Result<Todo, UsecaseError>
+    async fn get_todo_by_id<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Todo, UsecaseError>
     where
         C: Conn,
     {
-        let todo = self.repository.find_by_id(conn, id).await?;
+        let todo = self.repository.find_by_id(conn, user_id, id).await?;
         Ok(todo)
     }
 
     async fn create_todo<C>(
         &self,
         conn: &C,
-        title: String,
-        description: Option<String>,
+        user_id: Uuid,
+        input: TodoInput,
     ) -> Result<Todo, UsecaseError>
     where
         C: Conn,
     {
-        let todo = Todo::new(title, description);
-        let todo = self.repository.create(conn, todo).await?;
+        let repository = self.repository.clone();
+        let project_repository = self.project_repository.clone();
+        let audit_repository = self.audit_repository.clone();
+        let outbox_repository = self.outbox_repository.clone();
+        let (todo, events) = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let queue = EventQueue::new(outbox_repository.as_ref());
+                    ensure_writable(project_repository.as_ref(), tx, user_id, input.project_id)
+                        .await?;
+                    // New todos go to the end of the list.
+                    let position = repository.find_max_position(tx, user_id).await? + 1;
+                    let todo = insert_todo(
+                        repository.as_ref(),
+                        &queue,
+                        tx,
+                        input.into_todo(user_id, position)?,
+                    )
+                    .await?;
+                    audit_repository
+                        .create(
+                            tx,
+                            AuditEvent::new(user_id, AuditAction::Created, None, &todo),
+                        )
+                        .await?;
+                    Ok::<(Todo, Vec<TodoEvent>), TransactionError>((todo, queue.into_events()))
+                })
+            })
+            .await?;
+        self.broadcast(events);
         Ok(todo)
     }
 
     async fn update_todo<C>(
         &self,
         conn: &C,
+        user_id: Uuid,
         id: Uuid,
-        title: String,
-        description: Option<String>,
+        input: TodoInput,
+        expected_version: Option<i32>,
     ) -> Result<Todo, UsecaseError>
     where
         C: Conn,
     {
         let repository = self.repository.clone();
-        let todo = self
+        let project_repository = self.project_repository.clone();
+        let share_repository = self.share_repository.clone();
+        let audit_repository = self.audit_repository.clone();
+        let outbox_repository = self.outbox_repository.clone();
+        let (todo, events) = self
             .transaction_service
             .run(conn, move |tx| {
                 Box::pin(async move {
-                    let mut todo = repository.find_by_id(tx, id).await?;
-                    todo.update(title, description);
-                    let updated_todo = repository.update(tx, todo).await?;
-                    Ok::<Todo, TransactionError>(updated_todo)
+                    let queue = EventQueue::new(outbox_repository.as_ref());
+                    let mut todo = repository.find_by_id(tx, user_id, id).await?;
+                    ensure_version(&todo, expected_version)?;
+                    ensure_role(
+                        share_repository.as_ref(),
+                        tx,
+                        user_id,
+                        &todo,
+                        ShareRole::Editor,
+                    )
+                    .await?;
+                    ensure_writable(
+                        project_repository.as_ref(),
+                        tx,
+                        todo.owner_id,
+                        todo.project_id,
+                    )
+                    .await?;
+                    let before = todo.clone();
+                    input.apply_to(&mut todo)?;
+                    let updated_todo = save_todo(repository.as_ref(), &queue, tx, todo).await?;
+                    audit_repository
+                        .create(
+                            tx,
+                            AuditEvent::new(
+                                user_id,
+                                AuditAction::Updated,
+                                Some(&before),
+                                &updated_todo,
+                            ),
+                        )
+                        .await?;
+                    Ok::<(Todo, Vec<TodoEvent>), TransactionError>((
+                        updated_todo,
+                        queue.into_events(),
+                    ))
                 })
             })
             .await?;
+        self.broadcast(events);
         Ok(todo)
     }
 
-    async fn delete_todo<C>(&self, conn: &C, id: Uuid) -> Result<(), UsecaseError>
+    async fn patch_todo<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        patch: TodoPatch,
+        expected_version: Option<i32>,
+    ) -> Result<Todo, UsecaseError>
     where
         C: Conn,
     {
         let repository = self.repository.clone();
-        self.transaction_service
+        let project_repository = self.project_repository.clone();
+        let share_repository = self.share_repository.clone();
+        let audit_repository = self.audit_repository.clone();
+        let outbox_repository = self.outbox_repository.clone();
+        let (todo, events) = self
+            .transaction_service
             .run(conn, move |tx| {
                 Box::pin(async move {
-                    let todo = repository.find_by_id(tx, id).await?;
-                    repository.delete(tx, todo).await?;
-                    Ok::<(), TransactionError>(())
+                    let queue = EventQueue::new(outbox_repository.as_ref());
+                    let mut todo = repository.find_by_id(tx, user_id, id).await?;
+                    ensure_version(&todo, expected_version)?;
+                    ensure_role(
+                        share_repository.as_ref(),
+                        tx,
+                        user_id,
+                        &todo,
+                        ShareRole::Editor,
+                    )
+                    .await?;
+                    ensure_writable(
+                        project_repository.as_ref(),
+                        tx,
+                        todo.owner_id,
+                        todo.project_id,
+                    )
+                    .await?;
+                    let before = todo.clone();
+                    todo.apply_patch(patch)?;
+                    let patched_todo = save_todo(repository.as_ref(), &queue, tx, todo).await?;
+                    audit_repository
+                        .create(
+                            tx,
+                            AuditEvent::new(
+                                user_id,
+                                AuditAction::Updated,
+                                Some(&before),
+                                &patched_todo,
+                            ),
+                        )
+                        .await?;
+                    Ok::<(Todo, Vec<TodoEvent>), TransactionError>((
+                        patched_todo,
+                        queue.into_events(),
+                    ))
+                })
+            })
+            .await?;
+        self.broadcast(events);
+        Ok(todo)
+    }
+
+    async fn delete_todo<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        expected_version: Option<i32>,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn,
+    {
+        let repository = self.repository.clone();
+        let project_repository = self.project_repository.clone();
+        let share_repository = self.share_repository.clone();
+        let audit_repository = self.audit_repository.clone();
+        let outbox_repository = self.outbox_repository.clone();
+        let events = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let queue = EventQueue::new(outbox_repository.as_ref());
+                    let mut todo = repository.find_by_id(tx, user_id, id).await?;
+                    ensure_version(&todo, expected_version)?;
+                    ensure_role(
+                        share_repository.as_ref(),
+                        tx,
+                        user_id,
+                        &todo,
+                        ShareRole::Owner,
+                    )
+                    .await?;
+                    ensure_writable(
+                        project_repository.as_ref(),
+                        tx,
+                        todo.owner_id,
+                        todo.project_id,
+                    )
+                    .await?;
+                    let before = todo.clone();
+                    todo.trash()?;
+                    let trashed_todo = save_todo(repository.as_ref(), &queue, tx, todo).await?;
+                    audit_repository
+                        .create(
+                            tx,
+                            AuditEvent::new(
+                                user_id,
+                                AuditAction::Deleted,
+                                Some(&before),
+                                &trashed_todo,
+                            ),
+                        )
+                        .await?;
+                    Ok::<Vec<TodoEvent>, TransactionError>(queue.into_events())
                 })
             })
             .await?;
+        self.broadcast(events);
         Ok(())
     }
 
-    async fn mark_todo_completed<C>(&self, conn: &C, id: Uuid) -> Result<Todo, UsecaseError>
+    async fn move_todo<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        before: Option<Uuid>,
+        after: Option<Uuid>,
+        expected_version: Option<i32>,
+    ) -> Result<Todo, UsecaseError>
     where
         C: Conn,
     {
         let repository = self.repository.clone();
-        let todo = self
+        let project_repository = self.project_repository.clone();
+        let share_repository = self.share_repository.clone();
+        let audit_repository = self.audit_repository.clone();
+        let outbox_repository = self.outbox_repository.clone();
+        let (todo, events) = self
             .transaction_service
             .run(conn, move |tx| {
                 Box::pin(async move {
-                    let mut todo = repository.find_by_id(tx, id).await?;
-                    todo.mark_completed()?;
-                    let new_todo = repository.update(tx, todo).await?;
-                    Ok::<Todo, TransactionError>(new_todo)
+                    let queue = EventQueue::new(outbox_repository.as_ref());
+                    let todo = repository.find_by_id(tx, user_id, id).await?;
+                    ensure_version(&todo, expected_version)?;
+                    ensure_role(
+                        share_repository.as_ref(),
+                        tx,
+                        user_id,
+                        &todo,
+                        ShareRole::Editor,
+                    )
+                    .await?;
+                    ensure_writable(
+                        project_repository.as_ref(),
+                        tx,
+                        todo.owner_id,
+                        todo.project_id,
+                    )
+                    .await?;
+                    // Positions are per owner, so neighbours must come from the same list.
+                    for neighbour in before.into_iter().chain(after) {
+                        let neighbour = repository.find_by_id(tx, user_id, neighbour).await?;
+                        if neighbour.owner_id != todo.owner_id {
+                            return Err(TransactionError::Validation(format!(
+                                "Todo with id {} belongs to another list",
+                                neighbour.id
+                            )));
+                        }
+                    }
+                    let todos = repository.find_all(tx, todo.owner_id).await?;
+                    let previous = todo.clone();
+                    let mut moved_todo = todo;
+                    for changed in todo::reorder(todos, id, before, after)? {
+                        let changed = save_todo(repository.as_ref(), &queue, tx, changed).await?;
+                        if changed.id == id {
+                            moved_todo = changed;
+                        }
+                    }
+                    audit_repository
+                        .create(
+                            tx,
+                            AuditEvent::new(
+                                user_id,
+                                AuditAction::Updated,
+                                Some(&previous),
+                                &moved_todo,
+                            ),
+                        )
+                        .await?;
+                    Ok::<(Todo, Vec<TodoEvent>), TransactionError>((
+                        moved_todo,
+                        queue.into_events(),
+                    ))
                 })
             })
             .await?;
+        self.broadcast(events);
         Ok(todo)
     }
 
-    async fn unmark_todo_completed<C>(&self, conn: &C, id: Uuid) -> Result<Todo, UsecaseError>
+    async fn move_todo_to_project<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        project_id: Option<Uuid>,
+        expected_version: Option<i32>,
+    ) -> Result<Todo, UsecaseError>
     where
         C: Conn,
     {
         let repository = self.repository.clone();
-        let todo = self
+        let project_repository = self.project_repository.clone();
+        let share_repository = self.share_repository.clone();
+        let audit_repository = self.audit_repository.clone();
+        let outbox_repository = self.outbox_repository.clone();
+        let (todo, events) = self
             .transaction_service
             .run(conn, move |tx| {
                 Box::pin(async move {
-                    let mut todo = repository.find_by_id(tx, id).await?;
-                    todo.unmark_completed()?;
-                    let new_todo = repository.update(tx, todo).await?;
-                    Ok::<Todo, TransactionError>(new_todo)
+                    let queue = EventQueue::new(outbox_repository.as_ref());
+                    let mut todo = repository.find_by_id(tx, user_id, id).await?;
+                    ensure_version(&todo, expected_version)?;
+                    ensure_role(
+                        share_repository.as_ref(),
+                        tx,
+                        user_id,
+                        &todo,
+                        ShareRole::Owner,
+                    )
+                    .await?;
+                    ensure_writable(
+                        project_repository.as_ref(),
+                        tx,
+                        todo.owner_id,
+                        todo.project_id,
+                    )
+                    .await?;
+                    ensure_writable(project_repository.as_ref(), tx, todo.owner_id, project_id)
+                        .await?;
+                    let before = todo.clone();
+                    todo.move_to_project(project_id);
+                    let moved_todo = save_todo(repository.as_ref(), &queue, tx, todo).await?;
+                    audit_repository
+                        .create(
+                            tx,
+                            AuditEvent::new(
+                                user_id,
+                                AuditAction::Updated,
+                                Some(&before),
+                                &moved_todo,
+                            ),
+                        )
+                        .await?;
+                    Ok::<(Todo, Vec<TodoEvent>), TransactionError>((
+                        moved_todo,
+                        queue.into_events(),
+                    ))
                 })
             })
             .await?;
+        self.broadcast(events);
         Ok(todo)
     }
-}
-
-#[cfg(test)]
-mod tests {
 
-    use crate::domain::repositories::conn::tests::MockConn;
-    use crate::domain::repositories::errors::RepositoryError;
+    async fn batch_todos<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        operations: Vec<BatchOperation>,
+        strict: bool,
+    ) -> Result<Vec<Result<Todo, UsecaseError>>, UsecaseError>
+    where
+        C: Conn,
+    {
+        let repository = self.repository.clone();
+        let project_repository = self.project_repository.clone();
+        let dependency_repository = self.dependency_repository.clone();
+        let share_repository = self.share_repository.clone();
+        let audit_repository = self.audit_repository.clone();
+        let outbox_repository = self.outbox_repository.clone();
+        let transaction_service = self.transaction_service.clone();
+        let (results, events) = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let queue = EventQueue::new(outbox_repository.as_ref());
+                    let mut results = Vec::with_capacity(operations.len());
+                    for (index, operation) in operations.into_iter().enumerate() {
+                        if strict {
+                            let todo = apply_operation(
+                                repository.as_ref(),
+                                project_repository.as_ref(),
+                                dependency_repository.as_ref(),
+                                share_repository.as_ref(),
+                                audit_repository.as_ref(),
+                                &queue,
+                                tx,
+                                user_id,
+                                operation,
+                            )
+                            .await
+                            .map_err(|err| at_operation(index, err))?;
+                            results.push(Ok(todo));
+                        } else {
+                            let repository = repository.clone();
+                            let project_repository = project_repository.clone();
+                            let dependency_repository = dependency_repository.clone();
+                            let share_repository = share_repository.clone();
+                            let audit_repository = audit_repository.clone();
+                            let outbox_repository = outbox_repository.clone();
+                            let result = transaction_service
+                                .run(tx, move |savepoint| {
+                                    Box::pin(async move {
+                                        let queue = EventQueue::new(outbox_repository.as_ref());
+                                        let todo = apply_operation(
+                                            repository.as_ref(),
+                                            project_repository.as_ref(),
+                                            dependency_repository.as_ref(),
+                                            share_repository.as_ref(),
+                                            audit_repository.as_ref(),
+                                            &queue,
+                                            savepoint,
+                                            user_id,
+                                            operation,
+                                        )
+                                        .await?;
+                                        Ok((todo, queue.into_events()))
+                                    })
+                                })
+                                .await;
+                            // Events of a rolled back operation never happened.
+                            let result = result.map(|(todo, events)| {
+                                queue.extend(events);
+                                todo
+                            });
+                            results.push(result.map_err(UsecaseError::from));
+                        }
+                    }
+                    Ok::<(Vec<Result<Todo, UsecaseError>>, Vec<TodoEvent>), TransactionError>((
+                        results,
+                        queue.into_events(),
+                    ))
+                })
+            })
+            .await?;
+        self.broadcast(events);
+        Ok(results)
+    }
+
+    async fn get_trashed_todos<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<TodoPage, UsecaseError>
+    where
+        C: Conn,
+    {
+        let todos = self
+            .repository
+            .find_trashed(conn, user_id, after, limit + 1)
+            .await?;
+        Ok(into_page(todos, limit))
+    }
+
+    async fn restore_todo<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        expected_version: Option<i32>,
+    ) -> Result<Todo, UsecaseError>
+    where
+        C: Conn,
+    {
+        let repository = self.repository.clone();
+        let project_repository = self.project_repository.clone();
+        let share_repository = self.share_repository.clone();
+        let audit_repository = self.audit_repository.clone();
+        let outbox_repository = self.outbox_repository.clone();
+        let (todo, events) = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let queue = EventQueue::new(outbox_repository.as_ref());
+                    let mut todo = repository.find_trashed_by_id(tx, user_id, id).await?;
+                    ensure_version(&todo, expected_version)?;
+                    ensure_role(
+                        share_repository.as_ref(),
+                        tx,
+                        user_id,
+                        &todo,
+                        ShareRole::Owner,
+                    )
+                    .await?;
+                    ensure_writable(
+                        project_repository.as_ref(),
+                        tx,
+                        todo.owner_id,
+                        todo.project_id,
+                    )
+                    .await?;
+                    let before = todo.clone();
+                    todo.restore()?;
+                    let restored_todo = save_todo(repository.as_ref(), &queue, tx, todo).await?;
+                    audit_repository
+                        .create(
+                            tx,
+                            AuditEvent::new(
+                                user_id,
+                                AuditAction::Restored,
+                                Some(&before),
+                                &restored_todo,
+                            ),
+                        )
+                        .await?;
+                    Ok::<(Todo, Vec<TodoEvent>), TransactionError>((
+                        restored_todo,
+                        queue.into_events(),
+                    ))
+                })
+            })
+            .await?;
+        self.broadcast(events);
+        Ok(todo)
+    }
+
+    async fn purge_todo<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        expected_version: Option<i32>,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn,
+    {
+        let repository = self.repository.clone();
+        let project_repository = self.project_repository.clone();
+        let share_repository = self.share_repository.clone();
+        let audit_repository = self.audit_repository.clone();
+        self.transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let todo = repository.find_trashed_by_id(tx, user_id, id).await?;
+                    ensure_version(&todo, expected_version)?;
+                    ensure_role(
+                        share_repository.as_ref(),
+                        tx,
+                        user_id,
+                        &todo,
+                        ShareRole::Owner,
+                    )
+                    .await?;
+                    ensure_writable(
+                        project_repository.as_ref(),
+                        tx,
+                        todo.owner_id,
+                        todo.project_id,
+                    )
+                    .await?;
+                    let event = AuditEvent::purged(user_id, &todo);
+                    repository.delete(tx, todo).await?;
+                    audit_repository.create(tx, event).await?;
+                    Ok::<(), TransactionError>(())
+                })
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn mark_todo_completed<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        strict: bool,
+        expected_version: Option<i32>,
+    ) -> Result<Todo, UsecaseError>
+    where
+        C: Conn,
+    {
+        let repository = self.repository.clone();
+        let project_repository = self.project_repository.clone();
+        let share_repository = self.share_repository.clone();
+        let dependency_repository = self.dependency_repository.clone();
+        let audit_repository = self.audit_repository.clone();
+        let outbox_repository = self.outbox_repository.clone();
+        let (todo, events) = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let queue = EventQueue::new(outbox_repository.as_ref());
+                    let mut todo = repository.find_by_id(tx, user_id, id).await?;
+                    ensure_version(&todo, expected_version)?;
+                    ensure_role(
+                        share_repository.as_ref(),
+                        tx,
+                        user_id,
+                        &todo,
+                        ShareRole::Editor,
+                    )
+                    .await?;
+                    ensure_writable(
+                        project_repository.as_ref(),
+                        tx,
+                        todo.owner_id,
+                        todo.project_id,
+                    )
+                    .await?;
+                    let blockers = dependency_repository
+                        .find_blockers(tx, todo.owner_id, id)
+                        .await?;
+                    let before = todo.clone();
+                    let next = todo.mark_completed(strict, &blockers)?;
+                    let new_todo = save_todo(repository.as_ref(), &queue, tx, todo).await?;
+                    audit_repository
+                        .create(
+                            tx,
+                            AuditEvent::new(
+                                user_id,
+                                AuditAction::Completed,
+                                Some(&before),
+                                &new_todo,
+                            ),
+                        )
+                        .await?;
+                    create_next_occurrence(
+                        repository.as_ref(),
+                        audit_repository.as_ref(),
+                        &queue,
+                        tx,
+                        user_id,
+                        next,
+                    )
+                    .await?;
+                    Ok::<(Todo, Vec<TodoEvent>), TransactionError>((new_todo, queue.into_events()))
+                })
+            })
+            .await?;
+        self.broadcast(events);
+        Ok(todo)
+    }
+
+    async fn unmark_todo_completed<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        expected_version: Option<i32>,
+    ) -> Result<Todo, UsecaseError>
+    where
+        C: Conn,
+    {
+        let repository = self.repository.clone();
+        let project_repository = self.project_repository.clone();
+        let share_repository = self.share_repository.clone();
+        let audit_repository = self.audit_repository.clone();
+        let outbox_repository = self.outbox_repository.clone();
+        let (todo, events) = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let queue = EventQueue::new(outbox_repository.as_ref());
+                    let mut todo = repository.find_by_id(tx, user_id, id).await?;
+                    ensure_version(&todo, expected_version)?;
+                    ensure_role(
+                        share_repository.as_ref(),
+                        tx,
+                        user_id,
+                        &todo,
+                        ShareRole::Editor,
+                    )
+                    .await?;
+                    ensure_writable(
+                        project_repository.as_ref(),
+                        tx,
+                        todo.owner_id,
+                        todo.project_id,
+                    )
+                    .await?;
+                    let before = todo.clone();
+                    todo.unmark_completed()?;
+                    let new_todo = save_todo(repository.as_ref(), &queue, tx, todo).await?;
+                    audit_repository
+                        .create(
+                            tx,
+                            AuditEvent::new(
+                                user_id,
+                                AuditAction::Uncompleted,
+                                Some(&before),
+                                &new_todo,
+                            ),
+                        )
+                        .await?;
+                    Ok::<(Todo, Vec<TodoEvent>), TransactionError>((new_todo, queue.into_events()))
+                })
+            })
+            .await?;
+        self.broadcast(events);
+        Ok(todo)
+    }
+
+    async fn add_checklist_item<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        title: String,
+        expected_version: Option<i32>,
+    ) -> Result<(Todo, Uuid), UsecaseError>
+    where
+        C: Conn,
+    {
+        let repository = self.repository.clone();
+        let project_repository = self.project_repository.clone();
+        let share_repository = self.share_repository.clone();
+        let audit_repository = self.audit_repository.clone();
+        let outbox_repository = self.outbox_repository.clone();
+        let (todo, item_id, events) = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let queue = EventQueue::new(outbox_repository.as_ref());
+                    let mut todo = repository.find_by_id(tx, user_id, id).await?;
+                    ensure_version(&todo, expected_version)?;
+                    ensure_role(
+                        share_repository.as_ref(),
+                        tx,
+                        user_id,
+                        &todo,
+                        ShareRole::Editor,
+                    )
+                    .await?;
+                    ensure_writable(
+                        project_repository.as_ref(),
+                        tx,
+                        todo.owner_id,
+                        todo.project_id,
+                    )
+                    .await?;
+                    let before = todo.clone();
+                    let item_id = todo.add_item(title);
+                    let updated_todo = save_todo(repository.as_ref(), &queue, tx, todo).await?;
+                    audit_repository
+                        .create(
+                            tx,
+                            AuditEvent::new(
+                                user_id,
+                                AuditAction::Updated,
+                                Some(&before),
+                                &updated_todo,
+                            ),
+                        )
+                        .await?;
+                    Ok::<(Todo, Uuid, Vec<TodoEvent>), TransactionError>((
+                        updated_todo,
+                        item_id,
+                        queue.into_events(),
+                    ))
+                })
+            })
+            .await?;
+        self.broadcast(events);
+        Ok((todo, item_id))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn update_checklist_item<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        item_id: Uuid,
+        title: String,
+        completed: bool,
+        expected_version: Option<i32>,
+    ) -> Result<Todo, UsecaseError>
+    where
+        C: Conn,
+    {
+        let repository = self.repository.clone();
+        let project_repository = self.project_repository.clone();
+        let share_repository = self.share_repository.clone();
+        let audit_repository = self.audit_repository.clone();
+        let outbox_repository = self.outbox_repository.clone();
+        let (todo, events) = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let queue = EventQueue::new(outbox_repository.as_ref());
+                    let mut todo = repository.find_by_id(tx, user_id, id).await?;
+                    ensure_version(&todo, expected_version)?;
+                    ensure_role(
+                        share_repository.as_ref(),
+                        tx,
+                        user_id,
+                        &todo,
+                        ShareRole::Editor,
+                    )
+                    .await?;
+                    ensure_writable(
+                        project_repository.as_ref(),
+                        tx,
+                        todo.owner_id,
+                        todo.project_id,
+                    )
+                    .await?;
+                    let before = todo.clone();
+                    todo.update_item(item_id, title, completed)?;
+                    let updated_todo = save_todo(repository.as_ref(), &queue, tx, todo).await?;
+                    audit_repository
+                        .create(
+                            tx,
+                            AuditEvent::new(
+                                user_id,
+                                AuditAction::Updated,
+                                Some(&before),
+                                &updated_todo,
+                            ),
+                        )
+                        .await?;
+                    Ok::<(Todo, Vec<TodoEvent>), TransactionError>((
+                        updated_todo,
+                        queue.into_events(),
+                    ))
+                })
+            })
+            .await?;
+        self.broadcast(events);
+        Ok(todo)
+    }
+
+    async fn remove_checklist_item<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        item_id: Uuid,
+        expected_version: Option<i32>,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn,
+    {
+        let repository = self.repository.clone();
+        let project_repository = self.project_repository.clone();
+        let share_repository = self.share_repository.clone();
+        let audit_repository = self.audit_repository.clone();
+        let outbox_repository = self.outbox_repository.clone();
+        let events = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let queue = EventQueue::new(outbox_repository.as_ref());
+                    let mut todo = repository.find_by_id(tx, user_id, id).await?;
+                    ensure_version(&todo, expected_version)?;
+                    ensure_role(
+                        share_repository.as_ref(),
+                        tx,
+                        user_id,
+                        &todo,
+                        ShareRole::Editor,
+                    )
+                    .await?;
+                    ensure_writable(
+                        project_repository.as_ref(),
+                        tx,
+                        todo.owner_id,
+                        todo.project_id,
+                    )
+                    .await?;
+                    let before = todo.clone();
+                    todo.remove_item(item_id)?;
+                    let updated_todo = save_todo(repository.as_ref(), &queue, tx, todo).await?;
+                    audit_repository
+                        .create(
+                            tx,
+                            AuditEvent::new(
+                                user_id,
+                                AuditAction::Updated,
+                                Some(&before),
+                                &updated_todo,
+                            ),
+                        )
+                        .await?;
+                    Ok::<Vec<TodoEvent>, TransactionError>(queue.into_events())
+                })
+            })
+            .await?;
+        self.broadcast(events);
+        Ok(())
+    }
+
+    async fn subscribe_events<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        project_id: Option<Uuid>,
+        last_event_id: Option<Uuid>,
+    ) -> Result<TodoEventFeed, UsecaseError>
+    where
+        C: Conn,
+    {
+        if let Some(project_id) = project_id {
+            self.project_repository
+                .find_by_id(conn, user_id, project_id)
+                .await?;
+        }
+        Ok(TodoEventFeed {
+            subscription: self.event_hub.subscribe(last_event_id),
+            user_id,
+            project_id,
+            grants: vec![],
+            grants_loaded_at: None,
+            pending: None,
+        })
+    }
+
+    async fn next_event<C>(&self, conn: &C, feed: &mut TodoEventFeed) -> Option<TodoEvent>
+    where
+        C: Conn,
+    {
+        loop {
+            let event = match feed.pending.take() {
+                Some(event) => event,
+                None => feed.subscription.recv().await?,
+            };
+            if feed.project_id.is_some() && event.project_id != feed.project_id {
+                continue;
+            }
+            if event.owner_id == feed.user_id {
+                return Some(event);
+            }
+            if feed.grants_stale() || role_on_event(&event, feed.user_id, &feed.grants).is_some() {
+                feed.pending = Some(event);
+                // On failure the feed ends; the client resumes from the last
+                // event it saw, which replays the pending one.
+                feed.grants = self
+                    .share_repository
+                    .find_for_user(conn, feed.user_id)
+                    .await
+                    .ok()?;
+                feed.grants_loaded_at = Some(Instant::now());
+                let event = feed.pending.take()?;
+                if role_on_event(&event, feed.user_id, &feed.grants).is_some() {
+                    return Some(event);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::domain::models::audit::AuditQuery;
+    use crate::domain::models::dependency::{Dependency, DependencyGraph};
+    use crate::domain::models::project::Project;
+    use crate::domain::models::share::{ShareGrant, ShareTarget};
+    use crate::domain::models::todo_event::{OutboxMessage, TodoEvent, TodoEventKind};
+    use crate::domain::repositories::conn::tests::MockConn;
+    use crate::domain::repositories::errors::RepositoryError;
+    use crate::domain::repositories::todo_repository::tests::MockTodoRepository;
+    use crate::domain::repositories::todo_repository::{SortOrder, TodoSortKey};
 
     use super::*;
     use std::sync::{Arc, Mutex};
 
-    #[derive(Clone)]
-    struct MockTodoRepository {
-        todos: Arc<Mutex<Vec<Todo>>>,
+    const OWNER_ID: Uuid = Uuid::from_u128(0xe1e2e3e4_f1f2_a1a2_b1b2_b3b4b5b6b7b8);
+
+    fn todos() -> Vec<Todo> {
+        vec![
+            Todo {
+                id: Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap(),
+                title: "Test Todo 1".into(),
+                description: None,
+                completed: false,
+                created_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+                updated_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+                version: 1,
+                deleted_at: None,
+                due_at: None,
+                remind_at: None,
+                priority: Priority::Medium,
+                position: 1,
+                items: vec![],
+                project_id: None,
+                recurrence: None,
+                owner_id: OWNER_ID,
+                events: vec![],
+            },
+            Todo {
+                id: Uuid::parse_str("b1b2b3b4c1c2d1d2e1e2e3e4e5e6e7e8").unwrap(),
+                title: "Test Todo 2".into(),
+                description: Some("Description for Test Todo 2".into()),
+                completed: true,
+                created_at: DateTime::from_timestamp(1_700_000_100, 0).unwrap(),
+                updated_at: DateTime::from_timestamp(1_700_000_100, 0).unwrap(),
+                version: 1,
+                deleted_at: None,
+                due_at: None,
+                remind_at: None,
+                priority: Priority::High,
+                position: 2,
+                items: vec![],
+                project_id: None,
+                recurrence: None,
+                owner_id: OWNER_ID,
+                events: vec![],
+            },
+        ]
+    }
+
+    const ACTIVE_PROJECT_ID: &str = "c1c2c3c4d1d2e1e2f1f2f3f4f5f6f7f8";
+    const ARCHIVED_PROJECT_ID: &str = "d1d2d3d4e1e2f1f2a1a2a3a4a5a6a7a8";
+    /// Owned by another user.
+    const FOREIGN_PROJECT_ID: &str = "e1e2e3e4f1f2a1a2b1b2b3b4b5b6b7b8";
+
+    /// Every project is visible to every user, as if it were shared with them.
+    struct MockProjectRepository {
+        projects: Vec<Project>,
     }
 
-    impl MockTodoRepository {
+    impl MockProjectRepository {
         pub fn new() -> Self {
+            let project = |id: &str, owner_id: Uuid, name: &str, archived: bool| Project {
+                id: Uuid::parse_str(id).unwrap(),
+                owner_id,
+                name: name.into(),
+                color: None,
+                archived,
+                created_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+                updated_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            };
             Self {
-                todos: Arc::new(Mutex::new(vec![
-                    Todo {
-                        id: Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap(),
-                        title: "Test Todo 1".into(),
-                        description: None,
-                        completed: false,
-                    },
-                    Todo {
-                        id: Uuid::parse_str("b1b2b3b4c1c2d1d2e1e2e3e4e5e6e7e8").unwrap(),
-                        title: "Test Todo 2".into(),
-                        description: Some("Description for Test Todo 2".into()),
-                        completed: true,
-                    },
-                ])),
+                projects: vec![
+                    project(ACTIVE_PROJECT_ID, OWNER_ID, "Active", false),
+                    project(ARCHIVED_PROJECT_ID, OWNER_ID, "Archived", true),
+                    project(FOREIGN_PROJECT_ID, Uuid::now_v7(), "Foreign", false),
+                ],
             }
         }
     }
 
     #[async_trait]
-    impl TodoRepository for MockTodoRepository {
-        async fn find_all<C>(&self, _conn: &C) -> Result<Vec<Todo>, RepositoryError> {
-            let todos = self.todos.lock().unwrap();
-            Ok(todos.clone())
+    impl ProjectRepository for MockProjectRepository {
+        async fn find_all<C>(
+            &self,
+            _conn: &C,
+            _user_id: Uuid,
+            archived: Option<bool>,
+        ) -> Result<Vec<Project>, RepositoryError> {
+            Ok(self
+                .projects
+                .iter()
+                .filter(|p| archived.is_none_or(|a| p.archived == a))
+                .cloned()
+                .collect())
         }
 
-        async fn find_by_id<C>(&self, _conn: &C, id: Uuid) -> Result<Todo, RepositoryError> {
-            let todos = self.todos.lock().unwrap();
-            todos
+        async fn find_by_id<C>(
+            &self,
+            _conn: &C,
+            _user_id: Uuid,
+            id: Uuid,
+        ) -> Result<Project, RepositoryError> {
+            self.projects
                 .iter()
-                .find(|todo| todo.id == id)
+                .find(|p| p.id == id)
                 .cloned()
                 .ok_or(RepositoryError::NotFound(format!(
-                    "Todo with id {} not found",
+                    "Project with id {} not found",
                     id
                 )))
         }
 
-        async fn create<C>(&self, _conn: &C, todo: Todo) -> Result<Todo, RepositoryError> {
-            self.todos.lock().unwrap().push(todo.clone());
-            Ok(todo)
+        async fn create<C>(
+            &self,
+            _conn: &C,
+            _project: Project,
+        ) -> Result<Project, RepositoryError> {
+            unimplemented!()
         }
 
-        async fn update<C>(&self, _conn: &C, todo: Todo) -> Result<Todo, RepositoryError> {
-            let target_id = todo.id;
-            let mut todos = self.todos.lock().unwrap();
-            todos
-                .iter_mut()
-                .find(|t| t.id == todo.id)
-                .map(|t| {
-                    *t = todo.clone();
-                    todo
-                })
-                .ok_or(RepositoryError::NotFound(format!(
-                    "Todo with id {} not found",
-                    target_id
-                )))
+        async fn update<C>(
+            &self,
+            _conn: &C,
+            _project: Project,
+        ) -> Result<Project, RepositoryError> {
+            unimplemented!()
         }
 
-        async fn delete<C>(&self, _conn: &C, todo: Todo) -> Result<(), RepositoryError> {
-            let mut todos = self.todos.lock().unwrap();
-            if let Some(index) = todos.iter().position(|t| t.id == todo.id) {
-                todos.remove(index);
-                Ok(())
-            } else {
-                Err(RepositoryError::NotFound(format!(
-                    "Todo with id {} not found",
-                    todo.id
-                )))
-            }
+        async fn delete<C>(&self, _conn: &C, _project: Project) -> Result<(), RepositoryError> {
+            unimplemented!()
+        }
+    }
+
+    /// Edges as `(todo_id, blocker_id)`, resolved against a shared todo list.
+    #[derive(Clone, Default)]
+    struct MockDependencyRepository {
+        todos: Arc<Mutex<Vec<Todo>>>,
+        edges: Arc<Mutex<Vec<(Uuid, Uuid)>>>,
+    }
+
+    #[async_trait]
+    impl DependencyRepository for MockDependencyRepository {
+        async fn find_blockers<C>(
+            &self,
+            _conn: &C,
+            _user_id: Uuid,
+            todo_id: Uuid,
+        ) -> Result<Vec<Todo>, RepositoryError> {
+            let edges = self.edges.lock().unwrap();
+            Ok(self
+                .todos
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|todo| edges.contains(&(todo_id, todo.id)))
+                .cloned()
+                .collect())
+        }
+
+        async fn find_graph<C>(
+            &self,
+            _conn: &C,
+            _user_id: Uuid,
+            _todo_id: Uuid,
+        ) -> Result<DependencyGraph, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn create<C>(
+            &self,
+            _conn: &C,
+            _dependency: Dependency,
+        ) -> Result<(), RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn delete<C>(
+            &self,
+            _conn: &C,
+            _todo_id: Uuid,
+            _blocker_id: Uuid,
+        ) -> Result<(), RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn lock<C>(&self, _conn: &C) -> Result<(), RepositoryError> {
+            unimplemented!()
+        }
+    }
+
+    /// Shares its grants with a `MockTodoRepository` so shared todos become visible.
+    #[derive(Clone, Default)]
+    struct MockShareRepository {
+        grants: Arc<Mutex<Vec<ShareGrant>>>,
+    }
+
+    #[async_trait]
+    impl ShareRepository for MockShareRepository {
+        async fn find_all<C>(
+            &self,
+            _conn: &C,
+            _owner_id: Uuid,
+            _target: ShareTarget,
+        ) -> Result<Vec<ShareGrant>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn find_by_id<C>(
+            &self,
+            _conn: &C,
+            _owner_id: Uuid,
+            _target: ShareTarget,
+            _id: Uuid,
+        ) -> Result<ShareGrant, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn find_for_todo<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            _todo: &Todo,
+        ) -> Result<Vec<ShareGrant>, RepositoryError> {
+            Ok(self
+                .grants
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|grant| grant.user_id == user_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn find_for_user<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+        ) -> Result<Vec<ShareGrant>, RepositoryError> {
+            Ok(self
+                .grants
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|grant| grant.user_id == user_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn create<C>(
+            &self,
+            _conn: &C,
+            _grant: ShareGrant,
+        ) -> Result<ShareGrant, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn update<C>(
+            &self,
+            _conn: &C,
+            _grant: ShareGrant,
+        ) -> Result<ShareGrant, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn delete<C>(&self, _conn: &C, _grant: ShareGrant) -> Result<(), RepositoryError> {
+            unimplemented!()
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockAuditRepository {
+        events: Arc<Mutex<Vec<AuditEvent>>>,
+    }
+
+    #[async_trait]
+    impl AuditRepository for MockAuditRepository {
+        async fn find_by_todo<C>(
+            &self,
+            _conn: &C,
+            todo_id: Uuid,
+            _after: Option<Uuid>,
+            _limit: u64,
+        ) -> Result<Vec<AuditEvent>, RepositoryError> {
+            Ok(self
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|event| event.todo_id == todo_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_query<C>(
+            &self,
+            _conn: &C,
+            _owner_id: Uuid,
+            _query: AuditQuery,
+            _after: Option<Uuid>,
+            _limit: u64,
+        ) -> Result<Vec<AuditEvent>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn create<C>(
+            &self,
+            _conn: &C,
+            event: AuditEvent,
+        ) -> Result<AuditEvent, RepositoryError> {
+            self.events.lock().unwrap().push(event.clone());
+            Ok(event)
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockOutboxRepository {
+        events: Arc<Mutex<Vec<TodoEvent>>>,
+    }
+
+    #[async_trait]
+    impl OutboxRepository for MockOutboxRepository {
+        async fn create<C>(
+            &self,
+            _conn: &C,
+            events: Vec<TodoEvent>,
+        ) -> Result<(), RepositoryError> {
+            self.events.lock().unwrap().extend(events);
+            Ok(())
+        }
+
+        async fn find_due<C>(
+            &self,
+            _conn: &C,
+            _now: DateTime<Utc>,
+            _limit: u64,
+        ) -> Result<Vec<OutboxMessage>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn update<C>(
+            &self,
+            _conn: &C,
+            _message: OutboxMessage,
+        ) -> Result<OutboxMessage, RepositoryError> {
+            unimplemented!()
+        }
+    }
+
+    /// Records what is published; subscriptions replay it all and end.
+    #[derive(Clone, Default)]
+    struct MockEventHub {
+        published: Arc<Mutex<Vec<TodoEvent>>>,
+    }
+
+    impl EventHub for MockEventHub {
+        fn publish(&self, event: TodoEvent) {
+            self.published.lock().unwrap().push(event);
+        }
+
+        fn subscribe(&self, _last_event_id: Option<Uuid>) -> EventSubscription {
+            let (_, receiver) = tokio::sync::broadcast::channel(1);
+            EventSubscription::new(self.published.lock().unwrap().clone(), receiver)
         }
     }
 
@@ -299,29 +2095,188 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_todo_usecase_impl_get_all_todos() {
-        let repository = MockTodoRepository::new();
+    async fn test_todo_usecase_impl_get_all_todos() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository,
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+
+        let result = usecase
+            .get_all_todos(&MockConn, OWNER_ID, TodoQuery::default(), None, 20)
+            .await;
+
+        assert!(result.is_ok());
+        let page = result.unwrap();
+        assert_eq!(page.todos.len(), 2);
+        assert_eq!(page.todos[0].title, "Test Todo 1");
+        assert_eq!(page.todos[1].title, "Test Todo 2");
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_get_all_todos_paginated() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository,
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+
+        let first_page = usecase
+            .get_all_todos(&MockConn, OWNER_ID, TodoQuery::default(), None, 1)
+            .await
+            .unwrap();
+        assert_eq!(first_page.todos.len(), 1);
+        assert_eq!(first_page.todos[0].title, "Test Todo 1");
+        assert_eq!(first_page.next_cursor, Some(first_page.todos[0].id));
+
+        let second_page = usecase
+            .get_all_todos(
+                &MockConn,
+                OWNER_ID,
+                TodoQuery::default(),
+                first_page.next_cursor,
+                1,
+            )
+            .await
+            .unwrap();
+        assert_eq!(second_page.todos.len(), 1);
+        assert_eq!(second_page.todos[0].title, "Test Todo 2");
+        assert_eq!(second_page.next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_get_all_todos_filtered_and_sorted() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository,
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+
+        let query = TodoQuery {
+            completed: Some(false),
+            ..TodoQuery::default()
+        };
+        let page = usecase
+            .get_all_todos(&MockConn, OWNER_ID, query, None, 20)
+            .await
+            .unwrap();
+        assert_eq!(page.todos.len(), 1);
+        assert_eq!(page.todos[0].title, "Test Todo 1");
+
+        let query = TodoQuery {
+            text: Some("description for".into()),
+            ..TodoQuery::default()
+        };
+        let page = usecase
+            .get_all_todos(&MockConn, OWNER_ID, query, None, 20)
+            .await
+            .unwrap();
+        assert_eq!(page.todos.len(), 1);
+        assert_eq!(page.todos[0].title, "Test Todo 2");
+
+        let query = TodoQuery {
+            sort_key: TodoSortKey::Title,
+            sort_order: SortOrder::Desc,
+            ..TodoQuery::default()
+        };
+        let page = usecase
+            .get_all_todos(&MockConn, OWNER_ID, query, None, 20)
+            .await
+            .unwrap();
+        assert_eq!(page.todos[0].title, "Test Todo 2");
+        assert_eq!(page.todos[1].title, "Test Todo 1");
+
+        let query = TodoQuery {
+            created_after: DateTime::from_timestamp(1_700_000_050, 0),
+            ..TodoQuery::default()
+        };
+        let page = usecase
+            .get_all_todos(&MockConn, OWNER_ID, query, None, 20)
+            .await
+            .unwrap();
+        assert_eq!(page.todos.len(), 1);
+        assert_eq!(page.todos[0].title, "Test Todo 2");
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_search_todos() {
+        let repository = MockTodoRepository::new(todos());
         let transaction_service = MockTransactionService::new();
-        let usecase = TodoUsecaseImpl::new(repository, transaction_service);
+        let usecase = TodoUsecaseImpl::new(
+            repository,
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
 
-        let result = usecase.get_all_todos(&MockConn).await;
+        let first_page = usecase
+            .search_todos(&MockConn, OWNER_ID, "test todo", None, 1)
+            .await
+            .unwrap();
+        assert_eq!(first_page.hits.len(), 1);
+        assert_eq!(first_page.hits[0].todo.title, "Test Todo 2");
+        assert_eq!(first_page.next_cursor, Some(first_page.hits[0].todo.id));
 
-        assert!(result.is_ok());
-        let todos = result.unwrap();
-        assert_eq!(todos.len(), 2);
-        assert_eq!(todos[0].title, "Test Todo 1");
-        assert_eq!(todos[1].title, "Test Todo 2");
+        let second_page = usecase
+            .search_todos(&MockConn, OWNER_ID, "test todo", first_page.next_cursor, 1)
+            .await
+            .unwrap();
+        assert_eq!(second_page.hits.len(), 1);
+        assert_eq!(second_page.hits[0].todo.title, "Test Todo 1");
+        assert!(second_page.hits[0].rank < first_page.hits[0].rank);
+        assert_eq!(second_page.next_cursor, None);
+
+        let result = usecase
+            .search_todos(&MockConn, OWNER_ID, "test todo", Some(Uuid::now_v7()), 1)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
     }
 
     #[tokio::test]
     async fn test_todo_usecase_impl_get_todo_by_id() {
-        let repository = MockTodoRepository::new();
+        let repository = MockTodoRepository::new(todos());
         let transaction_service = MockTransactionService::new();
-        let usecase = TodoUsecaseImpl::new(repository, transaction_service);
+        let usecase = TodoUsecaseImpl::new(
+            repository,
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
 
         let result = usecase
             .get_todo_by_id(
                 &MockConn,
+                OWNER_ID,
                 Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap(),
             )
             .await;
@@ -333,34 +2288,459 @@ mod tests {
         assert!(!todo.completed);
     }
 
+    #[tokio::test]
+    async fn test_todo_usecase_impl_other_owner() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository,
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+        let other_owner = Uuid::now_v7();
+        let id = Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap();
+
+        let page = usecase
+            .get_all_todos(&MockConn, other_owner, TodoQuery::default(), None, 20)
+            .await
+            .unwrap();
+        assert!(page.todos.is_empty());
+
+        let result = usecase.get_todo_by_id(&MockConn, other_owner, id).await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+
+        let result = usecase.delete_todo(&MockConn, other_owner, id, None).await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+        assert!(
+            usecase
+                .get_todo_by_id(&MockConn, OWNER_ID, id)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_shared_todo_roles() {
+        let repository = MockTodoRepository::new(todos());
+        let shares = MockShareRepository {
+            grants: repository.grants.clone(),
+        };
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            shares,
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            MockTransactionService::new(),
+        );
+        let guest = Uuid::now_v7();
+        let id = Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap();
+        let grant = |role| {
+            *repository.grants.lock().unwrap() =
+                vec![ShareGrant::new(OWNER_ID, guest, ShareTarget::Todo(id), role).unwrap()];
+        };
+        let input = || TodoInput {
+            title: "Shared".into(),
+            ..TodoInput::default()
+        };
+
+        // Viewers can read the todo but not change it.
+        grant(ShareRole::Viewer);
+        let page = usecase
+            .get_all_todos(&MockConn, guest, TodoQuery::default(), None, 20)
+            .await
+            .unwrap();
+        assert_eq!(page.todos.len(), 1);
+        assert!(usecase.get_todo_by_id(&MockConn, guest, id).await.is_ok());
+        let result = usecase
+            .update_todo(&MockConn, guest, id, input(), None)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+        let result = usecase
+            .mark_todo_completed(&MockConn, guest, id, false, None)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+
+        // Editors can update and complete it but not delete it.
+        grant(ShareRole::Editor);
+        let todo = usecase
+            .update_todo(&MockConn, guest, id, input(), None)
+            .await
+            .unwrap();
+        assert_eq!(todo.title, "Shared");
+        assert_eq!(todo.owner_id, OWNER_ID);
+        let todo = usecase
+            .mark_todo_completed(&MockConn, guest, id, false, None)
+            .await
+            .unwrap();
+        assert!(todo.completed);
+        let result = usecase.delete_todo(&MockConn, guest, id, None).await;
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+        let results = usecase
+            .batch_todos(
+                &MockConn,
+                guest,
+                vec![BatchOperation::Delete {
+                    id,
+                    expected_version: None,
+                }],
+                false,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(results[0], Err(UsecaseError::Forbidden(_))));
+
+        // Owners can delete it.
+        grant(ShareRole::Owner);
+        usecase
+            .delete_todo(&MockConn, guest, id, None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_audit_events() {
+        let repository = MockTodoRepository::new(todos());
+        let shares = MockShareRepository {
+            grants: repository.grants.clone(),
+        };
+        let audits = MockAuditRepository::default();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            shares,
+            audits.clone(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            MockTransactionService::new(),
+        );
+        let editor = Uuid::now_v7();
+        let input = |title: &str| TodoInput {
+            title: title.into(),
+            ..TodoInput::default()
+        };
+
+        let todo = usecase
+            .create_todo(&MockConn, OWNER_ID, input("Audited"))
+            .await
+            .unwrap();
+        *repository.grants.lock().unwrap() = vec![
+            ShareGrant::new(
+                OWNER_ID,
+                editor,
+                ShareTarget::Todo(todo.id),
+                ShareRole::Editor,
+            )
+            .unwrap(),
+        ];
+        usecase
+            .update_todo(&MockConn, editor, todo.id, input("Renamed"), None)
+            .await
+            .unwrap();
+        usecase
+            .mark_todo_completed(&MockConn, editor, todo.id, false, None)
+            .await
+            .unwrap();
+        usecase
+            .unmark_todo_completed(&MockConn, OWNER_ID, todo.id, None)
+            .await
+            .unwrap();
+        // Refused writes leave no trace.
+        let result = usecase.delete_todo(&MockConn, editor, todo.id, None).await;
+        assert!(matches!(result, Err(UsecaseError::Forbidden(_))));
+        usecase
+            .delete_todo(&MockConn, OWNER_ID, todo.id, None)
+            .await
+            .unwrap();
+
+        let events = audits
+            .find_by_todo(&MockConn, todo.id, None, 10)
+            .await
+            .unwrap();
+        let actions: Vec<AuditAction> = events.iter().map(|event| event.action).collect();
+        assert_eq!(
+            actions,
+            vec![
+                AuditAction::Created,
+                AuditAction::Updated,
+                AuditAction::Completed,
+                AuditAction::Uncompleted,
+                AuditAction::Deleted,
+            ]
+        );
+        assert!(events[0].before.is_none());
+        assert_eq!(events[1].actor_id, editor);
+        assert_eq!(events[1].owner_id, OWNER_ID);
+        assert_eq!(events[1].before.as_ref().unwrap()["title"], "Audited");
+        assert_eq!(events[1].after.as_ref().unwrap()["title"], "Renamed");
+        assert_eq!(events[2].after.as_ref().unwrap()["completed"], true);
+        assert!(events[4].after.as_ref().unwrap()["deleted_at"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_outbox_events() {
+        let repository = MockTodoRepository::new(todos());
+        let outbox = MockOutboxRepository::default();
+        let hub = MockEventHub::default();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            outbox.clone(),
+            hub.clone(),
+            MockTransactionService::new(),
+        );
+        let input = |title: &str| TodoInput {
+            title: title.into(),
+            ..TodoInput::default()
+        };
+
+        let todo = usecase
+            .create_todo(&MockConn, OWNER_ID, input("Queued"))
+            .await
+            .unwrap();
+        assert!(todo.events.is_empty());
+        usecase
+            .update_todo(&MockConn, OWNER_ID, todo.id, input("Renamed"), None)
+            .await
+            .unwrap();
+        usecase
+            .mark_todo_completed(&MockConn, OWNER_ID, todo.id, false, None)
+            .await
+            .unwrap();
+        usecase
+            .unmark_todo_completed(&MockConn, OWNER_ID, todo.id, None)
+            .await
+            .unwrap();
+        usecase
+            .delete_todo(&MockConn, OWNER_ID, todo.id, None)
+            .await
+            .unwrap();
+
+        let events = outbox.events.lock().unwrap().clone();
+        let kinds: Vec<TodoEventKind> = events.iter().map(|event| event.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TodoEventKind::TodoCreated,
+                TodoEventKind::TodoUpdated,
+                TodoEventKind::TodoCompleted,
+                TodoEventKind::TodoUncompleted,
+                TodoEventKind::TodoDeleted,
+            ]
+        );
+        assert!(
+            events
+                .iter()
+                .all(|event| event.todo_id == todo.id && event.owner_id == OWNER_ID)
+        );
+        // Nothing is left pending on the stored todo.
+        let stored = repository.todos.lock().unwrap().clone();
+        assert!(stored.iter().all(|todo| todo.events.is_empty()));
+
+        // Committed events are broadcast as well.
+        assert_eq!(*hub.published.lock().unwrap(), events);
+
+        // Operations rolled back to their savepoint broadcast nothing.
+        hub.published.lock().unwrap().clear();
+        let results = usecase
+            .batch_todos(
+                &MockConn,
+                OWNER_ID,
+                vec![
+                    BatchOperation::Create(input("Batched")),
+                    BatchOperation::Update {
+                        id: Uuid::now_v7(),
+                        input: input("Missing"),
+                        expected_version: None,
+                    },
+                ],
+                false,
+            )
+            .await
+            .unwrap();
+        assert!(results[1].is_err());
+        let published = hub.published.lock().unwrap().clone();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].kind, TodoEventKind::TodoCreated);
+
+        // Subscribers only see the events on their own todos.
+        hub.publish(TodoEvent::new(
+            TodoEventKind::TodoCreated,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            Utc::now(),
+        ));
+        let mut feed = usecase
+            .subscribe_events(&MockConn, OWNER_ID, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            usecase.next_event(&MockConn, &mut feed).await,
+            Some(published[0].clone())
+        );
+        assert_eq!(usecase.next_event(&MockConn, &mut feed).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_event_feed_follows_grants() {
+        let shares = MockShareRepository::default();
+        let hub = MockEventHub::default();
+        let usecase = TodoUsecaseImpl::new(
+            MockTodoRepository::new(todos()),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            shares.clone(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            hub.clone(),
+            MockTransactionService::new(),
+        );
+        let viewer_id = Uuid::now_v7();
+        let project_id = Uuid::parse_str(ACTIVE_PROJECT_ID).unwrap();
+        let event = |todo_id: Uuid, project_id: Option<Uuid>| TodoEvent {
+            project_id,
+            ..TodoEvent::new(TodoEventKind::TodoUpdated, todo_id, OWNER_ID, Utc::now())
+        };
+        let shared = event(Uuid::now_v7(), None);
+        let in_project = event(Uuid::now_v7(), Some(project_id));
+        let private = event(Uuid::now_v7(), None);
+        for event in [&shared, &in_project, &private] {
+            hub.publish(event.clone());
+        }
+        shares.grants.lock().unwrap().extend([
+            ShareGrant::new(
+                OWNER_ID,
+                viewer_id,
+                ShareTarget::Todo(shared.todo_id),
+                ShareRole::Viewer,
+            )
+            .unwrap(),
+            ShareGrant::new(
+                OWNER_ID,
+                viewer_id,
+                ShareTarget::Project(project_id),
+                ShareRole::Viewer,
+            )
+            .unwrap(),
+        ]);
+        let next_events = async |feed: &mut TodoEventFeed| {
+            let mut events = vec![];
+            while let Some(event) = usecase.next_event(&MockConn, feed).await {
+                events.push(event);
+            }
+            events
+        };
+
+        // Events on the todos shared with the user reach them, the others do not.
+        let mut feed = usecase
+            .subscribe_events(&MockConn, viewer_id, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            next_events(&mut feed).await,
+            vec![shared.clone(), in_project.clone()]
+        );
+        let mut feed = usecase
+            .subscribe_events(&MockConn, viewer_id, Some(project_id), None)
+            .await
+            .unwrap();
+        assert_eq!(next_events(&mut feed).await, vec![in_project.clone()]);
+
+        // A revoked grant stops the events at once, even with grants loaded.
+        let mut feed = usecase
+            .subscribe_events(&MockConn, viewer_id, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            usecase.next_event(&MockConn, &mut feed).await,
+            Some(shared.clone())
+        );
+        shares
+            .grants
+            .lock()
+            .unwrap()
+            .retain(|grant| grant.target == ShareTarget::Todo(shared.todo_id));
+        assert_eq!(next_events(&mut feed).await, vec![]);
+
+        let result = usecase
+            .subscribe_events(&MockConn, viewer_id, Some(Uuid::now_v7()), None)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
     #[tokio::test]
     async fn test_todo_usecase_impl_create_todo() {
-        let repository = MockTodoRepository::new();
+        let repository = MockTodoRepository::new(todos());
         let transaction_service = MockTransactionService::new();
-        let usecase = TodoUsecaseImpl::new(repository.clone(), transaction_service);
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
 
         let result = usecase
-            .create_todo(&MockConn, "New Todo".into(), Some("Description".into()))
+            .create_todo(
+                &MockConn,
+                OWNER_ID,
+                TodoInput {
+                    title: "New Todo".into(),
+                    description: Some("Description".into()),
+                    ..TodoInput::default()
+                },
+            )
             .await;
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().title, "New Todo");
+        let todo = result.unwrap();
+        assert_eq!(todo.title, "New Todo");
+        assert_eq!(todo.priority, Priority::Medium);
+        assert_eq!(todo.position, 3);
         let len = { repository.todos.lock().unwrap().len() };
         assert_eq!(len, 3);
     }
 
     #[tokio::test]
     async fn test_todo_usecase_impl_update_todo() {
-        let repository = MockTodoRepository::new();
+        let repository = MockTodoRepository::new(todos());
         let transaction_service = MockTransactionService::new();
-        let usecase = TodoUsecaseImpl::new(repository.clone(), transaction_service);
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
 
         let result = usecase
             .update_todo(
                 &MockConn,
+                OWNER_ID,
                 Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap(),
-                "Updated Todo".into(),
-                Some("Updated Description".into()),
+                TodoInput {
+                    title: "Updated Todo".into(),
+                    description: Some("Updated Description".into()),
+                    ..TodoInput::default()
+                },
+                Some(1),
             )
             .await;
 
@@ -369,6 +2749,12 @@ mod tests {
         assert_eq!(todo.title, "Updated Todo");
         assert_eq!(todo.description, Some("Updated Description".into()));
         assert!(!todo.completed);
+        assert_eq!(
+            todo.created_at,
+            DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+        );
+        assert!(todo.updated_at > todo.created_at);
+        assert_eq!(todo.version, 2);
 
         let todos = {
             let todos = repository.todos.lock().unwrap();
@@ -381,18 +2767,409 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_todo_usecase_impl_delete_todo() {
-        let repository = MockTodoRepository::new();
+    async fn test_todo_usecase_impl_create_todo_invalid_schedule() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+
+        let result = usecase
+            .create_todo(
+                &MockConn,
+                OWNER_ID,
+                TodoInput {
+                    title: "New Todo".into(),
+                    due_at: DateTime::from_timestamp(1_700_000_000, 0),
+                    remind_at: DateTime::from_timestamp(1_700_000_100, 0),
+                    ..TodoInput::default()
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+        let len = { repository.todos.lock().unwrap().len() };
+        assert_eq!(len, 2);
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_get_overdue_todos() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+        usecase
+            .create_todo(
+                &MockConn,
+                OWNER_ID,
+                TodoInput {
+                    title: "Overdue Todo".into(),
+                    due_at: DateTime::from_timestamp(1_700_000_000, 0),
+                    ..TodoInput::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let query = TodoQuery {
+            overdue: Some(true),
+            ..TodoQuery::default()
+        };
+        let page = usecase
+            .get_all_todos(&MockConn, OWNER_ID, query, None, 20)
+            .await
+            .unwrap();
+
+        assert_eq!(page.todos.len(), 1);
+        assert_eq!(page.todos[0].title, "Overdue Todo");
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_update_todo_stale_version() {
+        let repository = MockTodoRepository::new(todos());
         let transaction_service = MockTransactionService::new();
-        let usecase = TodoUsecaseImpl::new(repository.clone(), transaction_service);
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
 
         let result = usecase
-            .delete_todo(
+            .update_todo(
                 &MockConn,
+                OWNER_ID,
                 Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap(),
+                TodoInput {
+                    title: "Updated Todo".into(),
+                    ..TodoInput::default()
+                },
+                Some(2),
+            )
+            .await;
+
+        assert!(matches!(result, Err(UsecaseError::PreconditionFailed(_))));
+        let title = { repository.todos.lock().unwrap()[0].title.clone() };
+        assert_eq!(title, "Test Todo 1");
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_patch_todo() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+        let id = Uuid::parse_str("b1b2b3b4c1c2d1d2e1e2e3e4e5e6e7e8").unwrap();
+
+        let result = usecase
+            .patch_todo(
+                &MockConn,
+                OWNER_ID,
+                id,
+                TodoPatch {
+                    description: Some(None),
+                    ..TodoPatch::default()
+                },
+                Some(1),
+            )
+            .await;
+
+        assert!(result.is_ok());
+        let todo = result.unwrap();
+        assert_eq!(todo.title, "Test Todo 2");
+        assert_eq!(todo.description, None);
+
+        let result = usecase
+            .patch_todo(
+                &MockConn,
+                OWNER_ID,
+                id,
+                TodoPatch {
+                    title: Some(None),
+                    ..TodoPatch::default()
+                },
+                None,
+            )
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_delete_todo() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+        let id = Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap();
+
+        let result = usecase.delete_todo(&MockConn, OWNER_ID, id, None).await;
+
+        assert!(result.is_ok());
+        let len = { repository.todos.lock().unwrap().len() };
+        assert_eq!(len, 2);
+        assert!(
+            usecase
+                .get_todo_by_id(&MockConn, OWNER_ID, id)
+                .await
+                .is_err()
+        );
+        let trash = usecase
+            .get_trashed_todos(&MockConn, OWNER_ID, None, 20)
+            .await
+            .unwrap();
+        assert_eq!(trash.todos.len(), 1);
+        assert_eq!(trash.todos[0].id, id);
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_move_todo() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+        let first_id = Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap();
+        let second_id = Uuid::parse_str("b1b2b3b4c1c2d1d2e1e2e3e4e5e6e7e8").unwrap();
+
+        let moved = usecase
+            .move_todo(
+                &MockConn,
+                OWNER_ID,
+                second_id,
+                Some(first_id),
+                None,
+                Some(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(moved.position, 1);
+        assert_eq!(moved.version, 2);
+
+        let page = usecase
+            .get_all_todos(&MockConn, OWNER_ID, TodoQuery::default(), None, 20)
+            .await
+            .unwrap();
+        assert_eq!(page.todos[0].title, "Test Todo 2");
+        assert_eq!(page.todos[1].title, "Test Todo 1");
+
+        let result = usecase
+            .move_todo(
+                &MockConn,
+                OWNER_ID,
+                first_id,
+                None,
+                Some(Uuid::now_v7()),
+                None,
+            )
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+
+        let result = usecase
+            .move_todo(&MockConn, OWNER_ID, first_id, None, None, None)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_batch_todos() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+
+        let results = usecase
+            .batch_todos(
+                &MockConn,
+                OWNER_ID,
+                vec![
+                    BatchOperation::Create(TodoInput {
+                        title: "Batch Todo".into(),
+                        ..TodoInput::default()
+                    }),
+                    BatchOperation::Complete {
+                        id: Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap(),
+                        strict: false,
+                        expected_version: None,
+                    },
+                    BatchOperation::Complete {
+                        id: Uuid::parse_str("b1b2b3b4c1c2d1d2e1e2e3e4e5e6e7e8").unwrap(),
+                        strict: false,
+                        expected_version: None,
+                    },
+                    BatchOperation::Delete {
+                        id: Uuid::nil(),
+                        expected_version: None,
+                    },
+                ],
+                false,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].as_ref().unwrap().title, "Batch Todo");
+        assert!(results[1].as_ref().unwrap().completed);
+        assert!(matches!(results[2], Err(UsecaseError::Conflict(_))));
+        assert!(matches!(results[3], Err(UsecaseError::NotFound(_))));
+        let len = { repository.todos.lock().unwrap().len() };
+        assert_eq!(len, 3);
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_batch_todos_strict() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+
+        let result = usecase
+            .batch_todos(
+                &MockConn,
+                OWNER_ID,
+                vec![
+                    BatchOperation::Uncomplete {
+                        id: Uuid::parse_str("b1b2b3b4c1c2d1d2e1e2e3e4e5e6e7e8").unwrap(),
+                        expected_version: None,
+                    },
+                    BatchOperation::Uncomplete {
+                        id: Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap(),
+                        expected_version: None,
+                    },
+                ],
+                true,
             )
             .await;
 
+        match result {
+            Err(UsecaseError::Conflict(msg)) => assert!(msg.starts_with("operation 1:")),
+            _ => panic!("expected a conflict from the second operation"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_restore_todo() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+        let id = Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap();
+
+        assert!(
+            usecase
+                .restore_todo(&MockConn, OWNER_ID, id, None)
+                .await
+                .is_err()
+        );
+        usecase
+            .delete_todo(&MockConn, OWNER_ID, id, None)
+            .await
+            .unwrap();
+        let result = usecase.restore_todo(&MockConn, OWNER_ID, id, None).await;
+
+        assert!(result.is_ok());
+        assert!(result.unwrap().deleted_at.is_none());
+        assert!(
+            usecase
+                .get_todo_by_id(&MockConn, OWNER_ID, id)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_purge_todo() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+        let id = Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap();
+
+        assert!(
+            usecase
+                .purge_todo(&MockConn, OWNER_ID, id, None)
+                .await
+                .is_err()
+        );
+        usecase
+            .delete_todo(&MockConn, OWNER_ID, id, None)
+            .await
+            .unwrap();
+        let result = usecase.purge_todo(&MockConn, OWNER_ID, id, None).await;
+
         assert!(result.is_ok());
         let len = { repository.todos.lock().unwrap().len() };
         assert_eq!(len, 1);
@@ -400,14 +3177,26 @@ mod tests {
 
     #[tokio::test]
     async fn test_todo_usecase_impl_mark_todo_completed() {
-        let repository = MockTodoRepository::new();
+        let repository = MockTodoRepository::new(todos());
         let transaction_service = MockTransactionService::new();
-        let usecase = TodoUsecaseImpl::new(repository.clone(), transaction_service);
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
 
         let result = usecase
             .mark_todo_completed(
                 &MockConn,
+                OWNER_ID,
                 Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap(),
+                false,
+                None,
             )
             .await;
 
@@ -416,16 +3205,143 @@ mod tests {
         assert!(completed);
     }
 
+    #[tokio::test]
+    async fn test_todo_usecase_impl_mark_todo_completed_recurring() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+        let due_at = DateTime::from_timestamp(1_800_000_000, 0).unwrap();
+
+        let todo = usecase
+            .create_todo(
+                &MockConn,
+                OWNER_ID,
+                TodoInput {
+                    title: "Daily chore".into(),
+                    due_at: Some(due_at),
+                    recurrence: Some("FREQ=DAILY;INTERVAL=2".into()),
+                    ..TodoInput::default()
+                },
+            )
+            .await
+            .unwrap();
+        let completed = usecase
+            .mark_todo_completed(&MockConn, OWNER_ID, todo.id, false, None)
+            .await
+            .unwrap();
+        assert!(completed.completed);
+        assert!(completed.recurrence.is_none());
+
+        let todos = repository.todos.lock().unwrap().clone();
+        assert_eq!(todos.len(), 4);
+        let next = &todos[3];
+        assert_eq!(next.title, "Daily chore");
+        assert!(!next.completed);
+        assert_eq!(next.due_at, Some(due_at + chrono::Duration::days(2)));
+        assert_eq!(next.position, 4);
+        assert!(next.recurrence.is_some());
+
+        let result = usecase
+            .create_todo(
+                &MockConn,
+                OWNER_ID,
+                TodoInput {
+                    title: "Broken chore".into(),
+                    due_at: Some(due_at),
+                    recurrence: Some("FREQ=HOURLY".into()),
+                    ..TodoInput::default()
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_checklist_items() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+        let id = Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap();
+
+        let (todo, item_id) = usecase
+            .add_checklist_item(&MockConn, OWNER_ID, id, "First step".into(), Some(1))
+            .await
+            .unwrap();
+        assert_eq!(todo.items.len(), 1);
+        assert_eq!(todo.version, 2);
+
+        let result = usecase
+            .mark_todo_completed(&MockConn, OWNER_ID, id, true, None)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Conflict(_))));
+
+        let todo = usecase
+            .update_checklist_item(
+                &MockConn,
+                OWNER_ID,
+                id,
+                item_id,
+                "First step".into(),
+                true,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(todo.progress(), (1, 1));
+        let result = usecase
+            .mark_todo_completed(&MockConn, OWNER_ID, id, true, None)
+            .await;
+        assert!(result.is_ok());
+
+        usecase
+            .remove_checklist_item(&MockConn, OWNER_ID, id, item_id, None)
+            .await
+            .unwrap();
+        let result = usecase
+            .remove_checklist_item(&MockConn, OWNER_ID, id, item_id, None)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+    }
+
     #[tokio::test]
     async fn test_todo_usecase_impl_mark_todo_incomplete() {
-        let repository = MockTodoRepository::new();
+        let repository = MockTodoRepository::new(todos());
         let transaction_service = MockTransactionService::new();
-        let usecase = TodoUsecaseImpl::new(repository.clone(), transaction_service);
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
 
         let result = usecase
             .unmark_todo_completed(
                 &MockConn,
+                OWNER_ID,
                 Uuid::parse_str("b1b2b3b4c1c2d1d2e1e2e3e4e5e6e7e8").unwrap(),
+                None,
             )
             .await;
 
@@ -433,4 +3349,174 @@ mod tests {
         let completed = { repository.todos.lock().unwrap()[1].completed };
         assert!(!completed);
     }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_move_todo_to_project() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+        let id = Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap();
+        let active = Uuid::parse_str(ACTIVE_PROJECT_ID).unwrap();
+
+        let todo = usecase
+            .move_todo_to_project(&MockConn, OWNER_ID, id, Some(active), Some(1))
+            .await
+            .unwrap();
+        assert_eq!(todo.project_id, Some(active));
+        let page = usecase
+            .get_all_todos(
+                &MockConn,
+                OWNER_ID,
+                TodoQuery {
+                    project_id: Some(active),
+                    ..TodoQuery::default()
+                },
+                None,
+                20,
+            )
+            .await
+            .unwrap();
+        assert_eq!(page.todos.len(), 1);
+        assert_eq!(page.todos[0].id, id);
+
+        let result = usecase
+            .move_todo_to_project(&MockConn, OWNER_ID, id, Some(Uuid::now_v7()), None)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+        // Todos only go into projects of their own owner.
+        let foreign = Uuid::parse_str(FOREIGN_PROJECT_ID).unwrap();
+        let result = usecase
+            .move_todo_to_project(&MockConn, OWNER_ID, id, Some(foreign), None)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+        let result = usecase
+            .create_todo(
+                &MockConn,
+                OWNER_ID,
+                TodoInput {
+                    title: "Elsewhere".into(),
+                    project_id: Some(foreign),
+                    ..TodoInput::default()
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+
+        let todo = usecase
+            .move_todo_to_project(&MockConn, OWNER_ID, id, None, None)
+            .await
+            .unwrap();
+        assert_eq!(todo.project_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_archived_project_is_read_only() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            MockDependencyRepository::default(),
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+        let id = Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap();
+        let archived = Uuid::parse_str(ARCHIVED_PROJECT_ID).unwrap();
+
+        let result = usecase
+            .move_todo_to_project(&MockConn, OWNER_ID, id, Some(archived), None)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Conflict(_))));
+
+        let result = usecase
+            .create_todo(
+                &MockConn,
+                OWNER_ID,
+                TodoInput {
+                    title: "In archive".into(),
+                    project_id: Some(archived),
+                    ..TodoInput::default()
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Conflict(_))));
+
+        repository.todos.lock().unwrap()[0].project_id = Some(archived);
+        let result = usecase
+            .mark_todo_completed(&MockConn, OWNER_ID, id, false, None)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Conflict(_))));
+        let result = usecase
+            .move_todo_to_project(&MockConn, OWNER_ID, id, None, None)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Conflict(_))));
+        assert!(!repository.todos.lock().unwrap()[0].completed);
+    }
+
+    #[tokio::test]
+    async fn test_todo_usecase_impl_mark_todo_completed_blocked() {
+        let repository = MockTodoRepository::new(todos());
+        let transaction_service = MockTransactionService::new();
+        let first = Uuid::parse_str("a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8").unwrap();
+        let second = Uuid::parse_str("b1b2b3b4c1c2d1d2e1e2e3e4e5e6e7e8").unwrap();
+        let dependencies = MockDependencyRepository {
+            todos: repository.todos.clone(),
+            edges: Arc::new(Mutex::new(vec![(second, first)])),
+        };
+        let usecase = TodoUsecaseImpl::new(
+            repository.clone(),
+            MockProjectRepository::new(),
+            dependencies,
+            MockShareRepository::default(),
+            MockAuditRepository::default(),
+            MockOutboxRepository::default(),
+            MockEventHub::default(),
+            transaction_service,
+        );
+
+        usecase
+            .unmark_todo_completed(&MockConn, OWNER_ID, second, None)
+            .await
+            .unwrap();
+        let result = usecase
+            .mark_todo_completed(&MockConn, OWNER_ID, second, false, None)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Conflict(_))));
+
+        let results = usecase
+            .batch_todos(
+                &MockConn,
+                OWNER_ID,
+                vec![
+                    BatchOperation::Complete {
+                        id: first,
+                        strict: false,
+                        expected_version: None,
+                    },
+                    BatchOperation::Complete {
+                        id: second,
+                        strict: false,
+                        expected_version: None,
+                    },
+                ],
+                true,
+            )
+            .await
+            .unwrap();
+        assert!(results.iter().all(|result| result.is_ok()));
+        let todos = repository.todos.lock().unwrap().clone();
+        assert!(todos[0].completed && todos[1].completed);
+    }
 }
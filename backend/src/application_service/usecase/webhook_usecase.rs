@@ -0,0 +1,901 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    application_service::{
+        service::{
+            event_publisher::EventPublisher,
+            transaction_service::{TransactionError, TransactionService},
+            webhook_sender::WebhookSender,
+        },
+        usecase::errors::UsecaseError,
+    },
+    domain::{
+        models::{
+            todo_event::{TodoEvent, TodoEventKind},
+            webhook::{DeliveryAttempt, WEBHOOK_LEASE, Webhook, WebhookDelivery},
+        },
+        repositories::{
+            conn::Conn, webhook_delivery_repository::WebhookDeliveryRepository,
+            webhook_repository::WebhookRepository,
+        },
+    },
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct WebhookInput {
+    pub url: String,
+    /// Empty subscribes to every kind of event.
+    pub events: Vec<TodoEventKind>,
+    pub secret: String,
+    pub active: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeliveryPage {
+    pub deliveries: Vec<WebhookDelivery>,
+    pub next_cursor: Option<Uuid>,
+}
+
+/// A delivery together with every attempt made at it.
+#[derive(Debug, Clone)]
+pub struct DeliveryDetails {
+    pub delivery: WebhookDelivery,
+    pub attempts: Vec<DeliveryAttempt>,
+}
+
+fn into_page(mut deliveries: Vec<WebhookDelivery>, limit: u64) -> DeliveryPage {
+    let next_cursor = if deliveries.len() as u64 > limit {
+        deliveries.truncate(limit as usize);
+        deliveries.last().map(|delivery| delivery.id)
+    } else {
+        None
+    };
+    DeliveryPage {
+        deliveries,
+        next_cursor,
+    }
+}
+
+/// Webhooks notify their owner about changes to the todos they own.
+#[async_trait]
+pub trait WebhookUsecase: Send + Sync + 'static {
+    async fn get_webhooks<C>(&self, conn: &C, user_id: Uuid) -> Result<Vec<Webhook>, UsecaseError>
+    where
+        C: Conn;
+    async fn get_webhook<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Webhook, UsecaseError>
+    where
+        C: Conn;
+    async fn create_webhook<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        input: WebhookInput,
+    ) -> Result<Webhook, UsecaseError>
+    where
+        C: Conn;
+    async fn update_webhook<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        input: WebhookInput,
+    ) -> Result<Webhook, UsecaseError>
+    where
+        C: Conn;
+    async fn delete_webhook<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<(), UsecaseError>
+    where
+        C: Conn;
+    async fn get_deliveries<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        webhook_id: Uuid,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<DeliveryPage, UsecaseError>
+    where
+        C: Conn;
+    async fn get_delivery<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        webhook_id: Uuid,
+        id: Uuid,
+    ) -> Result<DeliveryDetails, UsecaseError>
+    where
+        C: Conn;
+    /// Queues the delivery to be sent again, delivered or not.
+    async fn replay_delivery<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        webhook_id: Uuid,
+        id: Uuid,
+    ) -> Result<WebhookDelivery, UsecaseError>
+    where
+        C: Conn;
+    /// Sends up to `limit` due deliveries and returns how many were handled.
+    /// Failed sends are rescheduled with backoff rather than reported as errors.
+    async fn deliver_pending<C>(&self, conn: &C, limit: u64) -> Result<usize, UsecaseError>
+    where
+        C: Conn;
+}
+
+pub struct WebhookUsecaseImpl<W, D, S, T> {
+    webhook_repository: Arc<W>,
+    delivery_repository: Arc<D>,
+    sender: Arc<S>,
+    transaction_service: Arc<T>,
+}
+
+// Not derived: the outbox dispatcher and the delivery loop share one instance,
+// and none of the dependencies needs to be `Clone` for that.
+impl<W, D, S, T> Clone for WebhookUsecaseImpl<W, D, S, T> {
+    fn clone(&self) -> Self {
+        Self {
+            webhook_repository: Arc::clone(&self.webhook_repository),
+            delivery_repository: Arc::clone(&self.delivery_repository),
+            sender: Arc::clone(&self.sender),
+            transaction_service: Arc::clone(&self.transaction_service),
+        }
+    }
+}
+
+impl<W, D, S, T> WebhookUsecaseImpl<W, D, S, T> {
+    pub fn new(
+        webhook_repository: W,
+        delivery_repository: D,
+        sender: S,
+        transaction_service: T,
+    ) -> Self {
+        Self {
+            webhook_repository: Arc::new(webhook_repository),
+            delivery_repository: Arc::new(delivery_repository),
+            sender: Arc::new(sender),
+            transaction_service: Arc::new(transaction_service),
+        }
+    }
+}
+
+#[async_trait]
+impl<W, D, S, T> WebhookUsecase for WebhookUsecaseImpl<W, D, S, T>
+where
+    W: WebhookRepository + Send + Sync + 'static,
+    D: WebhookDeliveryRepository + Send + Sync + 'static,
+    S: WebhookSender + 'static,
+    T: TransactionService + Send + Sync + 'static,
+{
+    async fn get_webhooks<C>(&self, conn: &C, user_id: Uuid) -> Result<Vec<Webhook>, UsecaseError>
+    where
+        C: Conn,
+    {
+        let webhooks = self.webhook_repository.find_all(conn, user_id).await?;
+        Ok(webhooks)
+    }
+
+    async fn get_webhook<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Webhook, UsecaseError>
+    where
+        C: Conn,
+    {
+        let webhook = self
+            .webhook_repository
+            .find_by_id(conn, user_id, id)
+            .await?;
+        Ok(webhook)
+    }
+
+    async fn create_webhook<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        input: WebhookInput,
+    ) -> Result<Webhook, UsecaseError>
+    where
+        C: Conn,
+    {
+        let mut webhook = Webhook::new(user_id, input.url, input.events, input.secret)?;
+        webhook.active = input.active;
+        let webhook = self.webhook_repository.create(conn, webhook).await?;
+        Ok(webhook)
+    }
+
+    async fn update_webhook<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+        input: WebhookInput,
+    ) -> Result<Webhook, UsecaseError>
+    where
+        C: Conn,
+    {
+        let webhook_repository = self.webhook_repository.clone();
+        let webhook = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let mut webhook = webhook_repository.find_by_id(tx, user_id, id).await?;
+                    webhook.update(input.url, input.events, input.secret, input.active)?;
+                    let webhook = webhook_repository.update(tx, webhook).await?;
+                    Ok::<Webhook, TransactionError>(webhook)
+                })
+            })
+            .await?;
+        Ok(webhook)
+    }
+
+    async fn delete_webhook<C>(&self, conn: &C, user_id: Uuid, id: Uuid) -> Result<(), UsecaseError>
+    where
+        C: Conn,
+    {
+        let webhook_repository = self.webhook_repository.clone();
+        self.transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let webhook = webhook_repository.find_by_id(tx, user_id, id).await?;
+                    webhook_repository.delete(tx, webhook).await?;
+                    Ok::<(), TransactionError>(())
+                })
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn get_deliveries<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        webhook_id: Uuid,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<DeliveryPage, UsecaseError>
+    where
+        C: Conn,
+    {
+        self.webhook_repository
+            .find_by_id(conn, user_id, webhook_id)
+            .await?;
+        // Fetch one extra row to find out whether another page follows.
+        let deliveries = self
+            .delivery_repository
+            .find_by_webhook(conn, webhook_id, after, limit + 1)
+            .await?;
+        Ok(into_page(deliveries, limit))
+    }
+
+    async fn get_delivery<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        webhook_id: Uuid,
+        id: Uuid,
+    ) -> Result<DeliveryDetails, UsecaseError>
+    where
+        C: Conn,
+    {
+        self.webhook_repository
+            .find_by_id(conn, user_id, webhook_id)
+            .await?;
+        let delivery = self
+            .delivery_repository
+            .find_by_id(conn, webhook_id, id)
+            .await?;
+        let attempts = self.delivery_repository.find_attempts(conn, id).await?;
+        Ok(DeliveryDetails { delivery, attempts })
+    }
+
+    async fn replay_delivery<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        webhook_id: Uuid,
+        id: Uuid,
+    ) -> Result<WebhookDelivery, UsecaseError>
+    where
+        C: Conn,
+    {
+        let webhook_repository = self.webhook_repository.clone();
+        let delivery_repository = self.delivery_repository.clone();
+        let delivery = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    webhook_repository
+                        .find_by_id(tx, user_id, webhook_id)
+                        .await?;
+                    let mut delivery = delivery_repository.find_by_id(tx, webhook_id, id).await?;
+                    delivery.replay(Utc::now());
+                    let delivery = delivery_repository.update(tx, delivery).await?;
+                    Ok::<WebhookDelivery, TransactionError>(delivery)
+                })
+            })
+            .await?;
+        Ok(delivery)
+    }
+
+    /// The batch is claimed with a lease in a short transaction and sent
+    /// outside of it, so no locks are held while waiting on receivers.
+    /// Concurrent dispatchers skip claimed deliveries until the lease runs out.
+    async fn deliver_pending<C>(&self, conn: &C, limit: u64) -> Result<usize, UsecaseError>
+    where
+        C: Conn,
+    {
+        let delivery_repository = self.delivery_repository.clone();
+        let now = Utc::now();
+        let leased_until = now + WEBHOOK_LEASE;
+        let deliveries = self
+            .transaction_service
+            .run(conn, move |tx| {
+                Box::pin(async move {
+                    let mut deliveries = delivery_repository.find_due(tx, now, limit).await?;
+                    for delivery in deliveries.iter_mut() {
+                        delivery.lease(leased_until);
+                        *delivery = delivery_repository.update(tx, delivery.clone()).await?;
+                    }
+                    Ok::<Vec<WebhookDelivery>, TransactionError>(deliveries)
+                })
+            })
+            .await?;
+        let mut webhook_ids: Vec<Uuid> = deliveries
+            .iter()
+            .map(|delivery| delivery.webhook_id)
+            .collect();
+        webhook_ids.sort();
+        webhook_ids.dedup();
+        let webhooks: HashMap<Uuid, Webhook> = self
+            .webhook_repository
+            .find_by_ids(conn, &webhook_ids)
+            .await?
+            .into_iter()
+            .map(|webhook| (webhook.id, webhook))
+            .collect();
+        let handled = deliveries.len();
+        for mut delivery in deliveries {
+            // Deliveries are deleted with their webhook, so it is only missing
+            // when the webhook was deleted after the claim.
+            let Some(webhook) = webhooks.get(&delivery.webhook_id) else {
+                continue;
+            };
+            let attempt = if !webhook.active {
+                delivery.abandon("Webhook is inactive".into());
+                None
+            } else if Utc::now() >= leased_until {
+                // Another dispatcher may have claimed it again by now.
+                continue;
+            } else {
+                let outcome = self
+                    .sender
+                    .send(webhook, &delivery)
+                    .await
+                    .map_err(|err| err.to_string());
+                Some(delivery.record_attempt(outcome, Utc::now()))
+            };
+            let delivery_repository = self.delivery_repository.clone();
+            self.transaction_service
+                .run(conn, move |tx| {
+                    Box::pin(async move {
+                        if let Some(attempt) = attempt {
+                            delivery_repository.create_attempt(tx, attempt).await?;
+                        }
+                        delivery_repository.update(tx, delivery).await?;
+                        Ok::<(), TransactionError>(())
+                    })
+                })
+                .await?;
+        }
+        Ok(handled)
+    }
+}
+
+/// Queues the event for every webhook of the todo's owner that accepts it.
+/// Runs in the outbox dispatcher's transaction, so only committed changes
+/// are delivered and each event is queued exactly once.
+#[async_trait]
+impl<W, D, S, T> EventPublisher for WebhookUsecaseImpl<W, D, S, T>
+where
+    W: WebhookRepository + Send + Sync + 'static,
+    D: WebhookDeliveryRepository + Send + Sync + 'static,
+    S: WebhookSender + 'static,
+    T: TransactionService + Send + Sync + 'static,
+{
+    async fn publish<C>(&self, conn: &C, event: &TodoEvent) -> Result<(), UsecaseError>
+    where
+        C: Conn,
+    {
+        let deliveries = self
+            .webhook_repository
+            .find_all(conn, event.owner_id)
+            .await?
+            .into_iter()
+            .filter(|webhook| webhook.accepts(event.kind))
+            .map(|webhook| WebhookDelivery::new(webhook.id, event))
+            .collect();
+        self.delivery_repository.create(conn, deliveries).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::webhook::DeliveryStatus;
+    use crate::domain::repositories::conn::tests::MockConn;
+    use crate::domain::repositories::errors::RepositoryError;
+    use chrono::{DateTime, Duration};
+    use std::sync::Mutex;
+
+    const OWNER_ID: Uuid = Uuid::from_u128(0x0a0b0c0d_0e0f_1a1b_2a2b_3a3b3c3d3e3f);
+
+    #[derive(Clone, Default)]
+    struct Store {
+        webhooks: Arc<Mutex<Vec<Webhook>>>,
+        deliveries: Arc<Mutex<Vec<WebhookDelivery>>>,
+        attempts: Arc<Mutex<Vec<DeliveryAttempt>>>,
+    }
+
+    fn not_found(what: &str, id: Uuid) -> RepositoryError {
+        RepositoryError::NotFound(format!("{} with id {} not found", what, id))
+    }
+
+    struct MockWebhookRepository(Store);
+
+    #[async_trait]
+    impl WebhookRepository for MockWebhookRepository {
+        async fn find_all<C>(
+            &self,
+            _conn: &C,
+            owner_id: Uuid,
+        ) -> Result<Vec<Webhook>, RepositoryError> {
+            let webhooks = self.0.webhooks.lock().unwrap();
+            Ok(webhooks
+                .iter()
+                .filter(|webhook| webhook.owner_id == owner_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_id<C>(
+            &self,
+            _conn: &C,
+            owner_id: Uuid,
+            id: Uuid,
+        ) -> Result<Webhook, RepositoryError> {
+            let webhooks = self.0.webhooks.lock().unwrap();
+            webhooks
+                .iter()
+                .find(|webhook| webhook.owner_id == owner_id && webhook.id == id)
+                .cloned()
+                .ok_or(not_found("Webhook", id))
+        }
+
+        async fn find_by_ids<C>(
+            &self,
+            _conn: &C,
+            ids: &[Uuid],
+        ) -> Result<Vec<Webhook>, RepositoryError> {
+            let webhooks = self.0.webhooks.lock().unwrap();
+            Ok(webhooks
+                .iter()
+                .filter(|webhook| ids.contains(&webhook.id))
+                .cloned()
+                .collect())
+        }
+
+        async fn create<C>(&self, _conn: &C, webhook: Webhook) -> Result<Webhook, RepositoryError> {
+            self.0.webhooks.lock().unwrap().push(webhook.clone());
+            Ok(webhook)
+        }
+
+        async fn update<C>(&self, _conn: &C, webhook: Webhook) -> Result<Webhook, RepositoryError> {
+            let mut webhooks = self.0.webhooks.lock().unwrap();
+            let stored = webhooks
+                .iter_mut()
+                .find(|stored| stored.id == webhook.id)
+                .ok_or(not_found("Webhook", webhook.id))?;
+            *stored = webhook.clone();
+            Ok(webhook)
+        }
+
+        async fn delete<C>(&self, _conn: &C, webhook: Webhook) -> Result<(), RepositoryError> {
+            self.0
+                .webhooks
+                .lock()
+                .unwrap()
+                .retain(|stored| stored.id != webhook.id);
+            self.0
+                .deliveries
+                .lock()
+                .unwrap()
+                .retain(|delivery| delivery.webhook_id != webhook.id);
+            Ok(())
+        }
+    }
+
+    struct MockWebhookDeliveryRepository(Store);
+
+    #[async_trait]
+    impl WebhookDeliveryRepository for MockWebhookDeliveryRepository {
+        async fn find_by_webhook<C>(
+            &self,
+            _conn: &C,
+            webhook_id: Uuid,
+            after: Option<Uuid>,
+            limit: u64,
+        ) -> Result<Vec<WebhookDelivery>, RepositoryError> {
+            let deliveries = self.0.deliveries.lock().unwrap();
+            Ok(deliveries
+                .iter()
+                .filter(|delivery| {
+                    delivery.webhook_id == webhook_id
+                        && after.is_none_or(|after| delivery.id > after)
+                })
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_id<C>(
+            &self,
+            _conn: &C,
+            webhook_id: Uuid,
+            id: Uuid,
+        ) -> Result<WebhookDelivery, RepositoryError> {
+            let deliveries = self.0.deliveries.lock().unwrap();
+            deliveries
+                .iter()
+                .find(|delivery| delivery.webhook_id == webhook_id && delivery.id == id)
+                .cloned()
+                .ok_or(not_found("Webhook delivery", id))
+        }
+
+        async fn find_due<C>(
+            &self,
+            _conn: &C,
+            now: DateTime<Utc>,
+            limit: u64,
+        ) -> Result<Vec<WebhookDelivery>, RepositoryError> {
+            let deliveries = self.0.deliveries.lock().unwrap();
+            Ok(deliveries
+                .iter()
+                .filter(|delivery| delivery.next_attempt_at.is_some_and(|at| at <= now))
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+
+        async fn create<C>(
+            &self,
+            _conn: &C,
+            deliveries: Vec<WebhookDelivery>,
+        ) -> Result<(), RepositoryError> {
+            let mut stored = self.0.deliveries.lock().unwrap();
+            for delivery in deliveries {
+                if !stored.iter().any(|stored| {
+                    stored.webhook_id == delivery.webhook_id && stored.event_id == delivery.event_id
+                }) {
+                    stored.push(delivery);
+                }
+            }
+            Ok(())
+        }
+
+        async fn update<C>(
+            &self,
+            _conn: &C,
+            delivery: WebhookDelivery,
+        ) -> Result<WebhookDelivery, RepositoryError> {
+            let mut deliveries = self.0.deliveries.lock().unwrap();
+            let stored = deliveries
+                .iter_mut()
+                .find(|stored| stored.id == delivery.id)
+                .ok_or(not_found("Webhook delivery", delivery.id))?;
+            *stored = delivery.clone();
+            Ok(delivery)
+        }
+
+        async fn find_attempts<C>(
+            &self,
+            _conn: &C,
+            delivery_id: Uuid,
+        ) -> Result<Vec<DeliveryAttempt>, RepositoryError> {
+            let attempts = self.0.attempts.lock().unwrap();
+            Ok(attempts
+                .iter()
+                .filter(|attempt| attempt.delivery_id == delivery_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn create_attempt<C>(
+            &self,
+            _conn: &C,
+            attempt: DeliveryAttempt,
+        ) -> Result<DeliveryAttempt, RepositoryError> {
+            self.0.attempts.lock().unwrap().push(attempt.clone());
+            Ok(attempt)
+        }
+    }
+
+    /// Answers with the queued statuses in order, then with 200. Records the
+    /// stored delivery as it was while sending.
+    #[derive(Clone, Default)]
+    struct MockWebhookSender {
+        store: Store,
+        responses: Arc<Mutex<Vec<Result<u16, String>>>>,
+        sent: Arc<Mutex<Vec<(String, WebhookDelivery)>>>,
+    }
+
+    #[async_trait]
+    impl WebhookSender for MockWebhookSender {
+        async fn send(
+            &self,
+            webhook: &Webhook,
+            delivery: &WebhookDelivery,
+        ) -> Result<u16, UsecaseError> {
+            let deliveries = self.store.deliveries.lock().unwrap();
+            let stored = deliveries.iter().find(|stored| stored.id == delivery.id);
+            self.sent
+                .lock()
+                .unwrap()
+                .push((webhook.url.clone(), stored.unwrap().clone()));
+            let mut responses = self.responses.lock().unwrap();
+            if responses.is_empty() {
+                return Ok(200);
+            }
+            responses.remove(0).map_err(UsecaseError::Unexpected)
+        }
+    }
+
+    struct MockTransactionService;
+
+    #[async_trait]
+    impl TransactionService for MockTransactionService {
+        type Tx<'a>
+            = MockConn
+        where
+            Self: 'a;
+        async fn run<C, F, R>(&self, _: &C, f: F) -> Result<R, TransactionError>
+        where
+            C: Conn,
+            F: for<'tx> FnOnce(
+                    &'tx Self::Tx<'tx>,
+                ) -> std::pin::Pin<
+                    Box<dyn std::future::Future<Output = Result<R, TransactionError>> + Send + 'tx>,
+                > + Send,
+            R: Send,
+        {
+            f(&MockConn).await
+        }
+    }
+
+    type Usecase = WebhookUsecaseImpl<
+        MockWebhookRepository,
+        MockWebhookDeliveryRepository,
+        MockWebhookSender,
+        MockTransactionService,
+    >;
+
+    fn usecase(store: &Store, sender: &MockWebhookSender) -> Usecase {
+        WebhookUsecaseImpl::new(
+            MockWebhookRepository(store.clone()),
+            MockWebhookDeliveryRepository(store.clone()),
+            sender.clone(),
+            MockTransactionService,
+        )
+    }
+
+    fn input(url: &str, events: Vec<TodoEventKind>) -> WebhookInput {
+        WebhookInput {
+            url: url.into(),
+            events,
+            secret: "0123456789abcdef".into(),
+            active: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_usecase_impl_manage_webhooks() {
+        let store = Store::default();
+        let usecase = usecase(&store, &MockWebhookSender::default());
+
+        let result = usecase
+            .create_webhook(&MockConn, OWNER_ID, input("ftp://example.com", vec![]))
+            .await;
+        assert!(matches!(result, Err(UsecaseError::Validation(_))));
+        let webhook = usecase
+            .create_webhook(&MockConn, OWNER_ID, input("https://example.com/a", vec![]))
+            .await
+            .unwrap();
+        assert_eq!(
+            usecase
+                .get_webhooks(&MockConn, OWNER_ID)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let updated = usecase
+            .update_webhook(
+                &MockConn,
+                OWNER_ID,
+                webhook.id,
+                WebhookInput {
+                    active: false,
+                    ..input("https://example.com/b", vec![TodoEventKind::TodoDeleted])
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.url, "https://example.com/b");
+        assert_eq!(updated.events, vec![TodoEventKind::TodoDeleted]);
+        assert!(!updated.active);
+
+        // Other users cannot see or change the webhook.
+        let stranger = Uuid::now_v7();
+        let result = usecase.get_webhook(&MockConn, stranger, webhook.id).await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+        let result = usecase
+            .delete_webhook(&MockConn, stranger, webhook.id)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+
+        usecase
+            .delete_webhook(&MockConn, OWNER_ID, webhook.id)
+            .await
+            .unwrap();
+        assert!(
+            usecase
+                .get_webhooks(&MockConn, OWNER_ID)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_webhook_usecase_impl_publish_and_deliver() {
+        let store = Store::default();
+        let sender = MockWebhookSender {
+            store: store.clone(),
+            ..MockWebhookSender::default()
+        };
+        let usecase = usecase(&store, &sender);
+        let all = usecase
+            .create_webhook(
+                &MockConn,
+                OWNER_ID,
+                input("https://example.com/all", vec![]),
+            )
+            .await
+            .unwrap();
+        let deleted_only = input(
+            "https://example.com/deleted",
+            vec![TodoEventKind::TodoDeleted],
+        );
+        usecase
+            .create_webhook(&MockConn, OWNER_ID, deleted_only)
+            .await
+            .unwrap();
+        let inactive = WebhookInput {
+            active: false,
+            ..input("https://example.com/inactive", vec![])
+        };
+        usecase
+            .create_webhook(&MockConn, OWNER_ID, inactive)
+            .await
+            .unwrap();
+
+        // Only matching, active webhooks of the owner get a delivery, once per event.
+        let event = TodoEvent::new(
+            TodoEventKind::TodoCreated,
+            Uuid::now_v7(),
+            OWNER_ID,
+            Utc::now(),
+        );
+        usecase.publish(&MockConn, &event).await.unwrap();
+        usecase.publish(&MockConn, &event).await.unwrap();
+        let other = TodoEvent::new(
+            TodoEventKind::TodoCreated,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            Utc::now(),
+        );
+        usecase.publish(&MockConn, &other).await.unwrap();
+        let page = usecase
+            .get_deliveries(&MockConn, OWNER_ID, all.id, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(page.deliveries.len(), 1);
+        assert_eq!(store.deliveries.lock().unwrap().len(), 1);
+        let delivery_id = page.deliveries[0].id;
+
+        // A failed send is retried after the backoff.
+        sender.responses.lock().unwrap().push(Ok(500));
+        assert_eq!(usecase.deliver_pending(&MockConn, 10).await.unwrap(), 1);
+        assert_eq!(usecase.deliver_pending(&MockConn, 10).await.unwrap(), 0);
+        // The delivery was claimed with a lease before it was sent.
+        let leased = sender.sent.lock().unwrap()[0].1.next_attempt_at.unwrap();
+        assert!(leased > Utc::now() + WEBHOOK_LEASE - Duration::seconds(10));
+        for delivery in store.deliveries.lock().unwrap().iter_mut() {
+            delivery.next_attempt_at = delivery.next_attempt_at.map(|at| at - Duration::seconds(1));
+        }
+        assert_eq!(usecase.deliver_pending(&MockConn, 10).await.unwrap(), 1);
+        let details = usecase
+            .get_delivery(&MockConn, OWNER_ID, all.id, delivery_id)
+            .await
+            .unwrap();
+        assert_eq!(details.delivery.status(), DeliveryStatus::Delivered);
+        let status_codes: Vec<Option<i32>> = details
+            .attempts
+            .iter()
+            .map(|attempt| attempt.status_code)
+            .collect();
+        assert_eq!(status_codes, vec![Some(500), Some(200)]);
+
+        // Replays send the delivery again; only the owner may ask for one.
+        let result = usecase
+            .replay_delivery(&MockConn, Uuid::now_v7(), all.id, delivery_id)
+            .await;
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+        let replayed = usecase
+            .replay_delivery(&MockConn, OWNER_ID, all.id, delivery_id)
+            .await
+            .unwrap();
+        assert_eq!(replayed.status(), DeliveryStatus::Pending);
+        sender
+            .responses
+            .lock()
+            .unwrap()
+            .push(Err("connection refused".into()));
+        assert_eq!(usecase.deliver_pending(&MockConn, 10).await.unwrap(), 1);
+        let details = usecase
+            .get_delivery(&MockConn, OWNER_ID, all.id, delivery_id)
+            .await
+            .unwrap();
+        assert_eq!(details.attempts.len(), 3);
+        assert_eq!(details.attempts[2].status_code, None);
+        assert_eq!(
+            details.delivery.last_error.as_deref(),
+            Some("UsecaseError: Unexpected(connection refused)")
+        );
+        assert_eq!(sender.sent.lock().unwrap().len(), 3);
+
+        // Deliveries of a webhook deactivated in the meantime are given up.
+        let mut webhook = usecase
+            .get_webhook(&MockConn, OWNER_ID, all.id)
+            .await
+            .unwrap();
+        webhook.active = false;
+        store.webhooks.lock().unwrap()[0] = webhook;
+        for delivery in store.deliveries.lock().unwrap().iter_mut() {
+            delivery.next_attempt_at = Some(Utc::now() - Duration::seconds(1));
+        }
+        assert_eq!(usecase.deliver_pending(&MockConn, 10).await.unwrap(), 1);
+        let details = usecase
+            .get_delivery(&MockConn, OWNER_ID, all.id, delivery_id)
+            .await
+            .unwrap();
+        assert_eq!(details.delivery.status(), DeliveryStatus::Failed);
+        assert_eq!(details.attempts.len(), 3);
+        assert_eq!(sender.sent.lock().unwrap().len(), 3);
+    }
+}
@@ -1,2 +1,15 @@
+pub mod api_key;
+pub mod audit;
+pub mod checklist_item;
+pub mod color;
+pub mod dependency;
 pub mod errors;
+pub mod idempotency;
+pub mod project;
+pub mod recurrence;
+pub mod share;
+pub mod tag;
 pub mod todo;
+pub mod todo_event;
+pub mod user;
+pub mod webhook;
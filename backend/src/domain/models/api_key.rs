@@ -0,0 +1,193 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::models::errors::DomainError;
+
+pub const API_KEY_NAME_MIN_LENGTH: u64 = 1;
+pub const API_KEY_NAME_MAX_LENGTH: u64 = 100;
+
+/// What a request authenticated with an API key may do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyScope {
+    TodosRead,
+    TodosWrite,
+}
+
+impl fmt::Display for ApiKeyScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ApiKeyScope::TodosRead => "todos:read",
+            ApiKeyScope::TodosWrite => "todos:write",
+        })
+    }
+}
+
+impl FromStr for ApiKeyScope {
+    type Err = DomainError;
+
+    fn from_str(scope: &str) -> Result<Self, Self::Err> {
+        match scope {
+            "todos:read" => Ok(ApiKeyScope::TodosRead),
+            "todos:write" => Ok(ApiKeyScope::TodosWrite),
+            _ => Err(DomainError::Validation(format!(
+                "{} is not a known scope",
+                scope
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    /// Public part of the key, shown in listings so owners can tell their keys apart.
+    pub prefix: String,
+    /// Hash of the whole key; the key itself is only ever shown once, at creation.
+    pub key_hash: String,
+    pub scopes: Vec<ApiKeyScope>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKey {
+    pub fn new(
+        user_id: Uuid,
+        name: String,
+        prefix: String,
+        key_hash: String,
+        mut scopes: Vec<ApiKeyScope>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, DomainError> {
+        let now = Utc::now();
+        if scopes.is_empty() {
+            return Err(DomainError::Validation(
+                "An API key needs at least one scope".into(),
+            ));
+        }
+        if expires_at.is_some_and(|at| at <= now) {
+            return Err(DomainError::Validation(
+                "expires_at must be in the future".into(),
+            ));
+        }
+        scopes.sort_by_key(|scope| *scope as u8);
+        scopes.dedup();
+        Ok(Self {
+            id: Uuid::now_v7(),
+            user_id,
+            name,
+            prefix,
+            key_hash,
+            scopes,
+            expires_at,
+            last_used_at: None,
+            revoked_at: None,
+            created_at: now,
+        })
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|at| at <= now)
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    pub fn has_scope(&self, scope: ApiKeyScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+
+    pub fn revoke(&mut self) -> Result<(), DomainError> {
+        if self.is_revoked() {
+            return Err(DomainError::Conflict(format!(
+                "API key with id {} is already revoked",
+                self.id
+            )));
+        }
+        self.revoked_at = Some(Utc::now());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_key(scopes: Vec<ApiKeyScope>, expires_at: Option<DateTime<Utc>>) -> ApiKey {
+        ApiKey::new(
+            Uuid::now_v7(),
+            "CI".into(),
+            "abcdef123456".into(),
+            "hash".into(),
+            scopes,
+            expires_at,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_api_key_creation() {
+        let key = api_key(
+            vec![
+                ApiKeyScope::TodosWrite,
+                ApiKeyScope::TodosRead,
+                ApiKeyScope::TodosWrite,
+            ],
+            None,
+        );
+        assert_eq!(
+            key.scopes,
+            vec![ApiKeyScope::TodosRead, ApiKeyScope::TodosWrite]
+        );
+        assert!(key.has_scope(ApiKeyScope::TodosWrite));
+        assert!(!key.is_expired(Utc::now()));
+        assert!(!key.is_revoked());
+        assert_eq!(key.last_used_at, None);
+
+        let no_scopes = ApiKey::new(
+            Uuid::now_v7(),
+            "CI".into(),
+            "abcdef123456".into(),
+            "hash".into(),
+            vec![],
+            None,
+        );
+        assert!(matches!(no_scopes, Err(DomainError::Validation(_))));
+        let expired = ApiKey::new(
+            Uuid::now_v7(),
+            "CI".into(),
+            "abcdef123456".into(),
+            "hash".into(),
+            vec![ApiKeyScope::TodosRead],
+            Some(Utc::now() - chrono::Duration::minutes(1)),
+        );
+        assert!(matches!(expired, Err(DomainError::Validation(_))));
+    }
+
+    #[test]
+    fn test_api_key_expiry_and_revocation() {
+        let expires_at = Utc::now() + chrono::Duration::hours(1);
+        let mut key = api_key(vec![ApiKeyScope::TodosRead], Some(expires_at));
+        assert!(!key.has_scope(ApiKeyScope::TodosWrite));
+        assert!(!key.is_expired(expires_at - chrono::Duration::seconds(1)));
+        assert!(key.is_expired(expires_at));
+
+        key.revoke().unwrap();
+        assert!(key.is_revoked());
+        assert!(matches!(key.revoke(), Err(DomainError::Conflict(_))));
+    }
+
+    #[test]
+    fn test_api_key_scope_parsing() {
+        for scope in [ApiKeyScope::TodosRead, ApiKeyScope::TodosWrite] {
+            assert_eq!(scope.to_string().parse::<ApiKeyScope>().unwrap(), scope);
+        }
+        assert!("todos:admin".parse::<ApiKeyScope>().is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::domain::models::todo::{Priority, Todo};
+
+/// The kind of change an audit event records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Created,
+    Updated,
+    /// Moved to the trash.
+    Deleted,
+    Completed,
+    Uncompleted,
+    Restored,
+    Purged,
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AuditAction::Created => "created",
+            AuditAction::Updated => "updated",
+            AuditAction::Deleted => "deleted",
+            AuditAction::Completed => "completed",
+            AuditAction::Uncompleted => "uncompleted",
+            AuditAction::Restored => "restored",
+            AuditAction::Purged => "purged",
+        })
+    }
+}
+
+/// One change to a todo, with the todo as it looked before and after.
+/// Written in the same transaction as the change itself.
+#[derive(Debug, Clone)]
+pub struct AuditEvent {
+    pub id: Uuid,
+    pub todo_id: Uuid,
+    /// Owner of the todo, who sees every event on it.
+    pub owner_id: Uuid,
+    /// The user who made the change.
+    pub actor_id: Uuid,
+    pub action: AuditAction,
+    /// `None` when the todo was just created.
+    pub before: Option<Value>,
+    /// `None` when the todo was purged.
+    pub after: Option<Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditEvent {
+    pub fn new(actor_id: Uuid, action: AuditAction, before: Option<&Todo>, after: &Todo) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            todo_id: after.id,
+            owner_id: after.owner_id,
+            actor_id,
+            action,
+            before: before.map(snapshot),
+            after: Some(snapshot(after)),
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn purged(actor_id: Uuid, before: &Todo) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            todo_id: before.id,
+            owner_id: before.owner_id,
+            actor_id,
+            action: AuditAction::Purged,
+            before: Some(snapshot(before)),
+            after: None,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Filters for the audit log; every field left `None` matches all events.
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    pub actor_id: Option<Uuid>,
+    /// Inclusive lower bound on `created_at`.
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `created_at`.
+    pub to: Option<DateTime<Utc>>,
+}
+
+fn snapshot(todo: &Todo) -> Value {
+    let priority = match todo.priority {
+        Priority::Low => "low",
+        Priority::Medium => "medium",
+        Priority::High => "high",
+        Priority::Urgent => "urgent",
+    };
+    let items: Vec<Value> = todo
+        .items
+        .iter()
+        .map(|item| {
+            json!({
+                "id": item.id,
+                "title": item.title,
+                "completed": item.completed,
+                "position": item.position,
+            })
+        })
+        .collect();
+    json!({
+        "id": todo.id,
+        "title": todo.title,
+        "description": todo.description,
+        "completed": todo.completed,
+        "version": todo.version,
+        "deleted_at": todo.deleted_at,
+        "due_at": todo.due_at,
+        "remind_at": todo.remind_at,
+        "priority": priority,
+        "position": todo.position,
+        "items": items,
+        "project_id": todo.project_id,
+        "recurrence": todo.recurrence.as_ref().map(ToString::to_string),
+        "owner_id": todo.owner_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_event_snapshots() {
+        let actor_id = Uuid::now_v7();
+        let todo = Todo::new(actor_id, "Audited".into(), None);
+        let created = AuditEvent::new(actor_id, AuditAction::Created, None, &todo);
+        assert_eq!(created.todo_id, todo.id);
+        assert_eq!(created.owner_id, todo.owner_id);
+        assert!(created.before.is_none());
+        let after = created.after.unwrap();
+        assert_eq!(after["title"], "Audited");
+        assert_eq!(after["priority"], "medium");
+        assert_eq!(after["description"], Value::Null);
+
+        let mut completed = todo.clone();
+        completed.mark_completed(false, &[]).unwrap();
+        let event = AuditEvent::new(actor_id, AuditAction::Completed, Some(&todo), &completed);
+        assert_eq!(event.before.unwrap()["completed"], false);
+        assert_eq!(event.after.unwrap()["completed"], true);
+
+        let purged = AuditEvent::purged(actor_id, &completed);
+        assert_eq!(purged.action, AuditAction::Purged);
+        assert!(purged.after.is_none());
+        assert_eq!(purged.before.unwrap()["id"], json!(todo.id));
+    }
+}
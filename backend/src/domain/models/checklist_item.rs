@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+pub const ITEM_TITLE_MIN_LENGTH: u64 = 1;
+pub const ITEM_TITLE_MAX_LENGTH: u64 = 100;
+
+/// A step of a todo. Only ever changed through its owning [`Todo`](super::todo::Todo).
+#[derive(Debug, Clone)]
+pub struct ChecklistItem {
+    pub id: Uuid,
+    pub title: String,
+    pub completed: bool,
+    /// Order within the owning todo; lower positions come first.
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ChecklistItem {
+    pub(crate) fn new(title: String, position: i32) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::now_v7(),
+            title,
+            completed: false,
+            position,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub(crate) fn update(&mut self, title: String, completed: bool) {
+        self.title = title;
+        self.completed = completed;
+        self.updated_at = Utc::now();
+    }
+}
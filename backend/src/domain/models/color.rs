@@ -0,0 +1,18 @@
+use crate::domain::models::errors::DomainError;
+
+/// Accepts hex colours in `#rrggbb` form.
+pub fn validate_color(color: Option<&str>) -> Result<(), DomainError> {
+    match color {
+        Some(color)
+            if color.len() != 7
+                || !color.starts_with('#')
+                || !color[1..].chars().all(|c| c.is_ascii_hexdigit()) =>
+        {
+            Err(DomainError::Validation(format!(
+                "Color {} must be in #rrggbb form",
+                color
+            )))
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::models::{errors::DomainError, todo::Todo};
+
+/// Edge meaning "`todo_id` is blocked by `blocker_id`".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub todo_id: Uuid,
+    pub blocker_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Dependency {
+    pub fn new(todo_id: Uuid, blocker_id: Uuid) -> Result<Self, DomainError> {
+        if todo_id == blocker_id {
+            return Err(DomainError::Validation("A todo cannot block itself".into()));
+        }
+        Ok(Self {
+            todo_id,
+            blocker_id,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// Every todo `root` transitively depends on, with the edges between them.
+#[derive(Debug, Clone)]
+pub struct DependencyGraph {
+    pub root: Uuid,
+    /// Includes the root. Trashed todos are kept so the graph stays connected.
+    pub todos: Vec<Todo>,
+    pub edges: Vec<Dependency>,
+}
+
+impl DependencyGraph {
+    pub fn contains(&self, id: Uuid) -> bool {
+        self.root == id || self.edges.iter().any(|edge| edge.blocker_id == id)
+    }
+
+    /// The graph must be rooted at the new edge's blocker: the edge closes a
+    /// cycle exactly when the blocked todo is already reachable from there.
+    pub fn ensure_acyclic(&self, dependency: &Dependency) -> Result<(), DomainError> {
+        if self.contains(dependency.todo_id) {
+            return Err(DomainError::Conflict(format!(
+                "Todo with id {} cannot be blocked by todo with id {} without creating a cycle",
+                dependency.todo_id, dependency.blocker_id
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(todo_id: Uuid, blocker_id: Uuid) -> Dependency {
+        Dependency::new(todo_id, blocker_id).unwrap()
+    }
+
+    #[test]
+    fn test_new_rejects_self_dependency() {
+        let id = Uuid::now_v7();
+        let result = Dependency::new(id, id);
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+    }
+
+    #[test]
+    fn test_ensure_acyclic() {
+        let (a, b, c, d) = (
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+        );
+        // b is blocked by c, which is blocked by d.
+        let graph = DependencyGraph {
+            root: b,
+            todos: vec![],
+            edges: vec![edge(b, c), edge(c, d)],
+        };
+
+        assert!(graph.ensure_acyclic(&edge(a, b)).is_ok());
+        assert!(matches!(
+            graph.ensure_acyclic(&edge(d, b)),
+            Err(DomainError::Conflict(_))
+        ));
+        assert!(matches!(
+            graph.ensure_acyclic(&edge(c, b)),
+            Err(DomainError::Conflict(_))
+        ));
+    }
+}
@@ -2,8 +2,12 @@ use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum DomainError {
+    #[error("DomainError: NotFound({0})")]
+    NotFound(String),
     #[error("DomainError: Conflict({0})")]
     Conflict(String),
+    #[error("DomainError: Validation({0})")]
+    Validation(String),
     #[error("DomainError: Unexpected({0})")]
     Unexpected(String),
 }
@@ -0,0 +1,102 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use crate::domain::models::errors::DomainError;
+
+pub const IDEMPOTENCY_KEY_MAX_LENGTH: usize = 255;
+/// How long a key is held. A finished request has its response replayed
+/// until then; one that never finished keeps the key for as long, since its
+/// change may have been made and running it again could make it twice.
+pub const RESPONSE_TTL_HOURS: i64 = 24;
+
+/// A response as it was sent, kept to be sent again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// A request made with an `Idempotency-Key`. Keys are scoped to the user and
+/// free again once the record expires.
+#[derive(Debug, Clone)]
+pub struct IdempotencyRecord {
+    pub user_id: Uuid,
+    pub key: String,
+    /// Identifies the request, so a reused key with a different request is caught.
+    pub fingerprint: String,
+    /// `None` while the request is still running.
+    pub response: Option<StoredResponse>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl IdempotencyRecord {
+    pub fn new(
+        user_id: Uuid,
+        key: String,
+        fingerprint: String,
+        now: DateTime<Utc>,
+    ) -> Result<Self, DomainError> {
+        if key.is_empty() || key.len() > IDEMPOTENCY_KEY_MAX_LENGTH {
+            return Err(DomainError::Validation(format!(
+                "Idempotency-Key must be between 1 and {} characters",
+                IDEMPOTENCY_KEY_MAX_LENGTH
+            )));
+        }
+        Ok(Self {
+            user_id,
+            key,
+            fingerprint,
+            response: None,
+            created_at: now,
+            expires_at: now + Duration::hours(RESPONSE_TTL_HOURS),
+        })
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+
+    /// Keeps the response for replay from now on.
+    pub fn complete(&mut self, response: StoredResponse, now: DateTime<Utc>) {
+        self.response = Some(response);
+        self.expires_at = now + Duration::hours(RESPONSE_TTL_HOURS);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idempotency_record_creation() {
+        let now = Utc::now();
+        let record =
+            IdempotencyRecord::new(Uuid::now_v7(), "retry-1".into(), "f".into(), now).unwrap();
+        assert!(record.response.is_none());
+        assert!(!record.is_expired(now + Duration::minutes(10)));
+        assert!(record.is_expired(now + Duration::hours(RESPONSE_TTL_HOURS)));
+
+        assert!(IdempotencyRecord::new(Uuid::now_v7(), "".into(), "f".into(), now).is_err());
+        let long_key = "k".repeat(IDEMPOTENCY_KEY_MAX_LENGTH + 1);
+        assert!(IdempotencyRecord::new(Uuid::now_v7(), long_key, "f".into(), now).is_err());
+    }
+
+    #[test]
+    fn test_idempotency_record_complete() {
+        let now = Utc::now();
+        let mut record =
+            IdempotencyRecord::new(Uuid::now_v7(), "retry-1".into(), "f".into(), now).unwrap();
+        let response = StoredResponse {
+            status: 201,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: b"{}".to_vec(),
+        };
+        let later = now + Duration::hours(1);
+        record.complete(response.clone(), later);
+        assert_eq!(record.response, Some(response));
+        assert!(!record.is_expired(now + Duration::hours(RESPONSE_TTL_HOURS)));
+        assert!(record.is_expired(later + Duration::hours(RESPONSE_TTL_HOURS)));
+    }
+}
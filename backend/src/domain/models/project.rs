@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::models::{color::validate_color, errors::DomainError};
+
+pub const PROJECT_NAME_MIN_LENGTH: u64 = 1;
+pub const PROJECT_NAME_MAX_LENGTH: u64 = 100;
+
+#[derive(Debug, Clone)]
+pub struct Project {
+    pub id: Uuid,
+    /// Only the owner's todos can be kept in the project.
+    pub owner_id: Uuid,
+    pub name: String,
+    /// Hex colour in `#rrggbb` form.
+    pub color: Option<String>,
+    /// Todos of an archived project are read-only.
+    pub archived: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Project {
+    pub fn new(owner_id: Uuid, name: String, color: Option<String>) -> Result<Self, DomainError> {
+        validate_color(color.as_deref())?;
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::now_v7(),
+            owner_id,
+            name,
+            color,
+            archived: false,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn update(
+        &mut self,
+        name: String,
+        color: Option<String>,
+        archived: bool,
+    ) -> Result<(), DomainError> {
+        validate_color(color.as_deref())?;
+        self.name = name;
+        self.color = color;
+        self.archived = archived;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Refuses changes to the project's todos while it is archived.
+    pub fn ensure_writable(&self) -> Result<(), DomainError> {
+        if self.archived {
+            return Err(DomainError::Conflict(format!(
+                "Project with id {} is archived",
+                self.id
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_creation() {
+        let owner_id = Uuid::now_v7();
+        let project = Project::new(owner_id, "Home".into(), Some("#00ff00".into())).unwrap();
+        assert!(project.id != Uuid::nil());
+        assert_eq!(project.owner_id, owner_id);
+        assert_eq!(project.name, "Home");
+        assert!(!project.archived);
+        assert_eq!(project.created_at, project.updated_at);
+        assert!(Project::new(owner_id, "Home".into(), Some("green".into())).is_err());
+    }
+
+    #[test]
+    fn test_project_archive() {
+        let mut project = Project::new(Uuid::now_v7(), "Home".into(), None).unwrap();
+        assert!(project.ensure_writable().is_ok());
+
+        project.update("Home".into(), None, true).unwrap();
+        assert!(matches!(
+            project.ensure_writable(),
+            Err(DomainError::Conflict(_))
+        ));
+
+        project.update("Home".into(), None, false).unwrap();
+        assert!(project.ensure_writable().is_ok());
+    }
+}
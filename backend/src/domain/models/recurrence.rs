@@ -0,0 +1,508 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, NaiveDateTime, Utc, Weekday};
+
+use crate::domain::models::errors::DomainError;
+
+pub const RECURRENCE_MAX_LENGTH: u64 = 255;
+
+/// Upper bound on the periods searched for the next occurrence, so rules such as
+/// "every 5th Monday" terminate even when few months match.
+const MAX_PERIODS: u32 = 120;
+/// Largest `INTERVAL` accepted, far beyond any useful rule.
+const MAX_INTERVAL: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// A `BYDAY` entry such as `MO`, `2TU` or `-1FR`. The ordinal is only allowed in
+/// monthly rules and picks the n-th (or n-th last) weekday of the month.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeekdayNum {
+    pub ordinal: Option<i8>,
+    pub weekday: Weekday,
+}
+
+/// Subset of an RFC 5545 `RRULE`. The todo's due date acts as `DTSTART`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<WeekdayNum>,
+    /// Occurrences left in the series, including the current one.
+    pub count: Option<u32>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl Recurrence {
+    /// The first occurrence strictly after `current`, or `None` once the series
+    /// passes `UNTIL`. `COUNT` is tracked by the todo, not here.
+    pub fn next_after(&self, current: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let date = current.date_naive();
+        let next_date = match self.frequency {
+            Frequency::Daily => date.checked_add_days(Days::new(self.interval.into()))?,
+            Frequency::Weekly => self.next_weekly(date)?,
+            Frequency::Monthly => self.next_monthly(date)?,
+        };
+        let next = next_date.and_time(current.time()).and_utc();
+        match self.until {
+            Some(until) if next > until => None,
+            _ => Some(next),
+        }
+    }
+
+    /// The same rule with one occurrence consumed, or `None` when it was the last.
+    pub fn advance(&self) -> Option<Self> {
+        match self.count {
+            Some(count) if count <= 1 => None,
+            count => Some(Self {
+                count: count.map(|count| count - 1),
+                ..self.clone()
+            }),
+        }
+    }
+
+    fn next_weekly(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let weeks = Days::new(7 * u64::from(self.interval));
+        if self.by_day.is_empty() {
+            return date.checked_add_days(weeks);
+        }
+        let mut offsets: Vec<u32> = self
+            .by_day
+            .iter()
+            .map(|day| day.weekday.num_days_from_monday())
+            .collect();
+        offsets.sort_unstable();
+        let today = date.weekday().num_days_from_monday();
+        let week_start = date - Days::new(today.into());
+        match offsets.iter().find(|&&offset| offset > today) {
+            Some(&offset) => week_start.checked_add_days(Days::new(offset.into())),
+            None => week_start
+                .checked_add_days(weeks)?
+                .checked_add_days(Days::new(offsets[0].into())),
+        }
+    }
+
+    fn next_monthly(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let month_start = date.with_day(1)?;
+        if !self.by_day.is_empty()
+            && let Some(next) = self
+                .monthly_candidates(month_start)
+                .into_iter()
+                .find(|&candidate| candidate > date)
+        {
+            return Some(next);
+        }
+        (1..=MAX_PERIODS).find_map(|period| {
+            let start =
+                month_start.checked_add_months(Months::new(period.checked_mul(self.interval)?))?;
+            if self.by_day.is_empty() {
+                start.with_day(date.day())
+            } else {
+                self.monthly_candidates(start).into_iter().next()
+            }
+        })
+    }
+
+    /// Every date of the month starting at `month_start` matched by `BYDAY`, sorted.
+    fn monthly_candidates(&self, month_start: NaiveDate) -> Vec<NaiveDate> {
+        let days: Vec<NaiveDate> = month_start
+            .iter_days()
+            .take_while(|day| day.month() == month_start.month())
+            .collect();
+        let mut candidates: Vec<NaiveDate> = self
+            .by_day
+            .iter()
+            .flat_map(|by_day| {
+                let matching: Vec<NaiveDate> = days
+                    .iter()
+                    .copied()
+                    .filter(|day| day.weekday() == by_day.weekday)
+                    .collect();
+                match by_day.ordinal {
+                    None => matching,
+                    Some(ordinal) if ordinal > 0 => matching
+                        .get(ordinal as usize - 1)
+                        .copied()
+                        .into_iter()
+                        .collect(),
+                    Some(ordinal) => matching
+                        .len()
+                        .checked_sub(ordinal.unsigned_abs() as usize)
+                        .and_then(|index| matching.get(index).copied())
+                        .into_iter()
+                        .collect(),
+                }
+            })
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates
+    }
+}
+
+fn invalid(message: impl Into<String>) -> DomainError {
+    DomainError::Validation(format!("Invalid recurrence rule: {}", message.into()))
+}
+
+fn parse_weekday(code: &str) -> Result<Weekday, DomainError> {
+    match code {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        _ => Err(invalid(format!("unknown weekday {}", code))),
+    }
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn parse_by_day(value: &str) -> Result<WeekdayNum, DomainError> {
+    let split = value
+        .len()
+        .checked_sub(2)
+        .filter(|&split| value.is_char_boundary(split))
+        .ok_or_else(|| invalid(format!("unknown weekday {}", value)))?;
+    let (ordinal, code) = value.split_at(split);
+    let weekday = parse_weekday(code)?;
+    let ordinal = match ordinal {
+        "" => None,
+        ordinal => {
+            let ordinal: i8 = ordinal
+                .trim_start_matches('+')
+                .parse()
+                .map_err(|_| invalid(format!("invalid BYDAY value {}", value)))?;
+            if ordinal == 0 || !(-5..=5).contains(&ordinal) {
+                return Err(invalid(format!("invalid BYDAY value {}", value)));
+            }
+            Some(ordinal)
+        }
+    };
+    Ok(WeekdayNum { ordinal, weekday })
+}
+
+/// `UNTIL` as a UTC date-time (`20261231T170000Z`) or a date, which includes the whole day.
+fn parse_until(value: &str) -> Result<DateTime<Utc>, DomainError> {
+    if let Ok(until) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ") {
+        return Ok(until.and_utc());
+    }
+    NaiveDate::parse_from_str(value, "%Y%m%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(23, 59, 59))
+        .map(|until| until.and_utc())
+        .ok_or_else(|| invalid(format!("invalid UNTIL value {}", value)))
+}
+
+fn parse_positive(name: &str, value: &str) -> Result<u32, DomainError> {
+    value
+        .parse::<u32>()
+        .ok()
+        .filter(|&value| value > 0)
+        .ok_or_else(|| invalid(format!("{} must be a positive integer", name)))
+}
+
+fn parse_interval(value: &str) -> Result<u32, DomainError> {
+    let interval = parse_positive("INTERVAL", value)?;
+    if interval > MAX_INTERVAL {
+        return Err(invalid(format!(
+            "INTERVAL must be at most {}",
+            MAX_INTERVAL
+        )));
+    }
+    Ok(interval)
+}
+
+impl FromStr for Recurrence {
+    type Err = DomainError;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let rule = rule.trim().to_ascii_uppercase();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(&rule);
+        let mut frequency = None;
+        let mut interval = None;
+        let mut by_day = None;
+        let mut count = None;
+        let mut until = None;
+        for part in rule.split(';') {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("malformed part {}", part)))?;
+            let duplicate = match name {
+                "FREQ" => frequency
+                    .replace(match value {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        _ => return Err(invalid(format!("unsupported FREQ {}", value))),
+                    })
+                    .is_some(),
+                "INTERVAL" => interval.replace(parse_interval(value)?).is_some(),
+                "BYDAY" => by_day
+                    .replace(
+                        value
+                            .split(',')
+                            .map(parse_by_day)
+                            .collect::<Result<Vec<_>, _>>()?,
+                    )
+                    .is_some(),
+                "COUNT" => count.replace(parse_positive(name, value)?).is_some(),
+                "UNTIL" => until.replace(parse_until(value)?).is_some(),
+                _ => return Err(invalid(format!("unsupported part {}", name))),
+            };
+            if duplicate {
+                return Err(invalid(format!("{} is given more than once", name)));
+            }
+        }
+
+        let frequency = frequency.ok_or_else(|| invalid("FREQ is required"))?;
+        let by_day = by_day.unwrap_or_default();
+        if count.is_some() && until.is_some() {
+            return Err(invalid("COUNT and UNTIL cannot be combined"));
+        }
+        match frequency {
+            Frequency::Daily if !by_day.is_empty() => {
+                return Err(invalid("BYDAY is not supported for daily rules"));
+            }
+            Frequency::Weekly if by_day.iter().any(|day| day.ordinal.is_some()) => {
+                return Err(invalid("BYDAY ordinals are only allowed in monthly rules"));
+            }
+            _ => {}
+        }
+        let recurrence = Self {
+            frequency,
+            interval: interval.unwrap_or(1),
+            by_day,
+            count,
+            until,
+        };
+        // The canonical form is what gets stored.
+        if recurrence.to_string().len() as u64 > RECURRENCE_MAX_LENGTH {
+            return Err(invalid(format!(
+                "must be at most {} characters",
+                RECURRENCE_MAX_LENGTH
+            )));
+        }
+        Ok(recurrence)
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+        };
+        write!(f, "FREQ={}", frequency)?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let by_day: Vec<String> = self
+                .by_day
+                .iter()
+                .map(|day| match day.ordinal {
+                    Some(ordinal) => format!("{}{}", ordinal, weekday_code(day.weekday)),
+                    None => weekday_code(day.weekday).to_string(),
+                })
+                .collect();
+            write!(f, ";BYDAY={}", by_day.join(","))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%dT%H%M%SZ"))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap().to_utc()
+    }
+
+    fn next(rule: &str, current: &str) -> Option<DateTime<Utc>> {
+        rule.parse::<Recurrence>().unwrap().next_after(at(current))
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let rule: Recurrence = "rrule:freq=weekly;interval=2;byday=MO,WE;count=5"
+            .parse()
+            .unwrap();
+        assert_eq!(rule.frequency, Frequency::Weekly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.by_day.len(), 2);
+        assert_eq!(rule.count, Some(5));
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=5"
+        );
+
+        let rule: Recurrence = "FREQ=MONTHLY;BYDAY=-1FR;UNTIL=20261231".parse().unwrap();
+        assert_eq!(
+            rule.by_day,
+            vec![WeekdayNum {
+                ordinal: Some(-1),
+                weekday: Weekday::Fri
+            }]
+        );
+        assert_eq!(rule.until, Some(at("2026-12-31T23:59:59Z")));
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=MONTHLY;BYDAY=-1FR;UNTIL=20261231T235959Z"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for rule in [
+            "",
+            "INTERVAL=2",
+            "FREQ=YEARLY",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;COUNT=-1",
+            "FREQ=DAILY;COUNT=2;UNTIL=20261231",
+            "FREQ=DAILY;BYDAY=MO",
+            "FREQ=WEEKLY;BYDAY=1MO",
+            "FREQ=WEEKLY;BYDAY=XX",
+            "FREQ=MONTHLY;BYDAY=6MO",
+            "FREQ=MONTHLY;BYDAY=0MO",
+            "FREQ=DAILY;FREQ=WEEKLY",
+            "FREQ=DAILY;BYMONTH=1",
+            "FREQ=DAILY;UNTIL=tomorrow",
+        ] {
+            let result = rule.parse::<Recurrence>();
+            assert!(
+                matches!(result, Err(DomainError::Validation(_))),
+                "{rule} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_next_daily() {
+        assert_eq!(
+            next("FREQ=DAILY", "2026-10-17T09:00:00Z"),
+            Some(at("2026-10-18T09:00:00Z"))
+        );
+        assert_eq!(
+            next("FREQ=DAILY;INTERVAL=3", "2026-10-30T09:00:00Z"),
+            Some(at("2026-11-02T09:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_next_weekly() {
+        // 2026-10-17 is a Saturday.
+        assert_eq!(
+            next("FREQ=WEEKLY", "2026-10-17T09:00:00Z"),
+            Some(at("2026-10-24T09:00:00Z"))
+        );
+        assert_eq!(
+            next("FREQ=WEEKLY;BYDAY=MO,WE", "2026-10-19T09:00:00Z"),
+            Some(at("2026-10-21T09:00:00Z"))
+        );
+        assert_eq!(
+            next("FREQ=WEEKLY;BYDAY=MO,WE", "2026-10-21T09:00:00Z"),
+            Some(at("2026-10-26T09:00:00Z"))
+        );
+        assert_eq!(
+            next("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE", "2026-10-21T09:00:00Z"),
+            Some(at("2026-11-02T09:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_next_monthly() {
+        assert_eq!(
+            next("FREQ=MONTHLY", "2026-10-17T09:00:00Z"),
+            Some(at("2026-11-17T09:00:00Z"))
+        );
+        // Months without a 31st are skipped.
+        assert_eq!(
+            next("FREQ=MONTHLY", "2026-10-31T09:00:00Z"),
+            Some(at("2026-12-31T09:00:00Z"))
+        );
+        assert_eq!(
+            next("FREQ=MONTHLY;INTERVAL=3", "2026-11-30T09:00:00Z"),
+            Some(at("2027-05-30T09:00:00Z"))
+        );
+        // Last Friday of the month.
+        assert_eq!(
+            next("FREQ=MONTHLY;BYDAY=-1FR", "2026-10-17T09:00:00Z"),
+            Some(at("2026-10-30T09:00:00Z"))
+        );
+        assert_eq!(
+            next("FREQ=MONTHLY;BYDAY=-1FR", "2026-10-30T09:00:00Z"),
+            Some(at("2026-11-27T09:00:00Z"))
+        );
+        // First Monday of every other month.
+        assert_eq!(
+            next("FREQ=MONTHLY;INTERVAL=2;BYDAY=1MO", "2026-10-05T09:00:00Z"),
+            Some(at("2026-12-07T09:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_huge_monthly_interval() {
+        let rule = "FREQ=MONTHLY;INTERVAL=4294967295".parse::<Recurrence>();
+        assert!(matches!(rule, Err(DomainError::Validation(_))));
+        assert!("FREQ=MONTHLY;INTERVAL=1000".parse::<Recurrence>().is_ok());
+
+        // Rules built directly are not capped; they end instead of overflowing.
+        let rule = Recurrence {
+            frequency: Frequency::Monthly,
+            interval: u32::MAX,
+            by_day: vec![],
+            count: None,
+            until: None,
+        };
+        assert_eq!(rule.next_after(at("2026-10-17T09:00:00Z")), None);
+    }
+
+    #[test]
+    fn test_next_until() {
+        assert_eq!(
+            next("FREQ=DAILY;UNTIL=20261018", "2026-10-17T09:00:00Z"),
+            Some(at("2026-10-18T09:00:00Z"))
+        );
+        assert_eq!(
+            next("FREQ=DAILY;UNTIL=20261018T080000Z", "2026-10-17T09:00:00Z"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_advance() {
+        let rule: Recurrence = "FREQ=DAILY;COUNT=2".parse().unwrap();
+        let rule = rule.advance().unwrap();
+        assert_eq!(rule.count, Some(1));
+        assert!(rule.advance().is_none());
+
+        let rule: Recurrence = "FREQ=DAILY".parse().unwrap();
+        assert_eq!(rule.advance(), Some(rule));
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::models::{errors::DomainError, todo::Todo, todo_event::TodoEvent};
+
+/// What a user may do with todos shared with them; each role includes the ones before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ShareRole {
+    /// May read the todo.
+    Viewer,
+    /// May also update and complete the todo.
+    Editor,
+    /// May also delete, restore and re-share the todo.
+    Owner,
+}
+
+impl fmt::Display for ShareRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ShareRole::Viewer => "viewer",
+            ShareRole::Editor => "editor",
+            ShareRole::Owner => "owner",
+        })
+    }
+}
+
+/// What a grant covers. A project grant covers the todos its owner keeps in that project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareTarget {
+    Todo(Uuid),
+    Project(Uuid),
+}
+
+#[derive(Debug, Clone)]
+pub struct ShareGrant {
+    pub id: Uuid,
+    /// Owner of the shared todos.
+    pub owner_id: Uuid,
+    /// User the todos are shared with.
+    pub user_id: Uuid,
+    pub target: ShareTarget,
+    pub role: ShareRole,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ShareGrant {
+    pub fn new(
+        owner_id: Uuid,
+        user_id: Uuid,
+        target: ShareTarget,
+        role: ShareRole,
+    ) -> Result<Self, DomainError> {
+        if owner_id == user_id {
+            return Err(DomainError::Validation(
+                "Todos cannot be shared with their owner".into(),
+            ));
+        }
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::now_v7(),
+            owner_id,
+            user_id,
+            target,
+            role,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn change_role(&mut self, role: ShareRole) {
+        self.role = role;
+        self.updated_at = Utc::now();
+    }
+}
+
+/// Role of `user_id` on `todo`: owners hold every right, everyone else gets
+/// the strongest role among the grants covering the todo.
+pub fn role_of(todo: &Todo, user_id: Uuid, grants: &[ShareGrant]) -> Option<ShareRole> {
+    role_on(todo.owner_id, todo.id, todo.project_id, user_id, grants)
+}
+
+/// Role of `user_id` on the todo an event happened to, judged by the project
+/// the todo is in after the change.
+pub fn role_on_event(event: &TodoEvent, user_id: Uuid, grants: &[ShareGrant]) -> Option<ShareRole> {
+    role_on(
+        event.owner_id,
+        event.todo_id,
+        event.project_id,
+        user_id,
+        grants,
+    )
+}
+
+fn role_on(
+    owner_id: Uuid,
+    todo_id: Uuid,
+    project_id: Option<Uuid>,
+    user_id: Uuid,
+    grants: &[ShareGrant],
+) -> Option<ShareRole> {
+    if owner_id == user_id {
+        return Some(ShareRole::Owner);
+    }
+    grants
+        .iter()
+        .filter(|grant| grant.user_id == user_id && grant.owner_id == owner_id)
+        .filter(|grant| match grant.target {
+            ShareTarget::Todo(id) => id == todo_id,
+            ShareTarget::Project(id) => project_id == Some(id),
+        })
+        .map(|grant| grant.role)
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::todo_event::TodoEventKind;
+
+    #[test]
+    fn test_share_grant_creation() {
+        let owner_id = Uuid::now_v7();
+        let user_id = Uuid::now_v7();
+        let mut grant = ShareGrant::new(
+            owner_id,
+            user_id,
+            ShareTarget::Todo(Uuid::now_v7()),
+            ShareRole::Viewer,
+        )
+        .unwrap();
+        assert_eq!(grant.created_at, grant.updated_at);
+
+        grant.change_role(ShareRole::Editor);
+        assert_eq!(grant.role, ShareRole::Editor);
+        assert!(grant.updated_at >= grant.created_at);
+
+        let with_owner = ShareGrant::new(
+            owner_id,
+            owner_id,
+            ShareTarget::Project(Uuid::now_v7()),
+            ShareRole::Viewer,
+        );
+        assert!(matches!(with_owner, Err(DomainError::Validation(_))));
+    }
+
+    #[test]
+    fn test_share_role_of() {
+        let owner_id = Uuid::now_v7();
+        let user_id = Uuid::now_v7();
+        let project_id = Uuid::now_v7();
+        let mut todo = Todo::new(owner_id, "Shared".into(), None);
+        assert_eq!(role_of(&todo, owner_id, &[]), Some(ShareRole::Owner));
+        assert_eq!(role_of(&todo, user_id, &[]), None);
+
+        let grants = vec![
+            ShareGrant::new(
+                owner_id,
+                user_id,
+                ShareTarget::Todo(todo.id),
+                ShareRole::Viewer,
+            )
+            .unwrap(),
+            ShareGrant::new(
+                owner_id,
+                user_id,
+                ShareTarget::Project(project_id),
+                ShareRole::Editor,
+            )
+            .unwrap(),
+        ];
+        assert_eq!(role_of(&todo, user_id, &grants), Some(ShareRole::Viewer));
+        todo.project_id = Some(project_id);
+        assert_eq!(role_of(&todo, user_id, &grants), Some(ShareRole::Editor));
+
+        // A project grant only covers the todos of the user who granted it.
+        let mut other = Todo::new(Uuid::now_v7(), "Other".into(), None);
+        other.project_id = Some(project_id);
+        assert_eq!(role_of(&other, user_id, &grants), None);
+        assert!(ShareRole::Viewer < ShareRole::Editor && ShareRole::Editor < ShareRole::Owner);
+    }
+
+    #[test]
+    fn test_share_role_on_event() {
+        let owner_id = Uuid::now_v7();
+        let user_id = Uuid::now_v7();
+        let project_id = Uuid::now_v7();
+        let mut event = TodoEvent::new(
+            TodoEventKind::TodoUpdated,
+            Uuid::now_v7(),
+            owner_id,
+            Utc::now(),
+        );
+        let grants = vec![
+            ShareGrant::new(
+                owner_id,
+                user_id,
+                ShareTarget::Project(project_id),
+                ShareRole::Viewer,
+            )
+            .unwrap(),
+        ];
+        assert_eq!(role_on_event(&event, owner_id, &[]), Some(ShareRole::Owner));
+        assert_eq!(role_on_event(&event, user_id, &grants), None);
+        event.project_id = Some(project_id);
+        assert_eq!(
+            role_on_event(&event, user_id, &grants),
+            Some(ShareRole::Viewer)
+        );
+    }
+}
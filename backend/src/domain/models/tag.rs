@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::models::{color::validate_color, errors::DomainError};
+
+pub const TAG_NAME_MIN_LENGTH: u64 = 1;
+pub const TAG_NAME_MAX_LENGTH: u64 = 50;
+
+#[derive(Debug, Clone)]
+pub struct Tag {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    /// Unique among the tags of the owner.
+    pub name: String,
+    /// Hex colour in `#rrggbb` form.
+    pub color: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Tag {
+    pub fn new(owner_id: Uuid, name: String, color: Option<String>) -> Result<Self, DomainError> {
+        validate_color(color.as_deref())?;
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::now_v7(),
+            owner_id,
+            name,
+            color,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn update(&mut self, name: String, color: Option<String>) -> Result<(), DomainError> {
+        validate_color(color.as_deref())?;
+        self.name = name;
+        self.color = color;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_creation() {
+        let owner_id = Uuid::now_v7();
+        let tag = Tag::new(owner_id, "work".into(), Some("#1a2B3c".into())).unwrap();
+        assert!(tag.id != Uuid::nil());
+        assert_eq!(tag.owner_id, owner_id);
+        assert_eq!(tag.name, "work");
+        assert_eq!(tag.color, Some("#1a2B3c".into()));
+        assert_eq!(tag.created_at, tag.updated_at);
+    }
+
+    #[test]
+    fn test_tag_invalid_color() {
+        for color in ["1a2b3c", "#1a2b3", "#1a2b3g", "#1a2b3c4"] {
+            let result = Tag::new(Uuid::now_v7(), "work".into(), Some(color.into()));
+            assert!(matches!(result, Err(DomainError::Validation(_))));
+        }
+    }
+
+    #[test]
+    fn test_tag_update() {
+        let mut tag = Tag::new(Uuid::now_v7(), "work".into(), None).unwrap();
+        let created_at = tag.created_at;
+        tag.update("home".into(), Some("#ffffff".into())).unwrap();
+        assert_eq!(tag.name, "home");
+        assert_eq!(tag.color, Some("#ffffff".into()));
+        assert_eq!(tag.created_at, created_at);
+
+        assert!(tag.update("home".into(), Some("white".into())).is_err());
+        assert_eq!(tag.color, Some("#ffffff".into()));
+    }
+}
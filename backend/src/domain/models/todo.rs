@@ -1,6 +1,25 @@
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::domain::models::errors::DomainError;
+use crate::domain::models::{
+    checklist_item::ChecklistItem,
+    errors::DomainError,
+    recurrence::Recurrence,
+    todo_event::{TodoEvent, TodoEventKind},
+};
+
+pub const TITLE_MIN_LENGTH: u64 = 2;
+pub const TITLE_MAX_LENGTH: u64 = 100;
+pub const DESCRIPTION_MAX_LENGTH: u64 = 255;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Urgent,
+}
 
 #[derive(Debug, Clone)]
 pub struct Todo {
@@ -8,29 +27,244 @@ pub struct Todo {
     pub title: String,
     pub description: Option<String>,
     pub completed: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Incremented by the repository on every write; used for optimistic locking.
+    pub version: i32,
+    /// Set while the todo sits in the trash.
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub due_at: Option<DateTime<Utc>>,
+    /// Always earlier than `due_at` when both are set.
+    pub remind_at: Option<DateTime<Utc>>,
+    pub priority: Priority,
+    /// Manual sort order; lower positions come first.
+    pub position: i64,
+    /// Ordered by position.
+    pub items: Vec<ChecklistItem>,
+    pub project_id: Option<Uuid>,
+    /// Requires a due date, which anchors the series. Only the open occurrence
+    /// carries the rule; completing it hands the rule on to the next one.
+    pub recurrence: Option<Recurrence>,
+    /// The user the todo belongs to; nobody else can read or change it.
+    pub owner_id: Uuid,
+    /// Events recorded since the todo was loaded; drained into the outbox when it is saved.
+    pub events: Vec<TodoEvent>,
+}
+
+/// Partial update following JSON Merge Patch: `None` leaves a field untouched,
+/// `Some(None)` clears it and `Some(Some(_))` replaces it.
+#[derive(Debug, Clone, Default)]
+pub struct TodoPatch {
+    pub title: Option<Option<String>>,
+    pub description: Option<Option<String>>,
+    pub due_at: Option<Option<DateTime<Utc>>>,
+    pub remind_at: Option<Option<DateTime<Utc>>>,
+    pub priority: Option<Option<Priority>>,
+    /// RRULE text, parsed when the patch is applied.
+    pub recurrence: Option<Option<String>>,
+}
+
+fn validate_schedule(
+    due_at: Option<DateTime<Utc>>,
+    remind_at: Option<DateTime<Utc>>,
+    recurrence: Option<&Recurrence>,
+) -> Result<(), DomainError> {
+    match (due_at, remind_at) {
+        (Some(due_at), Some(remind_at)) if remind_at >= due_at => {
+            return Err(DomainError::Validation(
+                "Reminder must be before the due date".into(),
+            ));
+        }
+        _ => {}
+    }
+    match (due_at, recurrence) {
+        (None, Some(_)) => Err(DomainError::Validation(
+            "A recurring todo needs a due date".into(),
+        )),
+        (Some(due_at), Some(recurrence))
+            if recurrence.until.is_some_and(|until| until < due_at) =>
+        {
+            Err(DomainError::Validation(
+                "Recurrence must not end before the due date".into(),
+            ))
+        }
+        _ => Ok(()),
+    }
 }
 
 impl Todo {
-    pub fn new(title: String, description: Option<String>) -> Self {
-        Self {
+    pub fn new(owner_id: Uuid, title: String, description: Option<String>) -> Self {
+        let now = Utc::now();
+        let mut todo = Self {
             id: Uuid::now_v7(),
             title,
             description,
             completed: false,
-        }
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            deleted_at: None,
+            due_at: None,
+            remind_at: None,
+            priority: Priority::default(),
+            position: 0,
+            items: Vec::new(),
+            project_id: None,
+            recurrence: None,
+            owner_id,
+            events: Vec::new(),
+        };
+        todo.record(TodoEventKind::TodoCreated);
+        todo
     }
 
     pub fn update(&mut self, title: String, description: Option<String>) {
         self.title = title;
         self.description = description;
+        self.changed(TodoEventKind::TodoUpdated);
+    }
+
+    pub fn reschedule(
+        &mut self,
+        due_at: Option<DateTime<Utc>>,
+        remind_at: Option<DateTime<Utc>>,
+        recurrence: Option<Recurrence>,
+    ) -> Result<(), DomainError> {
+        validate_schedule(due_at, remind_at, recurrence.as_ref())?;
+        self.due_at = due_at;
+        self.remind_at = remind_at;
+        self.recurrence = recurrence;
+        self.changed(TodoEventKind::TodoUpdated);
+        Ok(())
+    }
+
+    pub fn prioritize(&mut self, priority: Priority) {
+        self.priority = priority;
+        self.changed(TodoEventKind::TodoUpdated);
+    }
+
+    pub fn move_to_project(&mut self, project_id: Option<Uuid>) {
+        self.project_id = project_id;
+        self.changed(TodoEventKind::TodoUpdated);
     }
 
-    pub fn mark_completed(&mut self) -> Result<(), DomainError> {
+    pub fn reposition(&mut self, position: i64) {
+        self.position = position;
+        self.changed(TodoEventKind::TodoUpdated);
+    }
+
+    /// Overdue means past its due date while still open.
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        !self.completed && self.due_at.is_some_and(|due_at| due_at < now)
+    }
+
+    pub fn apply_patch(&mut self, patch: TodoPatch) -> Result<(), DomainError> {
+        let title = match patch.title {
+            None => None,
+            Some(None) => {
+                return Err(DomainError::Validation("Title cannot be removed".into()));
+            }
+            Some(Some(title)) => {
+                let length = title.chars().count() as u64;
+                if !(TITLE_MIN_LENGTH..=TITLE_MAX_LENGTH).contains(&length) {
+                    return Err(DomainError::Validation(format!(
+                        "Title must be between {} and {} characters",
+                        TITLE_MIN_LENGTH, TITLE_MAX_LENGTH
+                    )));
+                }
+                Some(title)
+            }
+        };
+        if let Some(Some(description)) = &patch.description
+            && description.chars().count() as u64 > DESCRIPTION_MAX_LENGTH
+        {
+            return Err(DomainError::Validation(format!(
+                "Description must be at most {} characters",
+                DESCRIPTION_MAX_LENGTH
+            )));
+        }
+        let priority = match patch.priority {
+            Some(None) => {
+                return Err(DomainError::Validation("Priority cannot be removed".into()));
+            }
+            Some(Some(priority)) => priority,
+            None => self.priority,
+        };
+        let recurrence = match patch.recurrence {
+            None => self.recurrence.clone(),
+            Some(None) => None,
+            Some(Some(rule)) => Some(rule.parse()?),
+        };
+        let due_at = patch.due_at.unwrap_or(self.due_at);
+        let remind_at = patch.remind_at.unwrap_or(self.remind_at);
+        validate_schedule(due_at, remind_at, recurrence.as_ref())?;
+
+        if let Some(title) = title {
+            self.title = title;
+        }
+        if let Some(description) = patch.description {
+            self.description = description;
+        }
+        self.due_at = due_at;
+        self.remind_at = remind_at;
+        self.recurrence = recurrence;
+        self.priority = priority;
+        self.changed(TodoEventKind::TodoUpdated);
+        Ok(())
+    }
+
+    /// A todo cannot be completed while any of its `blockers` is open, nor in
+    /// strict mode while checklist items are open. Completing an occurrence of a
+    /// recurring todo returns the next occurrence, unless the series is over.
+    pub fn mark_completed(
+        &mut self,
+        strict: bool,
+        blockers: &[Todo],
+    ) -> Result<Option<Todo>, DomainError> {
         if self.completed {
             return Err(DomainError::Conflict("Todo is already completed".into()));
         }
+        let open_blockers = blockers.iter().filter(|blocker| !blocker.completed).count();
+        if open_blockers > 0 {
+            return Err(DomainError::Conflict(format!(
+                "Todo is blocked by {} open todos",
+                open_blockers
+            )));
+        }
+        let (done, total) = self.progress();
+        if strict && done < total {
+            return Err(DomainError::Conflict(format!(
+                "{} of {} checklist items are still open",
+                total - done,
+                total
+            )));
+        }
         self.completed = true;
-        Ok(())
+        self.changed(TodoEventKind::TodoCompleted);
+        Ok(self.next_occurrence())
+    }
+
+    /// Takes the recurrence rule and builds the open todo due at the next
+    /// occurrence, with the reminder offset and checklist copied over.
+    fn next_occurrence(&mut self) -> Option<Todo> {
+        let recurrence = self.recurrence.take()?;
+        let due_at = self.due_at?;
+        let next_recurrence = recurrence.advance()?;
+        let next_due_at = recurrence.next_after(due_at)?;
+        let mut next = Todo::new(self.owner_id, self.title.clone(), self.description.clone());
+        next.due_at = Some(next_due_at);
+        next.remind_at = self
+            .remind_at
+            .map(|remind_at| next_due_at - (due_at - remind_at));
+        next.recurrence = Some(next_recurrence);
+        next.priority = self.priority;
+        next.project_id = self.project_id;
+        next.items = self
+            .items
+            .iter()
+            .map(|item| ChecklistItem::new(item.title.clone(), item.position))
+            .collect();
+        Some(next)
     }
 
     pub fn unmark_completed(&mut self) -> Result<(), DomainError> {
@@ -38,8 +272,163 @@ impl Todo {
             return Err(DomainError::Conflict("Todo is not completed".into()));
         }
         self.completed = false;
+        self.changed(TodoEventKind::TodoUncompleted);
         Ok(())
     }
+
+    pub fn is_trashed(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    pub fn trash(&mut self) -> Result<(), DomainError> {
+        if self.is_trashed() {
+            return Err(DomainError::Conflict("Todo is already in the trash".into()));
+        }
+        self.changed(TodoEventKind::TodoDeleted);
+        self.deleted_at = Some(self.updated_at);
+        Ok(())
+    }
+
+    pub fn restore(&mut self) -> Result<(), DomainError> {
+        if !self.is_trashed() {
+            return Err(DomainError::Conflict("Todo is not in the trash".into()));
+        }
+        self.deleted_at = None;
+        // Coming back from the trash counts as an update.
+        self.changed(TodoEventKind::TodoUpdated);
+        Ok(())
+    }
+
+    /// Completed and total number of checklist items.
+    pub fn progress(&self) -> (usize, usize) {
+        let done = self.items.iter().filter(|item| item.completed).count();
+        (done, self.items.len())
+    }
+
+    pub fn add_item(&mut self, title: String) -> Uuid {
+        let position = self.items.last().map_or(1, |item| item.position + 1);
+        let item = ChecklistItem::new(title, position);
+        let id = item.id;
+        self.items.push(item);
+        self.changed(TodoEventKind::TodoUpdated);
+        id
+    }
+
+    pub fn update_item(
+        &mut self,
+        item_id: Uuid,
+        title: String,
+        completed: bool,
+    ) -> Result<(), DomainError> {
+        let item = self
+            .items
+            .iter_mut()
+            .find(|item| item.id == item_id)
+            .ok_or_else(|| item_not_found(item_id))?;
+        item.update(title, completed);
+        self.changed(TodoEventKind::TodoUpdated);
+        Ok(())
+    }
+
+    pub fn remove_item(&mut self, item_id: Uuid) -> Result<(), DomainError> {
+        let index = self
+            .items
+            .iter()
+            .position(|item| item.id == item_id)
+            .ok_or_else(|| item_not_found(item_id))?;
+        self.items.remove(index);
+        self.changed(TodoEventKind::TodoUpdated);
+        Ok(())
+    }
+
+    /// Hands over the recorded events, stamped with the todo's current project.
+    pub fn take_events(&mut self) -> Vec<TodoEvent> {
+        let mut events = std::mem::take(&mut self.events);
+        for event in &mut events {
+            event.project_id = self.project_id;
+        }
+        events
+    }
+
+    /// Records an event unless one of the same kind is already pending, so a
+    /// single operation touching several fields yields a single event. Edits
+    /// made before a new todo is first saved are part of its creation.
+    fn record(&mut self, kind: TodoEventKind) {
+        let pending =
+            |pending: TodoEventKind| self.events.iter().any(|event| event.kind == pending);
+        let absorbed = kind == TodoEventKind::TodoUpdated && pending(TodoEventKind::TodoCreated);
+        if !pending(kind) && !absorbed {
+            let event = TodoEvent::new(kind, self.id, self.owner_id, Utc::now());
+            self.events.push(event);
+        }
+    }
+
+    /// Bumps `updated_at` and records the event describing the change.
+    fn changed(&mut self, kind: TodoEventKind) {
+        self.updated_at = Utc::now();
+        self.record(kind);
+    }
+}
+
+fn item_not_found(item_id: Uuid) -> DomainError {
+    DomainError::NotFound(format!("Checklist item with id {} not found", item_id))
+}
+
+/// Moves the todo `id` between its `after` and `before` neighbours and renumbers
+/// every position from 1. Returns only the todos whose position changed.
+pub fn reorder(
+    mut todos: Vec<Todo>,
+    id: Uuid,
+    before: Option<Uuid>,
+    after: Option<Uuid>,
+) -> Result<Vec<Todo>, DomainError> {
+    if before.is_none() && after.is_none() {
+        return Err(DomainError::Validation(
+            "Either before or after must be given".into(),
+        ));
+    }
+    if before == Some(id) || after == Some(id) {
+        return Err(DomainError::Validation(
+            "A todo cannot be moved next to itself".into(),
+        ));
+    }
+    todos.sort_by_key(|todo| (todo.position, todo.id));
+    let index_of = |todos: &[Todo], target: Uuid| {
+        todos
+            .iter()
+            .position(|todo| todo.id == target)
+            .ok_or(DomainError::Unexpected(format!(
+                "Todo with id {} is not part of the ordering",
+                target
+            )))
+    };
+    let moving = todos.remove(index_of(&todos, id)?);
+    let index = match (before, after) {
+        (Some(before), Some(after)) => {
+            let index = index_of(&todos, before)?;
+            if index == 0 || todos[index - 1].id != after {
+                return Err(DomainError::Validation(
+                    "The before and after todos are not adjacent".into(),
+                ));
+            }
+            index
+        }
+        (Some(before), None) => index_of(&todos, before)?,
+        (None, Some(after)) => index_of(&todos, after)? + 1,
+        (None, None) => unreachable!(),
+    };
+    todos.insert(index, moving);
+
+    Ok(todos
+        .into_iter()
+        .zip(1..)
+        .filter_map(|(mut todo, position)| {
+            (todo.position != position).then(|| {
+                todo.reposition(position);
+                todo
+            })
+        })
+        .collect())
 }
 
 #[cfg(test)]
@@ -47,32 +436,217 @@ mod tests {
     use super::*;
     #[test]
     fn test_todo_creation() {
-        let todo = Todo::new("Test Todo".into(), None);
+        let todo = Todo::new(Uuid::now_v7(), "Test Todo".into(), None);
         assert!(todo.id != Uuid::nil());
         assert_eq!(todo.title, "Test Todo");
         assert_eq!(todo.description, None);
         assert!(!todo.completed);
+        assert_eq!(todo.created_at, todo.updated_at);
     }
 
     #[test]
     fn test_todo_update() {
-        let mut todo = Todo::new("Test Todo".into(), None);
+        let mut todo = Todo::new(Uuid::now_v7(), "Test Todo".into(), None);
+        let created_at = todo.created_at;
         todo.update("Updated Todo".into(), Some("Updated Description".into()));
         assert_eq!(todo.title, "Updated Todo");
         assert_eq!(todo.description, Some("Updated Description".into()));
+        assert_eq!(todo.created_at, created_at);
+        assert!(todo.updated_at >= created_at);
+    }
+
+    #[test]
+    fn test_apply_patch() {
+        let mut todo = Todo::new(
+            Uuid::now_v7(),
+            "Test Todo".into(),
+            Some("Description".into()),
+        );
+        todo.apply_patch(TodoPatch {
+            description: Some(Some("New Description".into())),
+            ..TodoPatch::default()
+        })
+        .unwrap();
+        assert_eq!(todo.title, "Test Todo");
+        assert_eq!(todo.description, Some("New Description".into()));
+
+        todo.apply_patch(TodoPatch {
+            title: Some(Some("New Title".into())),
+            description: Some(None),
+            priority: Some(Some(Priority::Urgent)),
+            ..TodoPatch::default()
+        })
+        .unwrap();
+        assert_eq!(todo.title, "New Title");
+        assert_eq!(todo.description, None);
+        assert_eq!(todo.priority, Priority::Urgent);
+    }
+
+    #[test]
+    fn test_apply_patch_invalid() {
+        let mut todo = Todo::new(
+            Uuid::now_v7(),
+            "Test Todo".into(),
+            Some("Description".into()),
+        );
+        let invalid_patches = [
+            TodoPatch {
+                title: Some(None),
+                ..TodoPatch::default()
+            },
+            TodoPatch {
+                title: Some(Some("T".into())),
+                ..TodoPatch::default()
+            },
+            TodoPatch {
+                priority: Some(None),
+                ..TodoPatch::default()
+            },
+            TodoPatch {
+                title: Some(Some("Valid Title".into())),
+                description: Some(Some("d".repeat(256))),
+                ..TodoPatch::default()
+            },
+            TodoPatch {
+                due_at: Some(DateTime::from_timestamp(100, 0)),
+                remind_at: Some(DateTime::from_timestamp(200, 0)),
+                ..TodoPatch::default()
+            },
+        ];
+        for patch in invalid_patches {
+            assert!(matches!(
+                todo.apply_patch(patch),
+                Err(DomainError::Validation(_))
+            ));
+        }
+        assert_eq!(todo.title, "Test Todo");
+        assert_eq!(todo.description, Some("Description".into()));
+    }
+
+    #[test]
+    fn test_reschedule() {
+        let mut todo = Todo::new(Uuid::now_v7(), "Test Todo".into(), None);
+        let due_at = DateTime::from_timestamp(200, 0);
+        todo.reschedule(due_at, DateTime::from_timestamp(100, 0), None)
+            .unwrap();
+        assert_eq!(todo.due_at, due_at);
+        todo.reschedule(None, DateTime::from_timestamp(100, 0), None)
+            .unwrap();
+        assert_eq!(todo.due_at, None);
+
+        let result = todo.reschedule(due_at, due_at, None);
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+        assert_eq!(todo.due_at, None);
+    }
+
+    #[test]
+    fn test_is_overdue() {
+        let mut todo = Todo::new(Uuid::now_v7(), "Test Todo".into(), None);
+        let now = DateTime::from_timestamp(300, 0).unwrap();
+        assert!(!todo.is_overdue(now));
+        todo.reschedule(DateTime::from_timestamp(200, 0), None, None)
+            .unwrap();
+        assert!(todo.is_overdue(now));
+        todo.mark_completed(false, &[]).unwrap();
+        assert!(!todo.is_overdue(now));
+    }
+
+    fn ordered_todos(count: i64) -> Vec<Todo> {
+        (1..=count)
+            .map(|position| {
+                let mut todo = Todo::new(Uuid::now_v7(), format!("Todo {}", position), None);
+                todo.position = position;
+                todo
+            })
+            .collect()
+    }
+
+    fn titles_in_order(todos: &[Todo], changed: &[Todo]) -> Vec<String> {
+        let mut merged: Vec<Todo> = todos
+            .iter()
+            .map(|todo| {
+                changed
+                    .iter()
+                    .find(|c| c.id == todo.id)
+                    .unwrap_or(todo)
+                    .clone()
+            })
+            .collect();
+        merged.sort_by_key(|todo| todo.position);
+        merged.into_iter().map(|todo| todo.title).collect()
+    }
+
+    #[test]
+    fn test_reorder() {
+        let todos = ordered_todos(4);
+
+        let changed = reorder(todos.clone(), todos[3].id, Some(todos[1].id), None).unwrap();
+        assert_eq!(changed.len(), 3);
+        assert_eq!(
+            titles_in_order(&todos, &changed),
+            ["Todo 1", "Todo 4", "Todo 2", "Todo 3"]
+        );
+
+        let changed = reorder(todos.clone(), todos[0].id, None, Some(todos[2].id)).unwrap();
+        assert_eq!(
+            titles_in_order(&todos, &changed),
+            ["Todo 2", "Todo 3", "Todo 1", "Todo 4"]
+        );
+
+        let changed = reorder(
+            todos.clone(),
+            todos[0].id,
+            Some(todos[3].id),
+            Some(todos[2].id),
+        )
+        .unwrap();
+        assert_eq!(
+            titles_in_order(&todos, &changed),
+            ["Todo 2", "Todo 3", "Todo 1", "Todo 4"]
+        );
+    }
+
+    #[test]
+    fn test_reorder_invalid() {
+        let todos = ordered_todos(3);
+        let invalid_moves = [
+            (None, None),
+            (Some(todos[0].id), None),
+            (Some(todos[2].id), Some(todos[0].id)),
+        ];
+        for (before, after) in invalid_moves {
+            let result = reorder(todos.clone(), todos[0].id, before, after);
+            assert!(matches!(result, Err(DomainError::Validation(_))));
+        }
     }
 
     #[test]
     fn test_mark_completed() {
+        let created_at = DateTime::from_timestamp(0, 0).unwrap();
         let mut todo = Todo {
             id: Uuid::now_v7(),
             title: "Test Todo".into(),
             description: None,
             completed: false,
+            created_at,
+            updated_at: created_at,
+            version: 1,
+            deleted_at: None,
+            due_at: None,
+            remind_at: None,
+            priority: Priority::Medium,
+            position: 1,
+            items: vec![],
+            project_id: None,
+            recurrence: None,
+            owner_id: Uuid::now_v7(),
+            events: vec![],
         };
         assert!(!todo.completed);
-        todo.mark_completed().unwrap();
+        todo.mark_completed(false, &[]).unwrap();
         assert!(todo.completed);
+        assert_eq!(todo.created_at, created_at);
+        assert!(todo.updated_at > created_at);
     }
 
     #[test]
@@ -82,11 +656,66 @@ mod tests {
             title: "Test Todo".into(),
             description: None,
             completed: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+            due_at: None,
+            remind_at: None,
+            priority: Priority::Medium,
+            position: 1,
+            items: vec![],
+            project_id: None,
+            recurrence: None,
+            owner_id: Uuid::now_v7(),
+            events: vec![],
         };
-        let result = todo.mark_completed();
+        let result = todo.mark_completed(false, &[]);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_mark_completed_strict() {
+        let mut todo = Todo::new(Uuid::now_v7(), "Test Todo".into(), None);
+        let first = todo.add_item("First step".into());
+        let second = todo.add_item("Second step".into());
+        todo.update_item(first, "First step".into(), true).unwrap();
+        assert_eq!(todo.progress(), (1, 2));
+
+        let result = todo.mark_completed(true, &[]);
+        assert!(matches!(result, Err(DomainError::Conflict(_))));
+        assert!(!todo.completed);
+
+        todo.update_item(second, "Second step".into(), true)
+            .unwrap();
+        todo.mark_completed(true, &[]).unwrap();
+        assert!(todo.completed);
+    }
+
+    #[test]
+    fn test_checklist_items() {
+        let mut todo = Todo::new(Uuid::now_v7(), "Test Todo".into(), None);
+        let first = todo.add_item("First step".into());
+        let second = todo.add_item("Second step".into());
+        assert_eq!(todo.items[0].position, 1);
+        assert_eq!(todo.items[1].position, 2);
+
+        todo.remove_item(first).unwrap();
+        let third = todo.add_item("Third step".into());
+        assert_eq!(todo.items[0].id, second);
+        assert_eq!(todo.items[1].id, third);
+        assert_eq!(todo.items[1].position, 3);
+
+        assert!(matches!(
+            todo.remove_item(first),
+            Err(DomainError::NotFound(_))
+        ));
+        assert!(matches!(
+            todo.update_item(first, "Gone".into(), true),
+            Err(DomainError::NotFound(_))
+        ));
+    }
+
     #[test]
     fn test_unmark_completed() {
         let mut todo = Todo {
@@ -94,6 +723,19 @@ mod tests {
             title: "Test Todo".into(),
             description: None,
             completed: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+            due_at: None,
+            remind_at: None,
+            priority: Priority::Medium,
+            position: 1,
+            items: vec![],
+            project_id: None,
+            recurrence: None,
+            owner_id: Uuid::now_v7(),
+            events: vec![],
         };
         todo.unmark_completed().unwrap();
         assert!(!todo.completed);
@@ -106,8 +748,168 @@ mod tests {
             title: "Test Todo".into(),
             description: None,
             completed: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+            due_at: None,
+            remind_at: None,
+            priority: Priority::Medium,
+            position: 1,
+            items: vec![],
+            project_id: None,
+            recurrence: None,
+            owner_id: Uuid::now_v7(),
+            events: vec![],
         };
         let result = todo.unmark_completed();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_trash_and_restore() {
+        let mut todo = Todo::new(Uuid::now_v7(), "Test Todo".into(), None);
+        todo.trash().unwrap();
+        assert!(todo.is_trashed());
+        assert!(todo.trash().is_err());
+        todo.restore().unwrap();
+        assert!(!todo.is_trashed());
+        assert!(todo.restore().is_err());
+    }
+
+    #[test]
+    fn test_recorded_events() {
+        let kinds = |todo: &mut Todo| -> Vec<TodoEventKind> {
+            todo.take_events().iter().map(|event| event.kind).collect()
+        };
+        let mut todo = Todo::new(Uuid::now_v7(), "Test Todo".into(), None);
+        let project_id = Uuid::now_v7();
+        todo.project_id = Some(project_id);
+        // Edits before the first save are part of the creation.
+        todo.prioritize(Priority::Low);
+        let events = todo.take_events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, TodoEventKind::TodoCreated);
+        assert_eq!(events[0].todo_id, todo.id);
+        assert_eq!(events[0].project_id, Some(project_id));
+        assert!(todo.events.is_empty());
+
+        // Several changes in one operation yield a single update.
+        todo.update("Renamed".into(), None);
+        todo.prioritize(Priority::High);
+        assert_eq!(kinds(&mut todo), vec![TodoEventKind::TodoUpdated]);
+
+        todo.mark_completed(false, &[]).unwrap();
+        todo.unmark_completed().unwrap();
+        todo.trash().unwrap();
+        assert_eq!(
+            kinds(&mut todo),
+            vec![
+                TodoEventKind::TodoCompleted,
+                TodoEventKind::TodoUncompleted,
+                TodoEventKind::TodoDeleted,
+            ]
+        );
+
+        // Failed changes record nothing.
+        assert!(todo.trash().is_err());
+        assert!(todo.events.is_empty());
+    }
+
+    #[test]
+    fn test_recurrence_requires_due_date() {
+        let mut todo = Todo::new(Uuid::now_v7(), "Weekly chore".into(), None);
+        let rule: Recurrence = "FREQ=WEEKLY".parse().unwrap();
+        let result = todo.reschedule(None, None, Some(rule.clone()));
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+
+        let due_at = DateTime::from_timestamp(1_700_000_000, 0);
+        todo.reschedule(due_at, None, Some(rule)).unwrap();
+        let result = todo.apply_patch(TodoPatch {
+            due_at: Some(None),
+            ..TodoPatch::default()
+        });
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+        let result = todo.apply_patch(TodoPatch {
+            recurrence: Some(Some("FREQ=YEARLY".into())),
+            ..TodoPatch::default()
+        });
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+
+        todo.apply_patch(TodoPatch {
+            due_at: Some(None),
+            recurrence: Some(None),
+            ..TodoPatch::default()
+        })
+        .unwrap();
+        assert!(todo.recurrence.is_none());
+    }
+
+    #[test]
+    fn test_recurrence_must_not_end_before_due_date() {
+        let mut todo = Todo::new(Uuid::now_v7(), "Weekly chore".into(), None);
+        let rule: Recurrence = "FREQ=WEEKLY;UNTIL=20231101".parse().unwrap();
+        let result = todo.reschedule(DateTime::from_timestamp(1_800_000_000, 0), None, Some(rule));
+        assert!(matches!(result, Err(DomainError::Validation(_))));
+    }
+
+    #[test]
+    fn test_mark_completed_recurring() {
+        let mut todo = Todo::new(Uuid::now_v7(), "Monthly report".into(), None);
+        todo.priority = Priority::High;
+        todo.add_item("Collect numbers".into());
+        let due_at = "2026-10-30T09:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        todo.reschedule(
+            Some(due_at),
+            Some(due_at - chrono::Duration::days(1)),
+            Some("FREQ=MONTHLY;BYDAY=-1FR;COUNT=2".parse().unwrap()),
+        )
+        .unwrap();
+        let item = todo.items[0].id;
+        todo.update_item(item, "Collect numbers".into(), true)
+            .unwrap();
+
+        let next = todo.mark_completed(false, &[]).unwrap().unwrap();
+        assert!(todo.completed);
+        assert!(todo.recurrence.is_none());
+        assert_ne!(next.id, todo.id);
+        assert!(!next.completed);
+        assert_eq!(next.title, "Monthly report");
+        assert_eq!(next.owner_id, todo.owner_id);
+        assert_eq!(next.priority, Priority::High);
+        let next_due_at = "2026-11-27T09:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(next.due_at, Some(next_due_at));
+        assert_eq!(
+            next.remind_at,
+            Some(next_due_at - chrono::Duration::days(1))
+        );
+        assert_eq!(next.progress(), (0, 1));
+        assert_eq!(next.recurrence.as_ref().unwrap().count, Some(1));
+
+        // The last occurrence of the series does not generate another one.
+        let mut last = next;
+        assert!(last.mark_completed(false, &[]).unwrap().is_none());
+
+        // Reopening and completing again does not duplicate the next occurrence.
+        todo.unmark_completed().unwrap();
+        assert!(todo.mark_completed(false, &[]).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_mark_completed_blocked() {
+        let mut todo = Todo::new(Uuid::now_v7(), "Ship release".into(), None);
+        let mut blockers = vec![
+            Todo::new(Uuid::now_v7(), "Write changelog".into(), None),
+            Todo::new(Uuid::now_v7(), "Run tests".into(), None),
+        ];
+        blockers[0].mark_completed(false, &[]).unwrap();
+
+        let result = todo.mark_completed(false, &blockers);
+        assert!(matches!(result, Err(DomainError::Conflict(_))));
+        assert!(!todo.completed);
+
+        blockers[1].mark_completed(false, &[]).unwrap();
+        todo.mark_completed(false, &blockers).unwrap();
+        assert!(todo.completed);
+    }
 }
@@ -0,0 +1,225 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::domain::models::errors::DomainError;
+
+/// Delay before the first retry of a failed publication; doubled on every further failure.
+pub const RETRY_BASE_DELAY_SECONDS: i64 = 1;
+/// Upper bound on the delay between two retries.
+pub const RETRY_MAX_DELAY_SECONDS: i64 = 600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoEventKind {
+    TodoCreated,
+    TodoUpdated,
+    TodoCompleted,
+    TodoUncompleted,
+    /// Moved to the trash.
+    TodoDeleted,
+}
+
+impl fmt::Display for TodoEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TodoEventKind::TodoCreated => "todo.created",
+            TodoEventKind::TodoUpdated => "todo.updated",
+            TodoEventKind::TodoCompleted => "todo.completed",
+            TodoEventKind::TodoUncompleted => "todo.uncompleted",
+            TodoEventKind::TodoDeleted => "todo.deleted",
+        })
+    }
+}
+
+impl FromStr for TodoEventKind {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "todo.created" => Ok(TodoEventKind::TodoCreated),
+            "todo.updated" => Ok(TodoEventKind::TodoUpdated),
+            "todo.completed" => Ok(TodoEventKind::TodoCompleted),
+            "todo.uncompleted" => Ok(TodoEventKind::TodoUncompleted),
+            "todo.deleted" => Ok(TodoEventKind::TodoDeleted),
+            _ => Err(DomainError::Validation(format!(
+                "Unknown todo event type: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Something that happened to a todo, recorded by the [`Todo`](super::todo::Todo)
+/// aggregate and queued in the outbox when the todo is saved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TodoEvent {
+    pub id: Uuid,
+    pub kind: TodoEventKind,
+    pub todo_id: Uuid,
+    pub owner_id: Uuid,
+    /// Project of the todo once the change is saved.
+    pub project_id: Option<Uuid>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl TodoEvent {
+    pub(crate) fn new(
+        kind: TodoEventKind,
+        todo_id: Uuid,
+        owner_id: Uuid,
+        occurred_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            kind,
+            todo_id,
+            owner_id,
+            project_id: None,
+            occurred_at,
+        }
+    }
+
+    /// The JSON form sent to clients outside the process.
+    pub fn payload(&self) -> Value {
+        json!({
+            "id": self.id,
+            "type": self.kind.to_string(),
+            "occurred_at": self.occurred_at,
+            "data": {
+                "todo_id": self.todo_id,
+                "owner_id": self.owner_id,
+                "project_id": self.project_id,
+            },
+        })
+    }
+
+    /// Reads back an event from its [`payload`](Self::payload).
+    pub fn from_payload(payload: &Value) -> Result<Self, DomainError> {
+        let data = &payload["data"];
+        Ok(Self {
+            id: payload_field(&payload["id"])?,
+            kind: payload_field::<String>(&payload["type"])?.parse()?,
+            todo_id: payload_field(&data["todo_id"])?,
+            owner_id: payload_field(&data["owner_id"])?,
+            project_id: payload_field(&data["project_id"])?,
+            occurred_at: payload_field(&payload["occurred_at"])?,
+        })
+    }
+}
+
+fn payload_field<T: DeserializeOwned>(value: &Value) -> Result<T, DomainError> {
+    serde_json::from_value(value.clone())
+        .map_err(|err| DomainError::Validation(format!("Malformed todo event payload: {}", err)))
+}
+
+/// A queued event and the state of its publication. Publication is
+/// at-least-once: failures are retried with exponential backoff until they succeed.
+#[derive(Debug, Clone)]
+pub struct OutboxMessage {
+    pub event: TodoEvent,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+impl OutboxMessage {
+    pub fn new(event: TodoEvent) -> Self {
+        Self {
+            next_attempt_at: event.occurred_at,
+            event,
+            attempts: 0,
+            published_at: None,
+            last_error: None,
+        }
+    }
+
+    pub fn mark_published(&mut self, now: DateTime<Utc>) {
+        self.attempts += 1;
+        self.published_at = Some(now);
+        self.last_error = None;
+    }
+
+    /// Schedules the next attempt after a delay that doubles with every failure.
+    pub fn mark_failed(&mut self, error: String, now: DateTime<Utc>) {
+        self.attempts += 1;
+        self.last_error = Some(error);
+        self.next_attempt_at = now + retry_delay(self.attempts);
+    }
+}
+
+/// Delay before retrying after the given number of failed attempts.
+pub fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 30) as u32;
+    let seconds = RETRY_BASE_DELAY_SECONDS.saturating_mul(1 << exponent);
+    Duration::seconds(seconds.min(RETRY_MAX_DELAY_SECONDS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_todo_event_kind_round_trip() {
+        for kind in [
+            TodoEventKind::TodoCreated,
+            TodoEventKind::TodoUpdated,
+            TodoEventKind::TodoCompleted,
+            TodoEventKind::TodoUncompleted,
+            TodoEventKind::TodoDeleted,
+        ] {
+            assert_eq!(kind.to_string().parse::<TodoEventKind>().unwrap(), kind);
+        }
+        assert!("todo.purged".parse::<TodoEventKind>().is_err());
+    }
+
+    #[test]
+    fn test_todo_event_payload_round_trip() {
+        let mut event = TodoEvent::new(
+            TodoEventKind::TodoCompleted,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            Utc::now(),
+        );
+        assert_eq!(TodoEvent::from_payload(&event.payload()).unwrap(), event);
+        event.project_id = Some(Uuid::now_v7());
+        assert_eq!(TodoEvent::from_payload(&event.payload()).unwrap(), event);
+
+        let mut payload = event.payload();
+        payload["type"] = json!("todo.purged");
+        assert!(TodoEvent::from_payload(&payload).is_err());
+        payload["type"] = json!("todo.created");
+        payload["data"]["owner_id"] = json!("not-a-uuid");
+        assert!(TodoEvent::from_payload(&payload).is_err());
+    }
+
+    #[test]
+    fn test_outbox_message_backoff() {
+        let now = Utc::now();
+        let event = TodoEvent::new(
+            TodoEventKind::TodoCreated,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            now,
+        );
+        let mut message = OutboxMessage::new(event);
+        assert_eq!(message.next_attempt_at, now);
+
+        message.mark_failed("unreachable".into(), now);
+        assert_eq!(message.attempts, 1);
+        assert_eq!(message.next_attempt_at, now + Duration::seconds(1));
+        message.mark_failed("unreachable".into(), now);
+        assert_eq!(message.next_attempt_at, now + Duration::seconds(2));
+        message.mark_failed("unreachable".into(), now);
+        assert_eq!(message.next_attempt_at, now + Duration::seconds(4));
+        assert_eq!(retry_delay(50), Duration::seconds(RETRY_MAX_DELAY_SECONDS));
+
+        message.mark_published(now);
+        assert_eq!(message.attempts, 4);
+        assert_eq!(message.published_at, Some(now));
+        assert!(message.last_error.is_none());
+    }
+}
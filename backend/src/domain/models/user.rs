@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::models::errors::DomainError;
+
+pub const EMAIL_MAX_LENGTH: u64 = 254;
+pub const PASSWORD_MIN_LENGTH: u64 = 8;
+pub const PASSWORD_MAX_LENGTH: u64 = 128;
+
+#[derive(Debug, Clone)]
+pub struct User {
+    pub id: Uuid,
+    /// Trimmed and lowercased; unique across all users.
+    pub email: String,
+    /// PHC string of the password hash; the password itself is never stored.
+    pub password_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Canonical form of an email address, used both to store and to look up users.
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+impl User {
+    pub fn new(email: &str, password_hash: String) -> Result<Self, DomainError> {
+        let email = normalize_email(email);
+        let valid = email.len() as u64 <= EMAIL_MAX_LENGTH
+            && email
+                .split_once('@')
+                .is_some_and(|(local, domain)| !local.is_empty() && !domain.is_empty());
+        if !valid {
+            return Err(DomainError::Validation(format!(
+                "{} is not a valid email address",
+                email
+            )));
+        }
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::now_v7(),
+            email,
+            password_hash,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_creation() {
+        let user = User::new("  Alice@Example.COM ", "hash".into()).unwrap();
+        assert!(user.id != Uuid::nil());
+        assert_eq!(user.email, "alice@example.com");
+        assert_eq!(user.password_hash, "hash");
+        assert_eq!(user.created_at, user.updated_at);
+    }
+
+    #[test]
+    fn test_user_invalid_email() {
+        for email in [
+            "",
+            "alice",
+            "@example.com",
+            "alice@",
+            &format!("{}@x.io", "a".repeat(250)),
+        ] {
+            let result = User::new(email, "hash".into());
+            assert!(matches!(result, Err(DomainError::Validation(_))));
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use serde_json::Value;
+use url::Url;
+use uuid::Uuid;
+
+use crate::domain::models::{
+    errors::DomainError,
+    todo_event::{TodoEvent, TodoEventKind, retry_delay},
+};
+
+pub const WEBHOOK_URL_MAX_LENGTH: u64 = 2048;
+pub const WEBHOOK_SECRET_MIN_LENGTH: u64 = 16;
+pub const WEBHOOK_SECRET_MAX_LENGTH: u64 = 256;
+/// A delivery is given up after this many failed attempts; it can still be replayed.
+pub const WEBHOOK_MAX_ATTEMPTS: i32 = 10;
+/// How long a dispatcher may take to send the deliveries it claimed before
+/// another one can claim them again.
+pub const WEBHOOK_LEASE: TimeDelta = TimeDelta::minutes(5);
+
+/// A subscription of one user to the events on the todos they own.
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub url: String,
+    /// The kinds of event delivered; empty means every kind.
+    pub events: Vec<TodoEventKind>,
+    /// Key of the HMAC-SHA256 signature sent with every delivery.
+    pub secret: String,
+    /// Inactive webhooks keep their history but receive nothing new.
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    pub fn new(
+        owner_id: Uuid,
+        url: String,
+        events: Vec<TodoEventKind>,
+        secret: String,
+    ) -> Result<Self, DomainError> {
+        validate_url(&url)?;
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::now_v7(),
+            owner_id,
+            url,
+            events: normalize(events),
+            secret,
+            active: true,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn update(
+        &mut self,
+        url: String,
+        events: Vec<TodoEventKind>,
+        secret: String,
+        active: bool,
+    ) -> Result<(), DomainError> {
+        validate_url(&url)?;
+        self.url = url;
+        self.events = normalize(events);
+        self.secret = secret;
+        self.active = active;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn accepts(&self, kind: TodoEventKind) -> bool {
+        self.active && (self.events.is_empty() || self.events.contains(&kind))
+    }
+}
+
+fn normalize(mut events: Vec<TodoEventKind>) -> Vec<TodoEventKind> {
+    events.sort_by_key(|kind| *kind as u8);
+    events.dedup();
+    events
+}
+
+fn validate_url(url: &str) -> Result<(), DomainError> {
+    let is_http = Url::parse(url)
+        .is_ok_and(|parsed| matches!(parsed.scheme(), "http" | "https") && parsed.host().is_some());
+    if !is_http {
+        return Err(DomainError::Validation(format!(
+            "{} is not an http(s) URL",
+            url
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    /// Every attempt failed; only a replay sends it again.
+    Failed,
+}
+
+/// One event to send to one webhook, retried with exponential backoff until
+/// the receiver answers with a 2xx status.
+#[derive(Debug, Clone)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: TodoEventKind,
+    /// The JSON body sent to the receiver.
+    pub payload: Value,
+    pub attempts: i32,
+    /// `None` once delivered or given up.
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The outcome of sending a delivery once.
+#[derive(Debug, Clone)]
+pub struct DeliveryAttempt {
+    pub id: Uuid,
+    pub delivery_id: Uuid,
+    /// `None` when no response came back.
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub attempted_at: DateTime<Utc>,
+}
+
+impl WebhookDelivery {
+    pub fn new(webhook_id: Uuid, event: &TodoEvent) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::now_v7(),
+            webhook_id,
+            event_id: event.id,
+            event_type: event.kind,
+            payload: event.payload(),
+            attempts: 0,
+            next_attempt_at: Some(now),
+            delivered_at: None,
+            last_error: None,
+            created_at: now,
+        }
+    }
+
+    pub fn status(&self) -> DeliveryStatus {
+        match (self.next_attempt_at, self.delivered_at) {
+            (Some(_), _) => DeliveryStatus::Pending,
+            (None, Some(_)) => DeliveryStatus::Delivered,
+            (None, None) => DeliveryStatus::Failed,
+        }
+    }
+
+    /// Records the response to an attempt, or the transport error when there
+    /// was none, and schedules the next attempt if it failed.
+    pub fn record_attempt(
+        &mut self,
+        outcome: Result<u16, String>,
+        now: DateTime<Utc>,
+    ) -> DeliveryAttempt {
+        self.attempts += 1;
+        let (status_code, error) = match outcome {
+            Ok(status) if (200..300).contains(&status) => (Some(status as i32), None),
+            Ok(status) => (
+                Some(status as i32),
+                Some(format!("Receiver answered with status {}", status)),
+            ),
+            Err(error) => (None, Some(error)),
+        };
+        if error.is_none() {
+            self.delivered_at = Some(now);
+            self.next_attempt_at = None;
+        } else if self.attempts >= WEBHOOK_MAX_ATTEMPTS {
+            self.next_attempt_at = None;
+        } else {
+            self.next_attempt_at = Some(now + retry_delay(self.attempts));
+        }
+        self.last_error = error.clone();
+        DeliveryAttempt {
+            id: Uuid::now_v7(),
+            delivery_id: self.id,
+            status_code,
+            error,
+            attempted_at: now,
+        }
+    }
+
+    /// Claims the delivery for one dispatcher: it is not due again before
+    /// `until`, so a dispatcher stopping before recording a result only
+    /// delays the next attempt.
+    pub fn lease(&mut self, until: DateTime<Utc>) {
+        self.next_attempt_at = Some(until);
+    }
+
+    /// Gives up on the delivery without sending it, e.g. because its webhook
+    /// was deactivated. It can still be replayed.
+    pub fn abandon(&mut self, reason: String) {
+        self.next_attempt_at = None;
+        self.last_error = Some(reason);
+    }
+
+    /// Sends the delivery again at the next dispatch with a fresh retry budget,
+    /// whatever happened to it so far. Earlier attempts stay on record.
+    pub fn replay(&mut self, now: DateTime<Utc>) {
+        self.attempts = 0;
+        self.next_attempt_at = Some(now);
+        self.delivered_at = None;
+        self.last_error = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_webhook_filter() {
+        let owner_id = Uuid::now_v7();
+        for url in [
+            "ftp://example.com",
+            "example.com",
+            "https://",
+            "http:// x",
+            "https:/",
+            "mailto:hooks@example.com",
+        ] {
+            let result = Webhook::new(owner_id, url.into(), vec![], "s".repeat(16));
+            assert!(result.is_err(), "{}", url);
+        }
+        for url in ["HTTPS://example.com/hook", "http://127.0.0.1:8080"] {
+            let result = Webhook::new(owner_id, url.into(), vec![], "s".repeat(16));
+            assert!(result.is_ok(), "{}", url);
+        }
+
+        let mut webhook = Webhook::new(
+            owner_id,
+            "https://example.com/hooks".into(),
+            vec![TodoEventKind::TodoDeleted, TodoEventKind::TodoCreated],
+            "s".repeat(16),
+        )
+        .unwrap();
+        assert_eq!(
+            webhook.events,
+            vec![TodoEventKind::TodoCreated, TodoEventKind::TodoDeleted]
+        );
+        assert!(webhook.accepts(TodoEventKind::TodoCreated));
+        assert!(!webhook.accepts(TodoEventKind::TodoUpdated));
+
+        webhook
+            .update(webhook.url.clone(), vec![], webhook.secret.clone(), true)
+            .unwrap();
+        assert!(webhook.accepts(TodoEventKind::TodoUpdated));
+        webhook
+            .update(webhook.url.clone(), vec![], webhook.secret.clone(), false)
+            .unwrap();
+        assert!(!webhook.accepts(TodoEventKind::TodoUpdated));
+    }
+
+    #[test]
+    fn test_delivery_attempts() {
+        let now = Utc::now();
+        let event = TodoEvent::new(
+            TodoEventKind::TodoCompleted,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            now,
+        );
+        let mut delivery = WebhookDelivery::new(Uuid::now_v7(), &event);
+        assert_eq!(delivery.payload["type"], "todo.completed");
+        assert_eq!(
+            delivery.payload["data"]["todo_id"],
+            event.todo_id.to_string()
+        );
+        assert_eq!(delivery.status(), DeliveryStatus::Pending);
+        delivery.lease(now + WEBHOOK_LEASE);
+        assert_eq!(delivery.status(), DeliveryStatus::Pending);
+        assert_eq!(delivery.next_attempt_at, Some(now + WEBHOOK_LEASE));
+
+        let attempt = delivery.record_attempt(Ok(503), now);
+        assert_eq!(attempt.status_code, Some(503));
+        assert!(attempt.error.is_some());
+        assert_eq!(delivery.next_attempt_at, Some(now + Duration::seconds(1)));
+        let attempt = delivery.record_attempt(Err("connection refused".into()), now);
+        assert_eq!(attempt.status_code, None);
+        assert_eq!(delivery.next_attempt_at, Some(now + Duration::seconds(2)));
+
+        delivery.record_attempt(Ok(204), now);
+        assert_eq!(delivery.status(), DeliveryStatus::Delivered);
+        assert_eq!(delivery.attempts, 3);
+        assert!(delivery.last_error.is_none());
+
+        // A replay starts over, up to the full number of attempts.
+        delivery.replay(now);
+        assert_eq!(delivery.status(), DeliveryStatus::Pending);
+        assert!(delivery.delivered_at.is_none());
+        for _ in 0..WEBHOOK_MAX_ATTEMPTS {
+            assert_eq!(delivery.status(), DeliveryStatus::Pending);
+            delivery.record_attempt(Ok(500), now);
+        }
+        assert_eq!(delivery.status(), DeliveryStatus::Failed);
+        assert_eq!(delivery.attempts, WEBHOOK_MAX_ATTEMPTS);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::domain::models::api_key::ApiKey;
+use crate::domain::repositories::conn::Conn;
+use crate::domain::repositories::errors::RepositoryError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Reads by id only see the keys of `user_id`; keys of other users are
+/// reported as not found.
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    /// Every key of the user, revoked and expired ones included, oldest first.
+    async fn find_all<C>(&self, conn: &C, user_id: Uuid) -> Result<Vec<ApiKey>, RepositoryError>
+    where
+        C: Conn;
+    async fn find_by_id<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<ApiKey, RepositoryError>
+    where
+        C: Conn;
+    /// Looks a key up by its public prefix, whoever it belongs to.
+    async fn find_by_prefix<C>(&self, conn: &C, prefix: &str) -> Result<ApiKey, RepositoryError>
+    where
+        C: Conn;
+    /// Fails with a conflict when the prefix is already taken.
+    async fn create<C>(&self, conn: &C, api_key: ApiKey) -> Result<ApiKey, RepositoryError>
+    where
+        C: Conn;
+    async fn update<C>(&self, conn: &C, api_key: ApiKey) -> Result<ApiKey, RepositoryError>
+    where
+        C: Conn;
+    /// Records a use of the key without touching anything else about it.
+    async fn touch<C>(
+        &self,
+        conn: &C,
+        id: Uuid,
+        used_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError>
+    where
+        C: Conn;
+}
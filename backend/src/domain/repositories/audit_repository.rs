@@ -0,0 +1,33 @@
+use crate::domain::models::audit::{AuditEvent, AuditQuery};
+use crate::domain::repositories::conn::Conn;
+use crate::domain::repositories::errors::RepositoryError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Events are append-only and listed oldest first, paged by id.
+#[async_trait]
+pub trait AuditRepository: Send + Sync {
+    async fn find_by_todo<C>(
+        &self,
+        conn: &C,
+        todo_id: Uuid,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<Vec<AuditEvent>, RepositoryError>
+    where
+        C: Conn;
+    /// Events on the todos of `owner_id` matching the query.
+    async fn find_by_query<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        query: AuditQuery,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<Vec<AuditEvent>, RepositoryError>
+    where
+        C: Conn;
+    async fn create<C>(&self, conn: &C, event: AuditEvent) -> Result<AuditEvent, RepositoryError>
+    where
+        C: Conn;
+}
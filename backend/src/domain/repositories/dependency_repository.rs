@@ -0,0 +1,46 @@
+use crate::domain::models::dependency::{Dependency, DependencyGraph};
+use crate::domain::models::todo::Todo;
+use crate::domain::repositories::conn::Conn;
+use crate::domain::repositories::errors::RepositoryError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Reads only return the todos visible to `user_id`, like the todo repository;
+/// edges to todos they cannot see are left out of the graph.
+#[async_trait]
+pub trait DependencyRepository: Send + Sync {
+    /// Direct blockers of the todo that are not in the trash, ordered by position.
+    async fn find_blockers<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        todo_id: Uuid,
+    ) -> Result<Vec<Todo>, RepositoryError>
+    where
+        C: Conn;
+    async fn find_graph<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        todo_id: Uuid,
+    ) -> Result<DependencyGraph, RepositoryError>
+    where
+        C: Conn;
+    /// Adding an existing edge is a no-op.
+    async fn create<C>(&self, conn: &C, dependency: Dependency) -> Result<(), RepositoryError>
+    where
+        C: Conn;
+    async fn delete<C>(
+        &self,
+        conn: &C,
+        todo_id: Uuid,
+        blocker_id: Uuid,
+    ) -> Result<(), RepositoryError>
+    where
+        C: Conn;
+    /// Serializes dependency writes until the surrounding transaction ends, so a
+    /// cycle check cannot race a concurrent insert.
+    async fn lock<C>(&self, conn: &C) -> Result<(), RepositoryError>
+    where
+        C: Conn;
+}
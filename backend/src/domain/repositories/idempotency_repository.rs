@@ -0,0 +1,42 @@
+use crate::domain::models::idempotency::IdempotencyRecord;
+use crate::domain::repositories::conn::Conn;
+use crate::domain::repositories::errors::RepositoryError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[async_trait]
+pub trait IdempotencyRepository: Send + Sync {
+    /// Stores `record` unless an unexpired record already holds its key, and
+    /// tells whether it did. Expired records are replaced.
+    async fn claim<C>(
+        &self,
+        conn: &C,
+        record: IdempotencyRecord,
+        now: DateTime<Utc>,
+    ) -> Result<bool, RepositoryError>
+    where
+        C: Conn;
+    async fn find<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        key: &str,
+    ) -> Result<IdempotencyRecord, RepositoryError>
+    where
+        C: Conn;
+    async fn update<C>(
+        &self,
+        conn: &C,
+        record: IdempotencyRecord,
+    ) -> Result<IdempotencyRecord, RepositoryError>
+    where
+        C: Conn;
+    async fn delete<C>(&self, conn: &C, user_id: Uuid, key: &str) -> Result<(), RepositoryError>
+    where
+        C: Conn;
+    /// Removes the records expired by `now` and returns how many there were.
+    async fn delete_expired<C>(&self, conn: &C, now: DateTime<Utc>) -> Result<u64, RepositoryError>
+    where
+        C: Conn;
+}
@@ -0,0 +1,30 @@
+use crate::domain::models::todo_event::{OutboxMessage, TodoEvent};
+use crate::domain::repositories::conn::Conn;
+use crate::domain::repositories::errors::RepositoryError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+#[async_trait]
+pub trait OutboxRepository: Send + Sync {
+    /// Queues the events for publication; called in the transaction that produced them.
+    async fn create<C>(&self, conn: &C, events: Vec<TodoEvent>) -> Result<(), RepositoryError>
+    where
+        C: Conn;
+    /// Unpublished messages due at `now`, oldest first. Inside a transaction the
+    /// rows stay locked until it ends and other dispatchers skip them.
+    async fn find_due<C>(
+        &self,
+        conn: &C,
+        now: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<OutboxMessage>, RepositoryError>
+    where
+        C: Conn;
+    async fn update<C>(
+        &self,
+        conn: &C,
+        message: OutboxMessage,
+    ) -> Result<OutboxMessage, RepositoryError>
+    where
+        C: Conn;
+}
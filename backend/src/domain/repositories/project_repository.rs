@@ -0,0 +1,41 @@
+use crate::domain::models::project::Project;
+use crate::domain::repositories::conn::Conn;
+use crate::domain::repositories::errors::RepositoryError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Projects are returned ordered by name.
+///
+/// Reads see every project visible to `user_id`: their own and those shared
+/// with them through a project grant. Writes only touch projects of the
+/// project's `owner_id`; projects a user cannot see are reported as not found.
+#[async_trait]
+pub trait ProjectRepository: Send + Sync {
+    /// `archived` keeps only archived (`Some(true)`) or active (`Some(false)`) projects.
+    async fn find_all<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        archived: Option<bool>,
+    ) -> Result<Vec<Project>, RepositoryError>
+    where
+        C: Conn;
+    async fn find_by_id<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Project, RepositoryError>
+    where
+        C: Conn;
+    async fn create<C>(&self, conn: &C, project: Project) -> Result<Project, RepositoryError>
+    where
+        C: Conn;
+    async fn update<C>(&self, conn: &C, project: Project) -> Result<Project, RepositoryError>
+    where
+        C: Conn;
+    /// Fails with `Conflict` while the project still holds todos, trashed ones included.
+    async fn delete<C>(&self, conn: &C, project: Project) -> Result<(), RepositoryError>
+    where
+        C: Conn;
+}
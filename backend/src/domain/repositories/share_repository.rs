@@ -0,0 +1,58 @@
+use crate::domain::models::share::{ShareGrant, ShareTarget};
+use crate::domain::models::todo::Todo;
+use crate::domain::repositories::conn::Conn;
+use crate::domain::repositories::errors::RepositoryError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Grants are looked up under the owner of the shared todos and their target;
+/// grants under another owner or target are reported as not found.
+#[async_trait]
+pub trait ShareRepository: Send + Sync {
+    /// Every grant on the target, oldest first.
+    async fn find_all<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        target: ShareTarget,
+    ) -> Result<Vec<ShareGrant>, RepositoryError>
+    where
+        C: Conn;
+    async fn find_by_id<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        target: ShareTarget,
+        id: Uuid,
+    ) -> Result<ShareGrant, RepositoryError>
+    where
+        C: Conn;
+    /// Grants of `user_id` covering the todo, directly or through its project.
+    async fn find_for_todo<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        todo: &Todo,
+    ) -> Result<Vec<ShareGrant>, RepositoryError>
+    where
+        C: Conn;
+    /// Every grant `user_id` holds, on anyone's todos.
+    async fn find_for_user<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+    ) -> Result<Vec<ShareGrant>, RepositoryError>
+    where
+        C: Conn;
+    /// Fails with a conflict when the user already holds a grant on the target,
+    /// and with not found when the user does not exist.
+    async fn create<C>(&self, conn: &C, grant: ShareGrant) -> Result<ShareGrant, RepositoryError>
+    where
+        C: Conn;
+    async fn update<C>(&self, conn: &C, grant: ShareGrant) -> Result<ShareGrant, RepositoryError>
+    where
+        C: Conn;
+    async fn delete<C>(&self, conn: &C, grant: ShareGrant) -> Result<(), RepositoryError>
+    where
+        C: Conn;
+}
@@ -0,0 +1,42 @@
+use crate::domain::models::tag::Tag;
+use crate::domain::repositories::conn::Conn;
+use crate::domain::repositories::errors::RepositoryError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Tags are returned ordered by name. Users only see their own tags; tags of
+/// other users are reported as not found.
+#[async_trait]
+pub trait TagRepository: Send + Sync {
+    async fn find_all<C>(&self, conn: &C, owner_id: Uuid) -> Result<Vec<Tag>, RepositoryError>
+    where
+        C: Conn;
+    async fn find_by_id<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        id: Uuid,
+    ) -> Result<Tag, RepositoryError>
+    where
+        C: Conn;
+    async fn find_by_todo<C>(&self, conn: &C, todo_id: Uuid) -> Result<Vec<Tag>, RepositoryError>
+    where
+        C: Conn;
+    async fn create<C>(&self, conn: &C, tag: Tag) -> Result<Tag, RepositoryError>
+    where
+        C: Conn;
+    async fn update<C>(&self, conn: &C, tag: Tag) -> Result<Tag, RepositoryError>
+    where
+        C: Conn;
+    /// Also detaches the tag from every todo carrying it.
+    async fn delete<C>(&self, conn: &C, tag: Tag) -> Result<(), RepositoryError>
+    where
+        C: Conn;
+    /// Attaching a tag that is already attached is a no-op.
+    async fn attach<C>(&self, conn: &C, todo_id: Uuid, tag_id: Uuid) -> Result<(), RepositoryError>
+    where
+        C: Conn;
+    async fn detach<C>(&self, conn: &C, todo_id: Uuid, tag_id: Uuid) -> Result<(), RepositoryError>
+    where
+        C: Conn;
+}
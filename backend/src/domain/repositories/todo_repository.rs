@@ -2,14 +2,113 @@ use crate::domain::models::todo::Todo;
 use crate::domain::repositories::conn::Conn;
 use crate::domain::repositories::errors::RepositoryError;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TodoSortKey {
+    #[default]
+    Position,
+    CreatedAt,
+    UpdatedAt,
+    Title,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TodoQuery {
+    pub completed: Option<bool>,
+    /// Case-insensitive substring matched against title and description.
+    pub text: Option<String>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    /// `Some(true)` keeps only open todos past their due date, `Some(false)` excludes them.
+    pub overdue: Option<bool>,
+    pub due_after: Option<DateTime<Utc>>,
+    pub due_before: Option<DateTime<Utc>>,
+    /// Keeps only todos carrying the tag with this exact name.
+    pub tag: Option<String>,
+    pub project_id: Option<Uuid>,
+    pub sort_key: TodoSortKey,
+    pub sort_order: SortOrder,
+}
+
+/// A full-text search match; a higher rank means a better match.
+#[derive(Debug, Clone)]
+pub struct TodoSearchHit {
+    pub todo: Todo,
+    pub rank: f32,
+    /// Matching excerpt of the title and description, terms wrapped in `<mark>`.
+    pub snippet: String,
+}
+
+/// Every `find_*` method except the `find_trashed*` ones skips todos in the trash.
+/// `find_all` returns todos in position order.
+///
+/// `find_all` and `find_max_position` only see the todos of `owner_id`. The
+/// other reads see every todo visible to `user_id`: their own and those shared
+/// with them. Writes only touch todos of the todo's `owner_id`; todos a user
+/// cannot see are reported as not found.
 #[async_trait]
 pub trait TodoRepository: Send + Sync {
-    async fn find_all<C>(&self, conn: &C) -> Result<Vec<Todo>, RepositoryError>
+    async fn find_all<C>(&self, conn: &C, owner_id: Uuid) -> Result<Vec<Todo>, RepositoryError>
     where
         C: Conn;
-    async fn find_by_id<C>(&self, conn: &C, id: Uuid) -> Result<Todo, RepositoryError>
+    async fn find_by_query<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        query: &TodoQuery,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<Vec<Todo>, RepositoryError>
+    where
+        C: Conn;
+    /// Ranked full-text search over title and description, best matches first.
+    /// `text` follows web search syntax: quoted phrases, `or` and `-` exclusions.
+    async fn search<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        text: &str,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<Vec<TodoSearchHit>, RepositoryError>
+    where
+        C: Conn;
+    async fn find_by_id<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Todo, RepositoryError>
+    where
+        C: Conn;
+    /// Highest position in use by the owner, trashed todos included, or 0 when there are none.
+    async fn find_max_position<C>(&self, conn: &C, owner_id: Uuid) -> Result<i64, RepositoryError>
+    where
+        C: Conn;
+    async fn find_trashed<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<Vec<Todo>, RepositoryError>
+    where
+        C: Conn;
+    async fn find_trashed_by_id<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Todo, RepositoryError>
     where
         C: Conn;
     async fn create<C>(&self, conn: &C, todo: Todo) -> Result<Todo, RepositoryError>
@@ -22,3 +121,278 @@ pub trait TodoRepository: Send + Sync {
     where
         C: Conn;
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::domain::models::share::{self, ShareGrant};
+    use std::sync::{Arc, Mutex};
+
+    /// In-memory `TodoRepository` for usecase tests. Like the real repository,
+    /// users see their own todos and those `grants` share with them.
+    #[derive(Clone, Default)]
+    pub struct MockTodoRepository {
+        pub todos: Arc<Mutex<Vec<Todo>>>,
+        pub grants: Arc<Mutex<Vec<ShareGrant>>>,
+    }
+
+    impl MockTodoRepository {
+        pub fn new(todos: Vec<Todo>) -> Self {
+            Self {
+                todos: Arc::new(Mutex::new(todos)),
+                grants: Arc::new(Mutex::new(vec![])),
+            }
+        }
+
+        pub fn is_visible(&self, todo: &Todo, user_id: Uuid) -> bool {
+            share::role_of(todo, user_id, &self.grants.lock().unwrap()).is_some()
+        }
+    }
+
+    #[async_trait]
+    impl TodoRepository for MockTodoRepository {
+        async fn find_all<C>(
+            &self,
+            _conn: &C,
+            owner_id: Uuid,
+        ) -> Result<Vec<Todo>, RepositoryError> {
+            let todos = self.todos.lock().unwrap();
+            let mut todos: Vec<Todo> = todos
+                .iter()
+                .filter(|t| t.owner_id == owner_id && !t.is_trashed())
+                .cloned()
+                .collect();
+            todos.sort_by_key(|t| (t.position, t.id));
+            Ok(todos)
+        }
+
+        async fn find_by_query<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            query: &TodoQuery,
+            after: Option<Uuid>,
+            limit: u64,
+        ) -> Result<Vec<Todo>, RepositoryError> {
+            let mut todos: Vec<Todo> = self
+                .todos
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|todo| self.is_visible(todo, user_id) && !todo.is_trashed())
+                .filter(|todo| query.completed.is_none_or(|c| todo.completed == c))
+                .filter(|todo| {
+                    query.text.as_ref().is_none_or(|text| {
+                        let text = text.to_lowercase();
+                        todo.title.to_lowercase().contains(&text)
+                            || todo
+                                .description
+                                .as_ref()
+                                .is_some_and(|d| d.to_lowercase().contains(&text))
+                    })
+                })
+                .filter(|todo| query.created_after.is_none_or(|at| todo.created_at > at))
+                .filter(|todo| query.created_before.is_none_or(|at| todo.created_at < at))
+                .filter(|todo| {
+                    query
+                        .overdue
+                        .is_none_or(|overdue| todo.is_overdue(Utc::now()) == overdue)
+                })
+                .filter(|todo| {
+                    query
+                        .due_after
+                        .is_none_or(|at| todo.due_at.is_some_and(|due_at| due_at > at))
+                })
+                // The mock knows nothing of tags.
+                .filter(|_| query.tag.is_none())
+                .filter(|todo| {
+                    query
+                        .project_id
+                        .is_none_or(|project_id| todo.project_id == Some(project_id))
+                })
+                .filter(|todo| {
+                    query
+                        .due_before
+                        .is_none_or(|at| todo.due_at.is_some_and(|due_at| due_at < at))
+                })
+                .cloned()
+                .collect();
+            match query.sort_key {
+                TodoSortKey::Position => todos.sort_by_key(|t| (t.position, t.id)),
+                TodoSortKey::CreatedAt => todos.sort_by_key(|t| (t.created_at, t.id)),
+                TodoSortKey::UpdatedAt => todos.sort_by_key(|t| (t.updated_at, t.id)),
+                TodoSortKey::Title => todos.sort_by(|a, b| (&a.title, a.id).cmp(&(&b.title, b.id))),
+            }
+            if query.sort_order == SortOrder::Desc {
+                todos.reverse();
+            }
+            if let Some(after) = after {
+                let index =
+                    todos
+                        .iter()
+                        .position(|t| t.id == after)
+                        .ok_or(RepositoryError::NotFound(format!(
+                            "Cursor todo with id {} not found",
+                            after
+                        )))?;
+                todos.drain(..=index);
+            }
+            todos.truncate(limit as usize);
+            Ok(todos)
+        }
+
+        async fn search<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            text: &str,
+            after: Option<Uuid>,
+            limit: u64,
+        ) -> Result<Vec<TodoSearchHit>, RepositoryError> {
+            // Substring matches, ranked by how often the text occurs.
+            let text = text.to_lowercase();
+            let mut hits: Vec<TodoSearchHit> = self
+                .todos
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|todo| self.is_visible(todo, user_id) && !todo.is_trashed())
+                .filter_map(|todo| {
+                    let document = format!(
+                        "{} {}",
+                        todo.title,
+                        todo.description.as_deref().unwrap_or_default()
+                    );
+                    let matches = document.to_lowercase().matches(&text).count();
+                    (matches > 0).then(|| TodoSearchHit {
+                        todo: todo.clone(),
+                        rank: matches as f32,
+                        snippet: document,
+                    })
+                })
+                .collect();
+            hits.sort_by(|a, b| {
+                (b.rank, b.todo.id)
+                    .partial_cmp(&(a.rank, a.todo.id))
+                    .unwrap()
+            });
+            if let Some(after) = after {
+                let index = hits.iter().position(|hit| hit.todo.id == after).ok_or(
+                    RepositoryError::NotFound(format!("Cursor todo with id {} not found", after)),
+                )?;
+                hits.drain(..=index);
+            }
+            hits.truncate(limit as usize);
+            Ok(hits)
+        }
+
+        async fn find_by_id<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            id: Uuid,
+        ) -> Result<Todo, RepositoryError> {
+            let todos = self.todos.lock().unwrap();
+            todos
+                .iter()
+                .find(|todo| self.is_visible(todo, user_id) && todo.id == id && !todo.is_trashed())
+                .cloned()
+                .ok_or(RepositoryError::NotFound(format!(
+                    "Todo with id {} not found",
+                    id
+                )))
+        }
+
+        async fn find_max_position<C>(
+            &self,
+            _conn: &C,
+            owner_id: Uuid,
+        ) -> Result<i64, RepositoryError> {
+            let todos = self.todos.lock().unwrap();
+            Ok(todos
+                .iter()
+                .filter(|t| t.owner_id == owner_id)
+                .map(|t| t.position)
+                .max()
+                .unwrap_or(0))
+        }
+
+        async fn find_trashed<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            after: Option<Uuid>,
+            limit: u64,
+        ) -> Result<Vec<Todo>, RepositoryError> {
+            let mut todos: Vec<Todo> = self
+                .todos
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|todo| self.is_visible(todo, user_id) && todo.is_trashed())
+                .filter(|todo| after.is_none_or(|after| todo.id > after))
+                .cloned()
+                .collect();
+            todos.sort_by_key(|todo| todo.id);
+            todos.truncate(limit as usize);
+            Ok(todos)
+        }
+
+        async fn find_trashed_by_id<C>(
+            &self,
+            _conn: &C,
+            user_id: Uuid,
+            id: Uuid,
+        ) -> Result<Todo, RepositoryError> {
+            let todos = self.todos.lock().unwrap();
+            todos
+                .iter()
+                .find(|todo| self.is_visible(todo, user_id) && todo.id == id && todo.is_trashed())
+                .cloned()
+                .ok_or(RepositoryError::NotFound(format!(
+                    "Trashed todo with id {} not found",
+                    id
+                )))
+        }
+
+        async fn create<C>(&self, _conn: &C, todo: Todo) -> Result<Todo, RepositoryError> {
+            self.todos.lock().unwrap().push(todo.clone());
+            Ok(todo)
+        }
+
+        async fn update<C>(&self, _conn: &C, mut todo: Todo) -> Result<Todo, RepositoryError> {
+            let target_id = todo.id;
+            let mut todos = self.todos.lock().unwrap();
+            let stored =
+                todos
+                    .iter_mut()
+                    .find(|t| t.id == todo.id)
+                    .ok_or(RepositoryError::NotFound(format!(
+                        "Todo with id {} not found",
+                        target_id
+                    )))?;
+            if stored.version != todo.version {
+                return Err(RepositoryError::Conflict(format!(
+                    "Todo with id {} was modified concurrently",
+                    target_id
+                )));
+            }
+            todo.version += 1;
+            *stored = todo.clone();
+            Ok(todo)
+        }
+
+        async fn delete<C>(&self, _conn: &C, todo: Todo) -> Result<(), RepositoryError> {
+            let mut todos = self.todos.lock().unwrap();
+            if let Some(index) = todos.iter().position(|t| t.id == todo.id) {
+                todos.remove(index);
+                Ok(())
+            } else {
+                Err(RepositoryError::NotFound(format!(
+                    "Todo with id {} not found",
+                    todo.id
+                )))
+            }
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use crate::domain::models::user::User;
+use crate::domain::repositories::conn::Conn;
+use crate::domain::repositories::errors::RepositoryError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait UserRepository: Send + Sync {
+    async fn find_by_id<C>(&self, conn: &C, id: Uuid) -> Result<User, RepositoryError>
+    where
+        C: Conn;
+    /// `email` must already be normalized.
+    async fn find_by_email<C>(&self, conn: &C, email: &str) -> Result<User, RepositoryError>
+    where
+        C: Conn;
+    /// Fails with a conflict when the email address is already registered.
+    async fn create<C>(&self, conn: &C, user: User) -> Result<User, RepositoryError>
+    where
+        C: Conn;
+}
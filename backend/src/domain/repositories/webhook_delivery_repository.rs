@@ -0,0 +1,69 @@
+use crate::domain::models::webhook::{DeliveryAttempt, WebhookDelivery};
+use crate::domain::repositories::conn::Conn;
+use crate::domain::repositories::errors::RepositoryError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Deliveries are listed oldest first, paged by id.
+#[async_trait]
+pub trait WebhookDeliveryRepository: Send + Sync {
+    async fn find_by_webhook<C>(
+        &self,
+        conn: &C,
+        webhook_id: Uuid,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<Vec<WebhookDelivery>, RepositoryError>
+    where
+        C: Conn;
+    async fn find_by_id<C>(
+        &self,
+        conn: &C,
+        webhook_id: Uuid,
+        id: Uuid,
+    ) -> Result<WebhookDelivery, RepositoryError>
+    where
+        C: Conn;
+    /// Deliveries due at `now`, oldest first. Inside a transaction the rows stay
+    /// locked until it ends and other dispatchers skip them.
+    async fn find_due<C>(
+        &self,
+        conn: &C,
+        now: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<WebhookDelivery>, RepositoryError>
+    where
+        C: Conn;
+    /// Skips deliveries of an event the webhook already has, so publishing an
+    /// event twice queues it once.
+    async fn create<C>(
+        &self,
+        conn: &C,
+        deliveries: Vec<WebhookDelivery>,
+    ) -> Result<(), RepositoryError>
+    where
+        C: Conn;
+    async fn update<C>(
+        &self,
+        conn: &C,
+        delivery: WebhookDelivery,
+    ) -> Result<WebhookDelivery, RepositoryError>
+    where
+        C: Conn;
+    /// Every attempt at the delivery, oldest first.
+    async fn find_attempts<C>(
+        &self,
+        conn: &C,
+        delivery_id: Uuid,
+    ) -> Result<Vec<DeliveryAttempt>, RepositoryError>
+    where
+        C: Conn;
+    async fn create_attempt<C>(
+        &self,
+        conn: &C,
+        attempt: DeliveryAttempt,
+    ) -> Result<DeliveryAttempt, RepositoryError>
+    where
+        C: Conn;
+}
@@ -0,0 +1,37 @@
+use crate::domain::models::webhook::Webhook;
+use crate::domain::repositories::conn::Conn;
+use crate::domain::repositories::errors::RepositoryError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Reads by id only see the webhooks of `owner_id`; webhooks of other users
+/// are reported as not found.
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    /// Every webhook of the owner, inactive ones included, oldest first.
+    async fn find_all<C>(&self, conn: &C, owner_id: Uuid) -> Result<Vec<Webhook>, RepositoryError>
+    where
+        C: Conn;
+    async fn find_by_id<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+        id: Uuid,
+    ) -> Result<Webhook, RepositoryError>
+    where
+        C: Conn;
+    /// The webhooks with the given ids, whoever owns them; unknown ids are skipped.
+    async fn find_by_ids<C>(&self, conn: &C, ids: &[Uuid]) -> Result<Vec<Webhook>, RepositoryError>
+    where
+        C: Conn;
+    async fn create<C>(&self, conn: &C, webhook: Webhook) -> Result<Webhook, RepositoryError>
+    where
+        C: Conn;
+    async fn update<C>(&self, conn: &C, webhook: Webhook) -> Result<Webhook, RepositoryError>
+    where
+        C: Conn;
+    /// Deletes the webhook together with its deliveries.
+    async fn delete<C>(&self, conn: &C, webhook: Webhook) -> Result<(), RepositoryError>
+    where
+        C: Conn;
+}
@@ -1,6 +1,27 @@
+use std::time::Duration;
+
 pub struct Config {
     pub database_url: String,
     pub server_port: u16,
+    pub jwt: JwtConfig,
+}
+
+/// Key material for signing and verifying JWTs.
+pub enum JwtKeys {
+    Hs256 {
+        secret: String,
+    },
+    /// PEM-encoded RSA keys.
+    Rs256 {
+        private_key: String,
+        public_key: String,
+    },
+}
+
+pub struct JwtConfig {
+    pub keys: JwtKeys,
+    pub access_ttl: Duration,
+    pub refresh_ttl: Duration,
 }
 
 impl Default for Config {
@@ -23,6 +44,51 @@ impl Default for Config {
         Config {
             database_url,
             server_port,
+            jwt: JwtConfig::from_env(),
+        }
+    }
+}
+
+impl JwtConfig {
+    /// `JWT_ALGORITHM` selects `HS256` (the default, keyed by `JWT_SECRET`) or
+    /// `RS256` (keyed by the PEM files `JWT_PRIVATE_KEY_FILE` and `JWT_PUBLIC_KEY_FILE`).
+    fn from_env() -> Self {
+        let algorithm = std::env::var("JWT_ALGORITHM").unwrap_or_else(|_| "HS256".into());
+        let keys = match algorithm.as_str() {
+            "HS256" => {
+                let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+                assert!(
+                    secret.len() >= 32,
+                    "JWT_SECRET must be at least 32 bytes long"
+                );
+                JwtKeys::Hs256 { secret }
+            }
+            "RS256" => {
+                let read = |var: &str| {
+                    let path = std::env::var(var).unwrap_or_else(|_| panic!("{} must be set", var));
+                    std::fs::read_to_string(&path)
+                        .unwrap_or_else(|err| panic!("{} could not be read: {}", var, err))
+                };
+                JwtKeys::Rs256 {
+                    private_key: read("JWT_PRIVATE_KEY_FILE"),
+                    public_key: read("JWT_PUBLIC_KEY_FILE"),
+                }
+            }
+            _ => panic!("JWT_ALGORITHM must be HS256 or RS256"),
+        };
+        let seconds = |var: &str, default: u64| {
+            std::env::var(var)
+                .map(|value| {
+                    value
+                        .parse()
+                        .unwrap_or_else(|_| panic!("{} must be a number of seconds", var))
+                })
+                .unwrap_or(default)
+        };
+        JwtConfig {
+            keys,
+            access_ttl: Duration::from_secs(seconds("JWT_ACCESS_TTL_SECS", 15 * 60)),
+            refresh_ttl: Duration::from_secs(seconds("JWT_REFRESH_TTL_SECS", 30 * 24 * 60 * 60)),
         }
     }
 }
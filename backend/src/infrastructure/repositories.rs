@@ -1,3 +1,14 @@
+pub mod api_key_repository;
+pub mod audit_repository;
 pub mod data_models;
+pub mod dependency_repository;
 pub mod errors;
+pub mod idempotency_repository;
+pub mod outbox_repository;
+pub mod project_repository;
+pub mod share_repository;
+pub mod tag_repository;
 pub mod todo_repository;
+pub mod user_repository;
+pub mod webhook_delivery_repository;
+pub mod webhook_repository;
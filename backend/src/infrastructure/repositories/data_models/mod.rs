@@ -2,4 +2,19 @@
 
 pub mod prelude;
 
+pub mod api_keys;
+pub mod audit_events;
+pub mod checklist_items;
+pub mod idempotency_keys;
+pub mod outbox;
+pub mod projects;
+pub mod sea_orm_active_enums;
+pub mod share_grants;
+pub mod tags;
+pub mod todo_dependencies;
+pub mod todo_tags;
 pub mod todos;
+pub mod users;
+pub mod webhook_deliveries;
+pub mod webhook_delivery_attempts;
+pub mod webhooks;
@@ -1,3 +1,17 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.14
 
+pub use super::api_keys::Entity as ApiKeys;
+pub use super::audit_events::Entity as AuditEvents;
+pub use super::checklist_items::Entity as ChecklistItems;
+pub use super::idempotency_keys::Entity as IdempotencyKeys;
+pub use super::outbox::Entity as Outbox;
+pub use super::projects::Entity as Projects;
+pub use super::share_grants::Entity as ShareGrants;
+pub use super::tags::Entity as Tags;
+pub use super::todo_dependencies::Entity as TodoDependencies;
+pub use super::todo_tags::Entity as TodoTags;
 pub use super::todos::Entity as Todos;
+pub use super::users::Entity as Users;
+pub use super::webhook_deliveries::Entity as WebhookDeliveries;
+pub use super::webhook_delivery_attempts::Entity as WebhookDeliveryAttempts;
+pub use super::webhooks::Entity as Webhooks;
@@ -1,5 +1,6 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.14
 
+use super::sea_orm_active_enums::TodoPriority;
 use sea_orm::entity::prelude::*;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
@@ -13,9 +14,80 @@ pub struct Model {
     pub completed: bool,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
+    pub version: i32,
+    pub deleted_at: Option<DateTimeWithTimeZone>,
+    pub due_at: Option<DateTimeWithTimeZone>,
+    pub remind_at: Option<DateTimeWithTimeZone>,
+    pub priority: TodoPriority,
+    pub position: i64,
+    pub project_id: Option<Uuid>,
+    pub recurrence: Option<String>,
+    pub owner_id: Uuid,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::checklist_items::Entity")]
+    ChecklistItems,
+    #[sea_orm(
+        belongs_to = "super::projects::Entity",
+        from = "Column::ProjectId",
+        to = "super::projects::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Projects,
+    #[sea_orm(has_many = "super::share_grants::Entity")]
+    ShareGrants,
+    #[sea_orm(has_many = "super::todo_tags::Entity")]
+    TodoTags,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::OwnerId",
+        to = "super::users::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Users,
+}
+
+impl Related<super::checklist_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ChecklistItems.def()
+    }
+}
+
+impl Related<super::projects::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Projects.def()
+    }
+}
+
+impl Related<super::share_grants::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ShareGrants.def()
+    }
+}
+
+impl Related<super::todo_tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TodoTags.def()
+    }
+}
+
+impl Related<super::users::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Users.def()
+    }
+}
+
+impl Related<super::tags::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::todo_tags::Relation::Tags.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::todo_tags::Relation::Todos.def().rev())
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -1,10 +1,28 @@
-use crate::domain::repositories::todo_repository::TodoRepository;
-use crate::domain::{models::todo::Todo, repositories::conn::Conn};
-use crate::infrastructure::repositories::data_models::prelude::Todos as TodoTable;
-use crate::infrastructure::repositories::data_models::todos;
+use crate::domain::repositories::todo_repository::{
+    SortOrder, TodoQuery, TodoRepository, TodoSearchHit, TodoSortKey,
+};
+use crate::domain::{
+    models::{
+        checklist_item::ChecklistItem,
+        todo::{Priority, Todo},
+    },
+    repositories::conn::Conn,
+};
+use crate::infrastructure::repositories::data_models::prelude::{
+    ChecklistItems, ShareGrants, TodoTags, Todos as TodoTable,
+};
+use crate::infrastructure::repositories::data_models::sea_orm_active_enums::TodoPriority;
+use crate::infrastructure::repositories::data_models::{
+    checklist_items, share_grants, tags, todo_tags, todos,
+};
 use async_trait::async_trait;
 use chrono::Utc;
-use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait};
+use sea_orm::sea_query::{Expr, OnConflict, extension::postgres::PgExpr};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, DbBackend, DbErr, EntityTrait,
+    FromQueryResult, LoaderTrait, ModelTrait, Order, QueryFilter, QueryOrder, QuerySelect,
+    QueryTrait, Statement,
+};
 use uuid::Uuid;
 
 impl From<todos::Model> for Todo {
@@ -14,10 +32,100 @@ impl From<todos::Model> for Todo {
             title: model.title,
             description: model.description,
             completed: model.completed,
+            created_at: model.created_at.with_timezone(&Utc),
+            updated_at: model.updated_at.with_timezone(&Utc),
+            version: model.version,
+            deleted_at: model.deleted_at.map(|at| at.with_timezone(&Utc)),
+            due_at: model.due_at.map(|at| at.with_timezone(&Utc)),
+            remind_at: model.remind_at.map(|at| at.with_timezone(&Utc)),
+            priority: model.priority.into(),
+            position: model.position,
+            items: Vec::new(),
+            project_id: model.project_id,
+            // Rules are validated before they are stored.
+            recurrence: model.recurrence.and_then(|rule| rule.parse().ok()),
+            owner_id: model.owner_id,
+            events: Vec::new(),
         }
     }
 }
 
+impl From<checklist_items::Model> for ChecklistItem {
+    fn from(model: checklist_items::Model) -> Self {
+        ChecklistItem {
+            id: model.id,
+            title: model.title,
+            completed: model.completed,
+            position: model.position,
+            created_at: model.created_at.with_timezone(&Utc),
+            updated_at: model.updated_at.with_timezone(&Utc),
+        }
+    }
+}
+
+fn item_active_model(todo_id: Uuid, item: ChecklistItem) -> checklist_items::ActiveModel {
+    checklist_items::ActiveModel {
+        id: Set(item.id),
+        todo_id: Set(todo_id),
+        title: Set(item.title),
+        completed: Set(item.completed),
+        position: Set(item.position),
+        created_at: Set(item.created_at.fixed_offset()),
+        updated_at: Set(item.updated_at.fixed_offset()),
+    }
+}
+
+/// Converts todo rows into aggregates, loading their checklist items in one query.
+pub(crate) async fn with_items<C>(conn: &C, models: Vec<todos::Model>) -> Result<Vec<Todo>, DbErr>
+where
+    C: Conn,
+{
+    let items = models.load_many(ChecklistItems, conn).await?;
+    Ok(models
+        .into_iter()
+        .zip(items)
+        .map(|(model, items)| {
+            let mut todo = Todo::from(model);
+            todo.items = items.into_iter().map(ChecklistItem::from).collect();
+            todo.items.sort_by_key(|item| item.position);
+            todo
+        })
+        .collect())
+}
+
+/// Makes the stored checklist of a todo match `items`.
+async fn save_items<C>(conn: &C, todo_id: Uuid, items: Vec<ChecklistItem>) -> Result<(), DbErr>
+where
+    C: Conn,
+{
+    ChecklistItems::delete_many()
+        .filter(checklist_items::Column::TodoId.eq(todo_id))
+        .filter(checklist_items::Column::Id.is_not_in(items.iter().map(|item| item.id)))
+        .exec(conn)
+        .await?;
+    if items.is_empty() {
+        return Ok(());
+    }
+    ChecklistItems::insert_many(
+        items
+            .into_iter()
+            .map(|item| item_active_model(todo_id, item)),
+    )
+    .on_conflict(
+        OnConflict::column(checklist_items::Column::Id)
+            .update_columns([
+                checklist_items::Column::Title,
+                checklist_items::Column::Completed,
+                checklist_items::Column::Position,
+                checklist_items::Column::UpdatedAt,
+            ])
+            .to_owned(),
+    )
+    .exec(conn)
+    .await?;
+    Ok(())
+}
+
 impl From<Todo> for todos::ActiveModel {
     fn from(todo: Todo) -> Self {
         todos::ActiveModel {
@@ -25,12 +133,147 @@ impl From<Todo> for todos::ActiveModel {
             title: Set(todo.title),
             description: Set(todo.description),
             completed: Set(todo.completed),
-            created_at: Set(Utc::now().fixed_offset()),
-            updated_at: Set(Utc::now().fixed_offset()),
+            created_at: Set(todo.created_at.fixed_offset()),
+            updated_at: Set(todo.updated_at.fixed_offset()),
+            version: Set(todo.version),
+            deleted_at: Set(todo.deleted_at.map(|at| at.fixed_offset())),
+            due_at: Set(todo.due_at.map(|at| at.fixed_offset())),
+            remind_at: Set(todo.remind_at.map(|at| at.fixed_offset())),
+            priority: Set(todo.priority.into()),
+            position: Set(todo.position),
+            project_id: Set(todo.project_id),
+            recurrence: Set(todo.recurrence.map(|rule| rule.to_string())),
+            owner_id: Set(todo.owner_id),
         }
     }
 }
 
+impl From<TodoPriority> for Priority {
+    fn from(priority: TodoPriority) -> Self {
+        match priority {
+            TodoPriority::Low => Priority::Low,
+            TodoPriority::Medium => Priority::Medium,
+            TodoPriority::High => Priority::High,
+            TodoPriority::Urgent => Priority::Urgent,
+        }
+    }
+}
+
+impl From<Priority> for TodoPriority {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::Low => TodoPriority::Low,
+            Priority::Medium => TodoPriority::Medium,
+            Priority::High => TodoPriority::High,
+            Priority::Urgent => TodoPriority::Urgent,
+        }
+    }
+}
+
+impl From<TodoSortKey> for todos::Column {
+    fn from(key: TodoSortKey) -> Self {
+        match key {
+            TodoSortKey::Position => todos::Column::Position,
+            TodoSortKey::CreatedAt => todos::Column::CreatedAt,
+            TodoSortKey::UpdatedAt => todos::Column::UpdatedAt,
+            TodoSortKey::Title => todos::Column::Title,
+        }
+    }
+}
+
+impl From<SortOrder> for Order {
+    fn from(order: SortOrder) -> Self {
+        match order {
+            SortOrder::Asc => Order::Asc,
+            SortOrder::Desc => Order::Desc,
+        }
+    }
+}
+
+// Backslash is PostgreSQL's default LIKE escape character.
+fn contains_pattern(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// Ranks the matches of `$1` among the todos visible to `$2` and pages through them by
+/// (rank, id), both descending, starting after the cursor `($3, $4)` when
+/// given. Snippets are only built for the rows of the page.
+const SEARCH_SQL: &str = "WITH query AS (SELECT websearch_to_tsquery('english', $1) AS q), \
+    page AS ( \
+        SELECT t.id, t.title, t.description, ts_rank(t.search_vector, query.q) AS rank \
+        FROM todos t, query \
+        WHERE (t.owner_id = $2 \
+            OR t.id IN (SELECT todo_id FROM share_grants WHERE user_id = $2) \
+            OR (t.owner_id, t.project_id) IN (SELECT owner_id, project_id FROM share_grants WHERE user_id = $2)) \
+          AND t.deleted_at IS NULL AND t.search_vector @@ query.q \
+          AND ($3::real IS NULL OR (ts_rank(t.search_vector, query.q), t.id) < ($3::real, $4::uuid)) \
+        ORDER BY rank DESC, t.id DESC \
+        LIMIT $5 \
+    ) \
+    SELECT page.id, page.rank, \
+        ts_headline('english', concat_ws(' ', page.title, page.description), query.q, \
+            'StartSel=<mark>, StopSel=</mark>, MaxFragments=2, MaxWords=20, MinWords=5') AS snippet \
+    FROM page, query \
+    ORDER BY page.rank DESC, page.id DESC";
+
+/// Rank of the cursor todo for the same query, or no row when it does not match.
+const SEARCH_CURSOR_SQL: &str = "SELECT id, ts_rank(search_vector, websearch_to_tsquery('english', $1)) AS rank \
+    FROM todos t \
+    WHERE t.id = $2 AND t.deleted_at IS NULL \
+      AND (t.owner_id = $3 \
+        OR t.id IN (SELECT todo_id FROM share_grants WHERE user_id = $3) \
+        OR (t.owner_id, t.project_id) IN (SELECT owner_id, project_id FROM share_grants WHERE user_id = $3)) \
+      AND search_vector @@ websearch_to_tsquery('english', $1)";
+
+/// Todos owned by `user_id` or shared with them, directly or through a project
+/// grant of the todo's owner.
+pub(crate) fn visible_to(user_id: Uuid) -> Condition {
+    let grants = || {
+        ShareGrants::find()
+            .select_only()
+            .filter(share_grants::Column::UserId.eq(user_id))
+    };
+    Condition::any()
+        .add(todos::Column::OwnerId.eq(user_id))
+        .add(
+            todos::Column::Id.in_subquery(
+                grants()
+                    .column(share_grants::Column::TodoId)
+                    .filter(share_grants::Column::TodoId.is_not_null())
+                    .into_query(),
+            ),
+        )
+        .add(
+            Expr::tuple([
+                Expr::col(todos::Column::OwnerId).into(),
+                Expr::col(todos::Column::ProjectId).into(),
+            ])
+            .in_subquery(
+                grants()
+                    .column(share_grants::Column::OwnerId)
+                    .column(share_grants::Column::ProjectId)
+                    .filter(share_grants::Column::ProjectId.is_not_null())
+                    .into_query(),
+            ),
+        )
+}
+
+#[derive(FromQueryResult)]
+struct SearchRow {
+    id: Uuid,
+    rank: f32,
+    snippet: String,
+}
+
+#[derive(FromQueryResult)]
+struct SearchCursorRow {
+    rank: f32,
+}
+
 #[derive(Clone)]
 pub struct TodoRepositoryImpl {}
 
@@ -51,25 +294,206 @@ impl TodoRepository for TodoRepositoryImpl {
     async fn find_all<C>(
         &self,
         conn: &C,
+        owner_id: Uuid,
+    ) -> Result<Vec<Todo>, crate::domain::repositories::errors::RepositoryError>
+    where
+        C: Conn,
+    {
+        let todos = TodoTable::find()
+            .filter(todos::Column::OwnerId.eq(owner_id))
+            .filter(todos::Column::DeletedAt.is_null())
+            .order_by_asc(todos::Column::Position)
+            .order_by_asc(todos::Column::Id)
+            .all(conn)
+            .await?;
+        Ok(with_items(conn, todos).await?)
+    }
+
+    async fn find_by_query<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        query: &TodoQuery,
+        after: Option<Uuid>,
+        limit: u64,
     ) -> Result<Vec<Todo>, crate::domain::repositories::errors::RepositoryError>
     where
         C: Conn,
     {
-        let todos = TodoTable::find().all(conn).await?;
-        Ok(todos.into_iter().map(Todo::from).collect())
+        let sort_column = todos::Column::from(query.sort_key);
+        let mut condition = Condition::all()
+            .add(visible_to(user_id))
+            .add(todos::Column::DeletedAt.is_null());
+        if let Some(completed) = query.completed {
+            condition = condition.add(todos::Column::Completed.eq(completed));
+        }
+        if let Some(text) = &query.text {
+            condition = condition.add(
+                Condition::any()
+                    .add(Expr::col(todos::Column::Title).ilike(contains_pattern(text)))
+                    .add(Expr::col(todos::Column::Description).ilike(contains_pattern(text))),
+            );
+        }
+        if let Some(created_after) = query.created_after {
+            condition = condition.add(todos::Column::CreatedAt.gt(created_after));
+        }
+        if let Some(created_before) = query.created_before {
+            condition = condition.add(todos::Column::CreatedAt.lt(created_before));
+        }
+        if let Some(overdue) = query.overdue {
+            let now = Utc::now();
+            condition = condition.add(if overdue {
+                Condition::all()
+                    .add(todos::Column::Completed.eq(false))
+                    .add(todos::Column::DueAt.lt(now))
+            } else {
+                Condition::any()
+                    .add(todos::Column::Completed.eq(true))
+                    .add(todos::Column::DueAt.is_null())
+                    .add(todos::Column::DueAt.gte(now))
+            });
+        }
+        if let Some(due_after) = query.due_after {
+            condition = condition.add(todos::Column::DueAt.gt(due_after));
+        }
+        if let Some(due_before) = query.due_before {
+            condition = condition.add(todos::Column::DueAt.lt(due_before));
+        }
+        if let Some(project_id) = query.project_id {
+            condition = condition.add(todos::Column::ProjectId.eq(project_id));
+        }
+        if let Some(tag) = &query.tag {
+            condition = condition.add(
+                todos::Column::Id.in_subquery(
+                    TodoTags::find()
+                        .select_only()
+                        .column(todo_tags::Column::TodoId)
+                        .inner_join(tags::Entity)
+                        .filter(tags::Column::Name.eq(tag))
+                        // Names are unique per owner; only the todo owner's tag counts.
+                        .filter(
+                            Expr::col((tags::Entity, tags::Column::OwnerId))
+                                .equals((todos::Entity, todos::Column::OwnerId)),
+                        )
+                        .into_query(),
+                ),
+            );
+        }
+        if let Some(after) = after {
+            // Keyset on (sort column, id): the id breaks ties between equal sort values.
+            let cursor = TodoTable::find_by_id(after)
+                .filter(visible_to(user_id))
+                .one(conn)
+                .await?
+                .ok_or(
+                    crate::domain::repositories::errors::RepositoryError::NotFound(format!(
+                        "Cursor todo with id {} not found",
+                        after
+                    )),
+                )?;
+            let value = cursor.get(sort_column);
+            condition = condition.add(match query.sort_order {
+                SortOrder::Asc => Condition::any().add(sort_column.gt(value.clone())).add(
+                    Condition::all()
+                        .add(sort_column.eq(value))
+                        .add(todos::Column::Id.gt(after)),
+                ),
+                SortOrder::Desc => Condition::any().add(sort_column.lt(value.clone())).add(
+                    Condition::all()
+                        .add(sort_column.eq(value))
+                        .add(todos::Column::Id.lt(after)),
+                ),
+            });
+        }
+        let todos = TodoTable::find()
+            .filter(condition)
+            .order_by(sort_column, Order::from(query.sort_order))
+            .order_by(todos::Column::Id, Order::from(query.sort_order))
+            .limit(limit)
+            .all(conn)
+            .await?;
+        Ok(with_items(conn, todos).await?)
+    }
+
+    async fn search<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        text: &str,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<Vec<TodoSearchHit>, crate::domain::repositories::errors::RepositoryError>
+    where
+        C: Conn,
+    {
+        let cursor_rank = match after {
+            Some(after) => {
+                let cursor = SearchCursorRow::find_by_statement(Statement::from_sql_and_values(
+                    DbBackend::Postgres,
+                    SEARCH_CURSOR_SQL,
+                    [text.into(), after.into(), user_id.into()],
+                ))
+                .one(conn)
+                .await?
+                .ok_or(
+                    crate::domain::repositories::errors::RepositoryError::NotFound(format!(
+                        "Cursor todo with id {} not found",
+                        after
+                    )),
+                )?;
+                Some(cursor.rank)
+            }
+            None => None,
+        };
+        let rows = SearchRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            SEARCH_SQL,
+            [
+                text.into(),
+                user_id.into(),
+                cursor_rank.into(),
+                after.into(),
+                (limit as i64).into(),
+            ],
+        ))
+        .all(conn)
+        .await?;
+
+        let models = TodoTable::find()
+            .filter(visible_to(user_id))
+            .filter(todos::Column::Id.is_in(rows.iter().map(|row| row.id)))
+            .all(conn)
+            .await?;
+        let mut todos = with_items(conn, models).await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let index = todos.iter().position(|todo| todo.id == row.id)?;
+                Some(TodoSearchHit {
+                    todo: todos.swap_remove(index),
+                    rank: row.rank,
+                    snippet: row.snippet,
+                })
+            })
+            .collect())
     }
 
     async fn find_by_id<C>(
         &self,
         conn: &C,
+        user_id: Uuid,
         id: Uuid,
     ) -> Result<Todo, crate::domain::repositories::errors::RepositoryError>
     where
         C: Conn,
     {
-        let todo = TodoTable::find_by_id(id).one(conn).await?;
+        let todo = TodoTable::find_by_id(id)
+            .filter(visible_to(user_id))
+            .filter(todos::Column::DeletedAt.is_null())
+            .one(conn)
+            .await?;
         match todo {
-            Some(todo) => Ok(Todo::from(todo)),
+            Some(todo) => Ok(with_items(conn, vec![todo]).await?.remove(0)),
             None => Err(
                 crate::domain::repositories::errors::RepositoryError::NotFound(format!(
                     "Todo with id {} not found",
@@ -79,6 +503,73 @@ impl TodoRepository for TodoRepositoryImpl {
         }
     }
 
+    async fn find_max_position<C>(
+        &self,
+        conn: &C,
+        owner_id: Uuid,
+    ) -> Result<i64, crate::domain::repositories::errors::RepositoryError>
+    where
+        C: Conn,
+    {
+        let position: Option<i64> = TodoTable::find()
+            .filter(todos::Column::OwnerId.eq(owner_id))
+            .select_only()
+            .column_as(todos::Column::Position.max(), "position")
+            .into_tuple()
+            .one(conn)
+            .await?
+            .flatten();
+        Ok(position.unwrap_or(0))
+    }
+
+    async fn find_trashed<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> Result<Vec<Todo>, crate::domain::repositories::errors::RepositoryError>
+    where
+        C: Conn,
+    {
+        let todos = TodoTable::find()
+            .filter(visible_to(user_id))
+            .filter(todos::Column::DeletedAt.is_not_null())
+            .apply_if(after, |query, after| {
+                query.filter(todos::Column::Id.gt(after))
+            })
+            .order_by_asc(todos::Column::Id)
+            .limit(limit)
+            .all(conn)
+            .await?;
+        Ok(with_items(conn, todos).await?)
+    }
+
+    async fn find_trashed_by_id<C>(
+        &self,
+        conn: &C,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<Todo, crate::domain::repositories::errors::RepositoryError>
+    where
+        C: Conn,
+    {
+        let todo = TodoTable::find_by_id(id)
+            .filter(visible_to(user_id))
+            .filter(todos::Column::DeletedAt.is_not_null())
+            .one(conn)
+            .await?;
+        match todo {
+            Some(todo) => Ok(with_items(conn, vec![todo]).await?.remove(0)),
+            None => Err(
+                crate::domain::repositories::errors::RepositoryError::NotFound(format!(
+                    "Trashed todo with id {} not found",
+                    id
+                )),
+            ),
+        }
+    }
+
     async fn create<C>(
         &self,
         conn: &C,
@@ -87,9 +578,13 @@ impl TodoRepository for TodoRepositoryImpl {
     where
         C: Conn,
     {
+        let items = todo.items.clone();
         let todo: todos::ActiveModel = todo.into();
         let todo: todos::Model = todo.insert(conn).await?;
-        Ok(todo.into())
+        save_items(conn, todo.id, items.clone()).await?;
+        let mut todo = Todo::from(todo);
+        todo.items = items;
+        Ok(todo)
     }
 
     async fn update<C>(
@@ -100,9 +595,30 @@ impl TodoRepository for TodoRepositoryImpl {
     where
         C: Conn,
     {
-        let todo: todos::ActiveModel = todo.into();
-        let todo: todos::Model = todo.update(conn).await?;
-        Ok(Todo::from(todo))
+        // Only write if nobody else has bumped the version since the todo was read.
+        let (id, owner_id, version) = (todo.id, todo.owner_id, todo.version);
+        let items = todo.items.clone();
+        let mut todo: todos::ActiveModel = todo.into();
+        todo.version = Set(version + 1);
+        let todo = TodoTable::update_many()
+            .set(todo)
+            .filter(todos::Column::Id.eq(id))
+            .filter(todos::Column::OwnerId.eq(owner_id))
+            .filter(todos::Column::Version.eq(version))
+            .exec_with_returning(conn)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(
+                crate::domain::repositories::errors::RepositoryError::Conflict(format!(
+                    "Todo with id {} was modified concurrently",
+                    id
+                )),
+            )?;
+        save_items(conn, id, items.clone()).await?;
+        let mut todo = Todo::from(todo);
+        todo.items = items;
+        Ok(todo)
     }
 
     async fn delete<C>(
@@ -113,8 +629,20 @@ impl TodoRepository for TodoRepositoryImpl {
     where
         C: Conn,
     {
-        let todo: todos::ActiveModel = todo.into();
-        todo.delete(conn).await?;
+        let result = TodoTable::delete_many()
+            .filter(todos::Column::Id.eq(todo.id))
+            .filter(todos::Column::OwnerId.eq(todo.owner_id))
+            .filter(todos::Column::Version.eq(todo.version))
+            .exec(conn)
+            .await?;
+        if result.rows_affected == 0 {
+            return Err(
+                crate::domain::repositories::errors::RepositoryError::Conflict(format!(
+                    "Todo with id {} was modified concurrently",
+                    todo.id
+                )),
+            );
+        }
         Ok(())
     }
 }
@@ -122,6 +650,9 @@ impl TodoRepository for TodoRepositoryImpl {
 #[cfg(all(test, feature = "db-tests"))]
 mod tests {
     use super::*;
+    use crate::domain::models::user::User;
+    use crate::domain::repositories::user_repository::UserRepository;
+    use crate::infrastructure::repositories::user_repository::UserRepositoryImpl;
     use migration::MigratorTrait;
     use sea_orm::{ConnectOptions, Database};
     use testcontainers::{ImageExt, runners::AsyncRunner};
@@ -140,32 +671,207 @@ mod tests {
         migration::Migrator::up(&conn, None)
             .await
             .expect("create migrator");
+        let owner = UserRepositoryImpl::new()
+            .create(
+                &conn,
+                User::new("owner@example.com", "hash".into()).unwrap(),
+            )
+            .await
+            .unwrap();
 
         let repo = TodoRepositoryImpl::new();
 
         // Test create
-        let todo = Todo::new("Test Todo".into(), None);
+        let todo = Todo::new(owner.id, "Test Todo".into(), None);
         let created_todo = repo.create(&conn, todo).await.unwrap();
         assert_eq!(created_todo.title, "Test Todo");
 
         // Test find_all
-        let todos = repo.find_all(&conn).await.unwrap();
+        let todos = repo.find_all(&conn, owner.id).await.unwrap();
         assert!(!todos.is_empty());
 
+        // Test find_max_position
+        assert_eq!(repo.find_max_position(&conn, owner.id).await.unwrap(), 0);
+
+        // Test find_by_query pagination
+        let mut second_todo = Todo::new(owner.id, "Second Todo".into(), None);
+        second_todo.reposition(1);
+        let second_todo = repo.create(&conn, second_todo).await.unwrap();
+        assert_eq!(repo.find_max_position(&conn, owner.id).await.unwrap(), 1);
+        let query = TodoQuery::default();
+        let page = repo
+            .find_by_query(&conn, owner.id, &query, None, 1)
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].id, created_todo.id);
+        let page = repo
+            .find_by_query(&conn, owner.id, &query, Some(created_todo.id), 10)
+            .await
+            .unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].id, second_todo.id);
+
+        // Test find_by_query due date filters
+        let mut overdue_todo = Todo::new(owner.id, "Overdue Todo".into(), None);
+        overdue_todo
+            .reschedule(Some(Utc::now() - chrono::Duration::days(1)), None, None)
+            .unwrap();
+        let overdue_todo = repo.create(&conn, overdue_todo).await.unwrap();
+        let query = TodoQuery {
+            overdue: Some(true),
+            ..TodoQuery::default()
+        };
+        let found = repo
+            .find_by_query(&conn, owner.id, &query, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, overdue_todo.id);
+        let query = TodoQuery {
+            overdue: Some(false),
+            ..TodoQuery::default()
+        };
+        let found = repo
+            .find_by_query(&conn, owner.id, &query, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 2);
+        repo.delete(&conn, overdue_todo).await.unwrap();
+
+        // Test find_by_query filtering and sorting
+        let query = TodoQuery {
+            text: Some("second".into()),
+            ..TodoQuery::default()
+        };
+        let found = repo
+            .find_by_query(&conn, owner.id, &query, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, second_todo.id);
+        let query = TodoQuery {
+            sort_key: TodoSortKey::Title,
+            sort_order: SortOrder::Desc,
+            ..TodoQuery::default()
+        };
+        let sorted = repo
+            .find_by_query(&conn, owner.id, &query, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(sorted[0].id, created_todo.id);
+        assert_eq!(sorted[1].id, second_todo.id);
+
+        // Test search
+        let hits = repo
+            .search(&conn, owner.id, "second", None, 10)
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].todo.id, second_todo.id);
+        assert!(hits[0].rank > 0.0);
+        assert!(hits[0].snippet.contains("<mark>Second</mark>"));
+        let first_page = repo.search(&conn, owner.id, "todo", None, 1).await.unwrap();
+        assert_eq!(first_page.len(), 1);
+        let second_page = repo
+            .search(&conn, owner.id, "todo", Some(first_page[0].todo.id), 10)
+            .await
+            .unwrap();
+        assert_eq!(second_page.len(), 1);
+        assert_ne!(second_page[0].todo.id, first_page[0].todo.id);
+        let hits = repo
+            .search(&conn, owner.id, "todo -second", None, 10)
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].todo.id, created_todo.id);
+
         // Test find_by_id
-        let found_todo = repo.find_by_id(&conn, created_todo.id).await.unwrap();
+        let found_todo = repo
+            .find_by_id(&conn, owner.id, created_todo.id)
+            .await
+            .unwrap();
         assert_eq!(found_todo.title, "Test Todo");
 
+        // Test todos are invisible to other owners
+        let other_owner = UserRepositoryImpl::new()
+            .create(
+                &conn,
+                User::new("other@example.com", "hash".into()).unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(
+            repo.find_by_id(&conn, other_owner.id, created_todo.id)
+                .await
+                .is_err()
+        );
+        assert!(
+            repo.find_all(&conn, other_owner.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
         // Test update
-        let mut updated_todo = created_todo;
-        updated_todo.title = "Updated Todo".into();
+        let mut updated_todo = created_todo.clone();
+        updated_todo.update("Updated Todo".into(), None);
         let updated_result = repo.update(&conn, updated_todo).await.unwrap();
         assert_eq!(updated_result.title, "Updated Todo");
+        assert_eq!(
+            updated_result.created_at.timestamp_micros(),
+            created_todo.created_at.timestamp_micros()
+        );
+        assert!(updated_result.updated_at > created_todo.updated_at);
+        assert_eq!(updated_result.version, created_todo.version + 1);
+
+        // Test checklist items are saved with the todo
+        let mut checklist_todo = updated_result.clone();
+        let first_item = checklist_todo.add_item("First step".into());
+        checklist_todo.add_item("Second step".into());
+        let checklist_todo = repo.update(&conn, checklist_todo).await.unwrap();
+        let mut found_todo = repo
+            .find_by_id(&conn, owner.id, checklist_todo.id)
+            .await
+            .unwrap();
+        assert_eq!(found_todo.items.len(), 2);
+        assert_eq!(found_todo.items[0].id, first_item);
+        found_todo.remove_item(first_item).unwrap();
+        let updated_result = repo.update(&conn, found_todo).await.unwrap();
+        let found_todo = repo
+            .find_by_id(&conn, owner.id, updated_result.id)
+            .await
+            .unwrap();
+        assert_eq!(found_todo.items.len(), 1);
+        assert_eq!(found_todo.items[0].title, "Second step");
+
+        // Test update with a stale version
+        let stale_result = repo.update(&conn, created_todo).await;
+        assert!(matches!(
+            stale_result,
+            Err(crate::domain::repositories::errors::RepositoryError::Conflict(_))
+        ));
+
+        // Test trash
+        let mut trashed_todo = updated_result;
+        trashed_todo.trash().unwrap();
+        let trashed_todo = repo.update(&conn, trashed_todo).await.unwrap();
+        assert!(
+            repo.find_by_id(&conn, owner.id, trashed_todo.id)
+                .await
+                .is_err()
+        );
+        let trash = repo.find_trashed(&conn, owner.id, None, 10).await.unwrap();
+        assert_eq!(trash.len(), 1);
+        let updated_result = repo
+            .find_trashed_by_id(&conn, owner.id, trashed_todo.id)
+            .await
+            .unwrap();
 
         // Test delete
         let target_id = updated_result.id;
         repo.delete(&conn, updated_result).await.unwrap();
-        let deleted_todo = repo.find_by_id(&conn, target_id).await;
+        let deleted_todo = repo.find_trashed_by_id(&conn, owner.id, target_id).await;
         assert!(deleted_todo.is_err());
     }
 }
@@ -1 +1,7 @@
+pub mod api_key_service;
+pub mod change_listener;
+pub mod event_hub;
+pub mod password_service;
+pub mod token_service;
 pub mod transaction_service;
+pub mod webhook_sender;
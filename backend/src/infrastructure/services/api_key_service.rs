@@ -0,0 +1,105 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+
+use crate::application_service::service::api_key_service::{ApiKeyService, GeneratedApiKey};
+
+const KEY_PREFIX: &str = "tdk_";
+const PREFIX_BYTES: usize = 6;
+const SECRET_BYTES: usize = 32;
+
+/// Keys look like `tdk_<12 hex prefix>_<64 hex secret>`. They carry 256 bits
+/// of randomness, so a single SHA-256 is enough to store them; a slow password
+/// hash would only make every API request slow.
+pub struct Sha256ApiKeyService;
+
+impl Sha256ApiKeyService {
+    pub fn new() -> Self {
+        Sha256ApiKeyService {}
+    }
+}
+
+impl Default for Sha256ApiKeyService {
+    fn default() -> Self {
+        Sha256ApiKeyService::new()
+    }
+}
+
+fn random_hex(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    OsRng.fill_bytes(&mut bytes);
+    to_hex(&bytes)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn hash(key: &str) -> String {
+    to_hex(&Sha256::digest(key.as_bytes()))
+}
+
+impl ApiKeyService for Sha256ApiKeyService {
+    fn generate(&self) -> GeneratedApiKey {
+        let prefix = random_hex(PREFIX_BYTES);
+        let key = format!("{}{}_{}", KEY_PREFIX, prefix, random_hex(SECRET_BYTES));
+        GeneratedApiKey {
+            hash: hash(&key),
+            key,
+            prefix,
+        }
+    }
+
+    fn prefix_of<'a>(&self, key: &'a str) -> Option<&'a str> {
+        let (prefix, secret) = key.strip_prefix(KEY_PREFIX)?.split_once('_')?;
+        let is_hex = |part: &str, bytes: usize| {
+            part.len() == bytes * 2 && part.bytes().all(|b| b.is_ascii_hexdigit())
+        };
+        (is_hex(prefix, PREFIX_BYTES) && is_hex(secret, SECRET_BYTES)).then_some(prefix)
+    }
+
+    fn verify(&self, key: &str, expected: &str) -> bool {
+        let actual = hash(key);
+        // Compare in constant time so the stored hash cannot be probed byte by byte.
+        actual.len() == expected.len()
+            && actual
+                .bytes()
+                .zip(expected.bytes())
+                .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_and_verify() {
+        let service = Sha256ApiKeyService::new();
+        let generated = service.generate();
+        assert!(generated.key.starts_with("tdk_"));
+        assert_eq!(service.prefix_of(&generated.key), Some(&*generated.prefix));
+        assert!(service.verify(&generated.key, &generated.hash));
+        assert!(!generated.hash.contains(&generated.key));
+
+        let other = service.generate();
+        assert_ne!(other.prefix, generated.prefix);
+        assert!(!service.verify(&other.key, &generated.hash));
+    }
+
+    #[test]
+    fn test_prefix_of_rejects_malformed_keys() {
+        let service = Sha256ApiKeyService::new();
+        let key = service.generate().key;
+        for malformed in [
+            "",
+            "tdk_",
+            &key[4..],
+            &key[..key.len() - 1],
+            &key.replace('_', "-"),
+            &format!("{}g", &key[..key.len() - 1]),
+        ] {
+            assert_eq!(service.prefix_of(malformed), None, "{}", malformed);
+        }
+    }
+}
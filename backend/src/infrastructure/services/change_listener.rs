@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use sea_orm::sqlx::{self, postgres::PgListener};
+
+use crate::{
+    application_service::service::event_hub::EventHub,
+    domain::models::{errors::DomainError, todo_event::TodoEvent},
+};
+
+/// Notified by the `outbox` trigger for every committed todo event.
+const CHANNEL: &str = "todo_changes";
+/// Delay before the first reconnection attempt; doubled after every failed one.
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Feeds the todo changes committed by every instance into the local event hub.
+pub struct PgChangeListener {
+    database_url: String,
+}
+
+impl PgChangeListener {
+    pub fn new(database_url: &str) -> Self {
+        Self {
+            database_url: database_url.to_string(),
+        }
+    }
+
+    /// Listens for as long as the server runs, reconnecting whenever the
+    /// database goes away. Changes committed while disconnected are missed:
+    /// notifications only reach the sessions listening at the time.
+    pub async fn run<H: EventHub>(&self, hub: &H) {
+        let mut delay = RECONNECT_BASE_DELAY;
+        loop {
+            let Err(err) = self.listen(hub, &mut delay).await;
+            eprintln!(
+                "Todo change listener disconnected, retrying in {:?}: {}",
+                delay, err
+            );
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+        }
+    }
+
+    /// Resets `delay` once listening, so only consecutive failures back off.
+    async fn listen<H: EventHub>(
+        &self,
+        hub: &H,
+        delay: &mut Duration,
+    ) -> Result<std::convert::Infallible, sqlx::Error> {
+        let mut listener = PgListener::connect(&self.database_url).await?;
+        listener.listen(CHANNEL).await?;
+        *delay = RECONNECT_BASE_DELAY;
+        loop {
+            let notification = listener.recv().await?;
+            let event = serde_json::from_str(notification.payload())
+                .map_err(|err| DomainError::Validation(err.to_string()))
+                .and_then(|payload| TodoEvent::from_payload(&payload));
+            match event {
+                Ok(event) => hub.publish(event),
+                Err(err) => eprintln!("Ignoring a todo change notification: {}", err),
+            }
+        }
+    }
+}
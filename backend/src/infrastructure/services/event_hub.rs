@@ -0,0 +1,142 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::{
+    application_service::service::event_hub::{EventHub, EventSubscription},
+    domain::models::todo_event::TodoEvent,
+};
+
+/// Events a subscriber may be behind before it loses its subscription.
+const CHANNEL_CAPACITY: usize = 256;
+/// Recent events kept for subscribers resuming with `Last-Event-ID`.
+const REPLAY_BUFFER_SIZE: usize = 1024;
+
+struct Inner {
+    sender: broadcast::Sender<TodoEvent>,
+    replay: Mutex<VecDeque<TodoEvent>>,
+}
+
+/// An in-memory hub; clones share the same subscribers.
+#[derive(Clone)]
+pub struct BroadcastEventHub {
+    inner: Arc<Inner>,
+}
+
+impl BroadcastEventHub {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        BroadcastEventHub {
+            inner: Arc::new(Inner {
+                sender,
+                replay: Mutex::new(VecDeque::with_capacity(REPLAY_BUFFER_SIZE)),
+            }),
+        }
+    }
+}
+
+impl Default for BroadcastEventHub {
+    fn default() -> Self {
+        BroadcastEventHub::new()
+    }
+}
+
+impl EventHub for BroadcastEventHub {
+    fn publish(&self, event: TodoEvent) {
+        // Buffering and sending under one lock keeps a subscriber from
+        // missing or repeating an event published while it subscribes.
+        let mut replay = self.inner.replay.lock().unwrap();
+        if replay.iter().any(|buffered| buffered.id == event.id) {
+            return;
+        }
+        if replay.len() == REPLAY_BUFFER_SIZE {
+            replay.pop_front();
+        }
+        replay.push_back(event.clone());
+        // Without subscribers there is nobody to tell.
+        let _ = self.inner.sender.send(event);
+    }
+
+    fn subscribe(&self, last_event_id: Option<Uuid>) -> EventSubscription {
+        let replay = self.inner.replay.lock().unwrap();
+        let receiver = self.inner.sender.subscribe();
+        let missed = match last_event_id {
+            None => vec![],
+            Some(last_event_id) => {
+                let start = replay
+                    .iter()
+                    .position(|event| event.id == last_event_id)
+                    .map_or(0, |index| index + 1);
+                replay.iter().skip(start).cloned().collect()
+            }
+        };
+        EventSubscription::new(missed, receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::todo_event::TodoEventKind;
+    use chrono::Utc;
+
+    fn event() -> TodoEvent {
+        TodoEvent::new(
+            TodoEventKind::TodoUpdated,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            Utc::now(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_event_hub() {
+        let hub = BroadcastEventHub::new();
+        let first = event();
+        hub.publish(first.clone());
+
+        // New subscribers only see what comes next, once.
+        let mut subscription = hub.subscribe(None);
+        let second = event();
+        hub.publish(second.clone());
+        hub.publish(second.clone());
+        assert_eq!(subscription.recv().await, Some(second.clone()));
+
+        // Resuming replays what followed the last event seen...
+        let mut resumed = hub.subscribe(Some(first.id));
+        assert_eq!(resumed.recv().await, Some(second.clone()));
+        let third = event();
+        hub.publish(third.clone());
+        assert_eq!(resumed.recv().await, Some(third.clone()));
+        assert_eq!(subscription.recv().await, Some(third.clone()));
+
+        // ...or everything buffered when that event is unknown.
+        let mut resumed = hub.subscribe(Some(Uuid::now_v7()));
+        for expected in [first, second, third] {
+            assert_eq!(resumed.recv().await, Some(expected));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_event_hub_bounds() {
+        let hub = BroadcastEventHub::new();
+        let mut subscription = hub.subscribe(None);
+        let events: Vec<TodoEvent> = (0..REPLAY_BUFFER_SIZE + 1).map(|_| event()).collect();
+        for event in &events {
+            hub.publish(event.clone());
+        }
+
+        // A subscriber that fell too far behind is dropped.
+        assert_eq!(subscription.recv().await, None);
+
+        // The oldest event left the replay buffer.
+        let mut resumed = hub.subscribe(Some(events[0].id));
+        assert_eq!(resumed.recv().await, Some(events[1].clone()));
+        let mut resumed = hub.subscribe(Some(Uuid::now_v7()));
+        assert_eq!(resumed.recv().await, Some(events[1].clone()));
+    }
+}
@@ -0,0 +1,62 @@
+use argon2::{
+    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+    password_hash::{self, SaltString, rand_core::OsRng},
+};
+use async_trait::async_trait;
+
+use crate::application_service::{
+    service::password_service::PasswordService, usecase::errors::UsecaseError,
+};
+
+/// Argon2id with the crate's recommended parameters. Hashing is deliberately
+/// slow, so it runs on the blocking thread pool.
+pub struct Argon2PasswordService;
+
+impl Argon2PasswordService {
+    pub fn new() -> Self {
+        Argon2PasswordService {}
+    }
+}
+
+impl Default for Argon2PasswordService {
+    fn default() -> Self {
+        Argon2PasswordService::new()
+    }
+}
+
+async fn blocking<T, F>(f: F) -> Result<T, UsecaseError>
+where
+    F: FnOnce() -> Result<T, UsecaseError> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|err| UsecaseError::Unexpected(err.to_string()))?
+}
+
+#[async_trait]
+impl PasswordService for Argon2PasswordService {
+    async fn hash(&self, password: String) -> Result<String, UsecaseError> {
+        blocking(move || {
+            let salt = SaltString::generate(&mut OsRng);
+            Argon2::default()
+                .hash_password(password.as_bytes(), &salt)
+                .map(|hash| hash.to_string())
+                .map_err(|err| UsecaseError::Unexpected(err.to_string()))
+        })
+        .await
+    }
+
+    async fn verify(&self, password: String, hash: String) -> Result<bool, UsecaseError> {
+        blocking(move || {
+            let hash = PasswordHash::new(&hash)
+                .map_err(|err| UsecaseError::Unexpected(err.to_string()))?;
+            match Argon2::default().verify_password(password.as_bytes(), &hash) {
+                Ok(()) => Ok(true),
+                Err(password_hash::Error::Password) => Ok(false),
+                Err(err) => Err(UsecaseError::Unexpected(err.to_string())),
+            }
+        })
+        .await
+    }
+}
@@ -0,0 +1,221 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use jsonwebtoken::{
+    Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode, errors::ErrorKind,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    application_service::{
+        service::token_service::{TokenKind, TokenPair, TokenService},
+        usecase::errors::UsecaseError,
+    },
+    infrastructure::config::{JwtConfig, JwtKeys},
+};
+
+#[derive(Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum TokenType {
+    Access,
+    Refresh,
+}
+
+impl From<TokenKind> for TokenType {
+    fn from(kind: TokenKind) -> Self {
+        match kind {
+            TokenKind::Access => TokenType::Access,
+            TokenKind::Refresh => TokenType::Refresh,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Claims {
+    sub: Uuid,
+    /// Keeps refresh tokens from being accepted as access tokens and vice versa.
+    typ: TokenType,
+    jti: Uuid,
+    iat: i64,
+    exp: i64,
+}
+
+pub struct JwtTokenService {
+    algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    access_ttl: Duration,
+    refresh_ttl: Duration,
+}
+
+impl JwtTokenService {
+    /// Panics when the configured RSA keys are not valid PEM.
+    pub fn new(config: &JwtConfig) -> Self {
+        let (algorithm, encoding_key, decoding_key) = match &config.keys {
+            JwtKeys::Hs256 { secret } => (
+                Algorithm::HS256,
+                EncodingKey::from_secret(secret.as_bytes()),
+                DecodingKey::from_secret(secret.as_bytes()),
+            ),
+            JwtKeys::Rs256 {
+                private_key,
+                public_key,
+            } => (
+                Algorithm::RS256,
+                EncodingKey::from_rsa_pem(private_key.as_bytes())
+                    .expect("JWT private key must be an RSA key in PEM format"),
+                DecodingKey::from_rsa_pem(public_key.as_bytes())
+                    .expect("JWT public key must be an RSA key in PEM format"),
+            ),
+        };
+        Self {
+            algorithm,
+            encoding_key,
+            decoding_key,
+            access_ttl: config.access_ttl,
+            refresh_ttl: config.refresh_ttl,
+        }
+    }
+
+    fn encode(
+        &self,
+        user_id: Uuid,
+        kind: TokenKind,
+        ttl: Duration,
+    ) -> Result<String, UsecaseError> {
+        let now = Utc::now().timestamp();
+        let claims = Claims {
+            sub: user_id,
+            typ: kind.into(),
+            jti: Uuid::now_v7(),
+            iat: now,
+            exp: now + ttl.as_secs() as i64,
+        };
+        encode(&Header::new(self.algorithm), &claims, &self.encoding_key)
+            .map_err(|err| UsecaseError::Unexpected(err.to_string()))
+    }
+}
+
+impl TokenService for JwtTokenService {
+    fn issue(&self, user_id: Uuid) -> Result<TokenPair, UsecaseError> {
+        Ok(TokenPair {
+            access_token: self.encode(user_id, TokenKind::Access, self.access_ttl)?,
+            refresh_token: self.encode(user_id, TokenKind::Refresh, self.refresh_ttl)?,
+            expires_in: self.access_ttl.as_secs(),
+        })
+    }
+
+    fn verify(&self, token: &str, kind: TokenKind) -> Result<Uuid, UsecaseError> {
+        // Only the configured algorithm is accepted, whatever the token header claims.
+        let mut validation = Validation::new(self.algorithm);
+        // Tokens are issued and verified by the same clock.
+        validation.leeway = 0;
+        let claims = decode::<Claims>(token, &self.decoding_key, &validation)
+            .map_err(|err| match err.kind() {
+                ErrorKind::ExpiredSignature => {
+                    UsecaseError::Unauthorized("Token has expired".into())
+                }
+                _ => UsecaseError::Unauthorized("Token is invalid".into()),
+            })?
+            .claims;
+        if claims.typ != kind.into() {
+            return Err(UsecaseError::Unauthorized(
+                "Token is not valid for this purpose".into(),
+            ));
+        }
+        Ok(claims.sub)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hs256(secret: &str, access_ttl: Duration) -> JwtTokenService {
+        JwtTokenService::new(&JwtConfig {
+            keys: JwtKeys::Hs256 {
+                secret: secret.into(),
+            },
+            access_ttl,
+            refresh_ttl: Duration::from_secs(3600),
+        })
+    }
+
+    #[test]
+    fn test_hs256_round_trip() {
+        let service = hs256("0123456789abcdef0123456789abcdef", Duration::from_secs(60));
+        let user_id = Uuid::now_v7();
+        let pair = service.issue(user_id).unwrap();
+        assert_eq!(pair.expires_in, 60);
+        assert_eq!(
+            service
+                .verify(&pair.access_token, TokenKind::Access)
+                .unwrap(),
+            user_id
+        );
+        assert_eq!(
+            service
+                .verify(&pair.refresh_token, TokenKind::Refresh)
+                .unwrap(),
+            user_id
+        );
+    }
+
+    #[test]
+    fn test_rs256_round_trip() {
+        let service = JwtTokenService::new(&JwtConfig {
+            keys: JwtKeys::Rs256 {
+                private_key: include_str!("testdata/jwt_rs256_private.pem").into(),
+                public_key: include_str!("testdata/jwt_rs256_public.pem").into(),
+            },
+            access_ttl: Duration::from_secs(60),
+            refresh_ttl: Duration::from_secs(3600),
+        });
+        let user_id = Uuid::now_v7();
+        let pair = service.issue(user_id).unwrap();
+        assert_eq!(
+            service
+                .verify(&pair.access_token, TokenKind::Access)
+                .unwrap(),
+            user_id
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_kind() {
+        let service = hs256("0123456789abcdef0123456789abcdef", Duration::from_secs(60));
+        let pair = service.issue(Uuid::now_v7()).unwrap();
+        assert!(matches!(
+            service.verify(&pair.refresh_token, TokenKind::Access),
+            Err(UsecaseError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            service.verify(&pair.access_token, TokenKind::Refresh),
+            Err(UsecaseError::Unauthorized(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_rejects_foreign_and_expired_tokens() {
+        let service = hs256("0123456789abcdef0123456789abcdef", Duration::from_secs(60));
+        let other = hs256("fedcba9876543210fedcba9876543210", Duration::from_secs(60));
+        let pair = other.issue(Uuid::now_v7()).unwrap();
+        assert!(matches!(
+            service.verify(&pair.access_token, TokenKind::Access),
+            Err(UsecaseError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            service.verify("not-a-token", TokenKind::Access),
+            Err(UsecaseError::Unauthorized(_))
+        ));
+
+        let expired = hs256("0123456789abcdef0123456789abcdef", Duration::ZERO);
+        let pair = expired.issue(Uuid::now_v7()).unwrap();
+        std::thread::sleep(Duration::from_millis(1100));
+        assert!(matches!(
+            service.verify(&pair.access_token, TokenKind::Access),
+            Err(UsecaseError::Unauthorized(msg)) if msg.contains("expired")
+        ));
+    }
+}
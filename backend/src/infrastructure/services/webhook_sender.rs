@@ -0,0 +1,341 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::{
+    Client, Url,
+    dns::{Addrs, Name, Resolve, Resolving},
+    header::CONTENT_TYPE,
+    redirect::Policy,
+};
+use sha2::Sha256;
+
+use crate::{
+    application_service::{service::webhook_sender::WebhookSender, usecase::errors::UsecaseError},
+    domain::models::webhook::{Webhook, WebhookDelivery},
+};
+
+pub const DELIVERY_ID_HEADER: &str = "x-webhook-id";
+pub const EVENT_HEADER: &str = "x-webhook-event";
+pub const TIMESTAMP_HEADER: &str = "x-webhook-timestamp";
+/// `sha256=<hex HMAC-SHA256 of "<timestamp>.<body>" keyed with the webhook secret>`.
+pub const SIGNATURE_HEADER: &str = "x-webhook-signature";
+
+const SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Posts deliveries as JSON. The timestamp is part of the signed message so
+/// receivers can reject old deliveries replayed by someone else.
+///
+/// Webhook URLs come from users, so deliveries only go to public addresses:
+/// hosts are resolved when sending and loopback, link-local and private
+/// addresses are refused, keeping internal services out of reach.
+pub struct HttpWebhookSender {
+    client: Client,
+    /// Non-public addresses deliveries may still go to.
+    allowed: Arc<[IpAddr]>,
+}
+
+impl HttpWebhookSender {
+    pub fn new() -> Self {
+        HttpWebhookSender::with_allowed(Arc::new([]))
+    }
+
+    /// Lets tests deliver to a receiver on the local machine.
+    #[cfg(test)]
+    fn allowing(addresses: &[IpAddr]) -> Self {
+        HttpWebhookSender::with_allowed(addresses.into())
+    }
+
+    fn with_allowed(allowed: Arc<[IpAddr]>) -> Self {
+        let client = Client::builder()
+            .timeout(SEND_TIMEOUT)
+            // A redirect could take the signed payload somewhere it was not meant to go.
+            .redirect(Policy::none())
+            // A proxy would resolve the host itself, past the checks below.
+            .no_proxy()
+            .dns_resolver(Arc::new(PublicResolver {
+                allowed: allowed.clone(),
+            }))
+            .build()
+            .expect("build webhook HTTP client");
+        HttpWebhookSender { client, allowed }
+    }
+
+    fn ensure_allowed_host(&self, url: &str) -> Result<(), UsecaseError> {
+        let url = Url::parse(url)
+            .map_err(|err| UsecaseError::Unexpected(format!("Invalid webhook URL: {}", err)))?;
+        // Names go through `PublicResolver`, addresses are connected to directly.
+        let host = url.host_str().unwrap_or_default();
+        if let Ok(ip) = host.trim_start_matches('[').trim_end_matches(']').parse()
+            && !is_allowed(&self.allowed, ip)
+        {
+            return Err(UsecaseError::Unexpected(format!(
+                "Webhooks cannot be sent to {}",
+                ip
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for HttpWebhookSender {
+    fn default() -> Self {
+        HttpWebhookSender::new()
+    }
+}
+
+/// Loopback, link-local, private and other addresses that do not lead to the
+/// public internet.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => is_public_v6(ip),
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    // 0.0.0.0/8 reaches the local host on Linux.
+    let this_network = a == 0;
+    // 100.64.0.0/10 is shared by carrier-grade NATs.
+    let shared = a == 100 && (b & 0xc0) == 64;
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || this_network
+        || shared)
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    // Mapped (::ffff:0:0/96), compatible (::/96) and NAT64 (64:ff9b::/96)
+    // addresses lead to an IPv4 address, which decides.
+    let [high @ .., seven, eight] = ip.segments();
+    let embedded = Ipv4Addr::from((u32::from(seven) << 16) | u32::from(eight));
+    match high {
+        [0, 0, 0, 0, 0, 0xffff] | [0, 0, 0, 0, 0, 0] | [0x64, 0xff9b, 0, 0, 0, 0] => {
+            is_public_v4(embedded)
+        }
+        _ => {
+            !(ip.is_loopback()
+                || ip.is_unique_local()
+                || ip.is_unicast_link_local()
+                || ip.is_unspecified()
+                || ip.is_multicast())
+        }
+    }
+}
+
+fn is_allowed(allowed: &[IpAddr], ip: IpAddr) -> bool {
+    is_public(ip) || allowed.contains(&ip.to_canonical())
+}
+
+/// Drops the addresses deliveries may not go to, so the connection is made
+/// to an address that was checked, even if the name resolves differently later.
+struct PublicResolver {
+    allowed: Arc<[IpAddr]>,
+}
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let allowed = self.allowed.clone();
+        Box::pin(async move {
+            let addresses: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|address| is_allowed(&allowed, address.ip()))
+                .collect();
+            if addresses.is_empty() {
+                return Err(format!("Webhooks cannot be sent to {}", name.as_str()).into());
+            }
+            Ok(Box::new(addresses.into_iter()) as Addrs)
+        })
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// The value of the signature header for `body` sent at `timestamp`.
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", to_hex(&mac.finalize().into_bytes()))
+}
+
+#[async_trait]
+impl WebhookSender for HttpWebhookSender {
+    async fn send(
+        &self,
+        webhook: &Webhook,
+        delivery: &WebhookDelivery,
+    ) -> Result<u16, UsecaseError> {
+        self.ensure_allowed_host(&webhook.url)?;
+        let body = serde_json::to_vec(&delivery.payload)
+            .map_err(|err| UsecaseError::Unexpected(err.to_string()))?;
+        let timestamp = Utc::now().timestamp();
+        let response = self
+            .client
+            .post(&webhook.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(DELIVERY_ID_HEADER, delivery.id.to_string())
+            .header(EVENT_HEADER, delivery.event_type.to_string())
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, sign(&webhook.secret, timestamp, &body))
+            .body(body)
+            .send()
+            .await
+            .map_err(|err| UsecaseError::Unexpected(format!("Request failed: {}", err)))?;
+        Ok(response.status().as_u16())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::todo_event::{TodoEvent, TodoEventKind};
+    use axum::{Router, body::Bytes, extract::State, http::HeaderMap, http::StatusCode};
+    use std::net::Ipv4Addr;
+    use std::sync::Mutex;
+    use tokio::net::TcpListener;
+    use uuid::Uuid;
+
+    type Received = Arc<Mutex<Vec<(HeaderMap, Bytes)>>>;
+
+    /// Starts a stand-in receiver answering every request with `status`.
+    async fn receiver(status: StatusCode) -> (String, Received) {
+        let received = Received::default();
+        let app = Router::new()
+            .route(
+                "/hooks",
+                axum::routing::post(
+                    move |State(received): State<Received>, headers: HeaderMap, body: Bytes| async move {
+                        received.lock().unwrap().push((headers, body));
+                        status
+                    },
+                ),
+            )
+            .with_state(received.clone());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hooks", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, received)
+    }
+
+    /// Allows the receivers started by `receiver`.
+    fn sender() -> HttpWebhookSender {
+        HttpWebhookSender::allowing(&[IpAddr::V4(Ipv4Addr::LOCALHOST)])
+    }
+
+    fn delivery(webhook: &Webhook) -> WebhookDelivery {
+        let event = TodoEvent::new(
+            TodoEventKind::TodoCreated,
+            Uuid::now_v7(),
+            webhook.owner_id,
+            Utc::now(),
+        );
+        WebhookDelivery::new(webhook.id, &event)
+    }
+
+    #[tokio::test]
+    async fn test_send_signed_delivery() {
+        let (url, received) = receiver(StatusCode::NO_CONTENT).await;
+        let webhook = Webhook::new(Uuid::now_v7(), url, vec![], "0123456789abcdef".into()).unwrap();
+        let delivery = delivery(&webhook);
+        let sender = sender();
+
+        let status = sender.send(&webhook, &delivery).await.unwrap();
+        assert_eq!(status, 204);
+
+        let received = received.lock().unwrap();
+        let (headers, body) = &received[0];
+        let header = |name: &str| headers[name].to_str().unwrap().to_string();
+        assert_eq!(header("content-type"), "application/json");
+        assert_eq!(header(DELIVERY_ID_HEADER), delivery.id.to_string());
+        assert_eq!(header(EVENT_HEADER), "todo.created");
+        let timestamp: i64 = header(TIMESTAMP_HEADER).parse().unwrap();
+        assert_eq!(
+            header(SIGNATURE_HEADER),
+            sign("0123456789abcdef", timestamp, body)
+        );
+        assert_ne!(
+            header(SIGNATURE_HEADER),
+            sign("another secret..", timestamp, body)
+        );
+        let payload: serde_json::Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(payload, delivery.payload);
+    }
+
+    #[tokio::test]
+    async fn test_send_reports_failures() {
+        let (url, _) = receiver(StatusCode::SERVICE_UNAVAILABLE).await;
+        let webhook = Webhook::new(Uuid::now_v7(), url, vec![], "0123456789abcdef".into()).unwrap();
+        let sender = sender();
+        let status = sender.send(&webhook, &delivery(&webhook)).await.unwrap();
+        assert_eq!(status, 503);
+
+        // Nothing listens on the port once the listener is gone.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hooks", listener.local_addr().unwrap());
+        drop(listener);
+        let webhook = Webhook::new(Uuid::now_v7(), url, vec![], "0123456789abcdef".into()).unwrap();
+        let result = sender.send(&webhook, &delivery(&webhook)).await;
+        assert!(matches!(result, Err(UsecaseError::Unexpected(_))));
+    }
+
+    #[test]
+    fn test_is_public() {
+        for ip in [
+            "93.184.215.14",
+            "2606:2800:21f:cb07:6820:80da:af6b:8b2c",
+            "::ffff:93.184.215.14",
+            "64:ff9b::93.184.215.14",
+        ] {
+            assert!(is_public(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "0.1.2.3",
+            "::",
+            "::1",
+            "::ffff:127.0.0.1",
+            "::ffff:10.0.0.1",
+            "::10.0.0.1",
+            "64:ff9b::127.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+            "fe80::1",
+            "fd00::1",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_rejects_private_addresses() {
+        let (url, received) = receiver(StatusCode::NO_CONTENT).await;
+        let port = url.split(':').nth(2).unwrap();
+        let sender = HttpWebhookSender::new();
+        for url in [url.clone(), format!("http://localhost:{}", port)] {
+            let webhook =
+                Webhook::new(Uuid::now_v7(), url, vec![], "0123456789abcdef".into()).unwrap();
+            let result = sender.send(&webhook, &delivery(&webhook)).await;
+            assert!(matches!(result, Err(UsecaseError::Unexpected(_))));
+        }
+        assert!(received.lock().unwrap().is_empty());
+    }
+}
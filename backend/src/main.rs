@@ -1,18 +1,59 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use axum::{BoxError, Router, error_handling::HandleErrorLayer, serve};
+use axum::{BoxError, Extension, Router, error_handling::HandleErrorLayer, middleware, serve};
 
 use sea_orm::{ConnectOptions, Database};
+use todo_api_rust::application_service::usecase::api_key_usecase::ApiKeyUsecaseImpl;
+use todo_api_rust::application_service::usecase::audit_usecase::AuditUsecaseImpl;
+use todo_api_rust::application_service::usecase::auth_usecase::AuthUsecaseImpl;
+use todo_api_rust::application_service::usecase::dependency_usecase::DependencyUsecaseImpl;
+use todo_api_rust::application_service::usecase::idempotency_usecase::{
+    IdempotencyUsecase, IdempotencyUsecaseImpl,
+};
+use todo_api_rust::application_service::usecase::outbox_usecase::{
+    OutboxUsecase, OutboxUsecaseImpl,
+};
+use todo_api_rust::application_service::usecase::project_usecase::ProjectUsecaseImpl;
+use todo_api_rust::application_service::usecase::share_usecase::ShareUsecaseImpl;
+use todo_api_rust::application_service::usecase::tag_usecase::TagUsecaseImpl;
 use todo_api_rust::application_service::usecase::todo_usecase::TodoUsecaseImpl;
+use todo_api_rust::application_service::usecase::webhook_usecase::{
+    WebhookUsecase, WebhookUsecaseImpl,
+};
+use todo_api_rust::domain::repositories::conn::Conn;
 use todo_api_rust::infrastructure::config::Config;
+use todo_api_rust::infrastructure::repositories::api_key_repository::ApiKeyRepositoryImpl;
+use todo_api_rust::infrastructure::repositories::audit_repository::AuditRepositoryImpl;
+use todo_api_rust::infrastructure::repositories::dependency_repository::DependencyRepositoryImpl;
+use todo_api_rust::infrastructure::repositories::idempotency_repository::IdempotencyRepositoryImpl;
+use todo_api_rust::infrastructure::repositories::outbox_repository::OutboxRepositoryImpl;
+use todo_api_rust::infrastructure::repositories::project_repository::ProjectRepositoryImpl;
+use todo_api_rust::infrastructure::repositories::share_repository::ShareRepositoryImpl;
+use todo_api_rust::infrastructure::repositories::tag_repository::TagRepositoryImpl;
 use todo_api_rust::infrastructure::repositories::todo_repository::TodoRepositoryImpl;
+use todo_api_rust::infrastructure::repositories::user_repository::UserRepositoryImpl;
+use todo_api_rust::infrastructure::repositories::webhook_delivery_repository::WebhookDeliveryRepositoryImpl;
+use todo_api_rust::infrastructure::repositories::webhook_repository::WebhookRepositoryImpl;
+use todo_api_rust::infrastructure::services::api_key_service::Sha256ApiKeyService;
+use todo_api_rust::infrastructure::services::change_listener::PgChangeListener;
+use todo_api_rust::infrastructure::services::event_hub::BroadcastEventHub;
+use todo_api_rust::infrastructure::services::password_service::Argon2PasswordService;
+use todo_api_rust::infrastructure::services::token_service::JwtTokenService;
 use todo_api_rust::infrastructure::services::transaction_service::TransactionServiceImpl;
+use todo_api_rust::infrastructure::services::webhook_sender::HttpWebhookSender;
 use todo_api_rust::presentation;
+use todo_api_rust::presentation::auth::{ApiKeyAuthState, TokenVerifier, authenticate_api_key};
 use todo_api_rust::presentation::errors::{AppError, ErrorBody};
 use tokio::net::TcpListener;
 use tower::ServiceBuilder;
 
+const OUTBOX_BATCH_SIZE: u64 = 100;
+const OUTBOX_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const WEBHOOK_BATCH_SIZE: u64 = 20;
+const WEBHOOK_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const IDEMPOTENCY_PURGE_INTERVAL: Duration = Duration::from_secs(600);
+
 #[tokio::main]
 async fn main() {
     let config = Config::default();
@@ -23,23 +64,7 @@ async fn main() {
                 code: "404",
                 message: format!("Resource not found for URI: {}", uri),
             })
-        })
-        .layer(
-            ServiceBuilder::new()
-                // `timeout` will produce an error if the handler takes
-                // too long so we must handle those
-                .layer(HandleErrorLayer::new(async |err: BoxError| -> AppError {
-                    if err.is::<tower::timeout::error::Elapsed>() {
-                        AppError::Timeout
-                    } else {
-                        AppError::Internal(ErrorBody {
-                            code: "500",
-                            message: format!("Unhandled internal error: {}", err),
-                        })
-                    }
-                }))
-                .timeout(Duration::from_secs(10)),
-        );
+        });
 
     let listener = TcpListener::bind(format!(
         "{}:{}",
@@ -66,11 +91,88 @@ async fn app(config: &Config) -> Router {
         .min_connections(1)
         .connect_timeout(Duration::from_secs(5))
         .sqlx_logging(true);
-    let conn = Database::connect(opt).await.expect("connect db");
+    let conn = Arc::new(Database::connect(opt).await.expect("connect db"));
+
+    let event_hub = BroadcastEventHub::new();
+    // Changes committed by other instances reach this one's subscribers too.
+    let change_listener = PgChangeListener::new(database_url);
+    let listener_hub = event_hub.clone();
+    tokio::spawn(async move { change_listener.run(&listener_hub).await });
 
     let todo_repository = TodoRepositoryImpl::new();
     let transaction_service = TransactionServiceImpl::new();
-    let todo_usecase = TodoUsecaseImpl::new(todo_repository, transaction_service);
+    let todo_usecase = Arc::new(TodoUsecaseImpl::new(
+        todo_repository,
+        ProjectRepositoryImpl::new(),
+        DependencyRepositoryImpl::new(),
+        ShareRepositoryImpl::new(),
+        AuditRepositoryImpl::new(),
+        OutboxRepositoryImpl::new(),
+        event_hub,
+        transaction_service,
+    ));
+    let tag_usecase = Arc::new(TagUsecaseImpl::new(
+        TagRepositoryImpl::new(),
+        TodoRepositoryImpl::new(),
+        TransactionServiceImpl::new(),
+    ));
+    let project_usecase = ProjectUsecaseImpl::new(
+        ProjectRepositoryImpl::new(),
+        TodoRepositoryImpl::new(),
+        TransactionServiceImpl::new(),
+    );
+    let dependency_usecase = DependencyUsecaseImpl::new(
+        DependencyRepositoryImpl::new(),
+        TodoRepositoryImpl::new(),
+        TransactionServiceImpl::new(),
+    );
+    let share_usecase = Arc::new(ShareUsecaseImpl::new(
+        ShareRepositoryImpl::new(),
+        TodoRepositoryImpl::new(),
+        ProjectRepositoryImpl::new(),
+        TransactionServiceImpl::new(),
+    ));
+    let audit_usecase = Arc::new(AuditUsecaseImpl::new(
+        AuditRepositoryImpl::new(),
+        TodoRepositoryImpl::new(),
+    ));
+    let auth_usecase = AuthUsecaseImpl::new(
+        UserRepositoryImpl::new(),
+        Argon2PasswordService::new(),
+        JwtTokenService::new(&config.jwt),
+    );
+    let api_key_usecase = Arc::new(ApiKeyUsecaseImpl::new(
+        ApiKeyRepositoryImpl::new(),
+        Sha256ApiKeyService::new(),
+        TransactionServiceImpl::new(),
+    ));
+    let api_key_auth = ApiKeyAuthState {
+        api_key_usecase: api_key_usecase.clone(),
+        db: conn.clone(),
+    };
+    let token_verifier: TokenVerifier = Arc::new(JwtTokenService::new(&config.jwt));
+    let webhook_usecase = WebhookUsecaseImpl::new(
+        WebhookRepositoryImpl::new(),
+        WebhookDeliveryRepositoryImpl::new(),
+        HttpWebhookSender::new(),
+        TransactionServiceImpl::new(),
+    );
+    // Committed todo events fan out to the webhooks subscribed to them.
+    tokio::spawn(dispatch_outbox(
+        OutboxUsecaseImpl::new(
+            OutboxRepositoryImpl::new(),
+            webhook_usecase.clone(),
+            TransactionServiceImpl::new(),
+        ),
+        conn.clone(),
+    ));
+    tokio::spawn(deliver_webhooks(webhook_usecase.clone(), conn.clone()));
+    let idempotency_usecase =
+        Arc::new(IdempotencyUsecaseImpl::new(IdempotencyRepositoryImpl::new()));
+    tokio::spawn(purge_idempotency_keys(
+        idempotency_usecase.clone(),
+        conn.clone(),
+    ));
     Router::new()
         .nest(
             "/health",
@@ -78,10 +180,155 @@ async fn app(config: &Config) -> Router {
         )
         .nest("/hello", presentation::hello_handler::create_hello_router())
         .nest("/wait", presentation::wait_handler::create_wait_router())
+        .nest(
+            "/auth",
+            presentation::auth_handler::create_auth_router(Arc::new(auth_usecase), conn.clone()),
+        )
+        .nest(
+            "/api-keys",
+            presentation::api_key_handler::create_api_key_router(api_key_usecase, conn.clone()),
+        )
+        .nest(
+            "/webhooks",
+            presentation::webhook_handler::create_webhook_router(
+                Arc::new(webhook_usecase),
+                conn.clone(),
+            ),
+        )
         .nest(
             "/todos",
-            presentation::todo_handler::create_todo_router(Arc::new(todo_usecase), Arc::new(conn)),
+            presentation::todo_handler::create_todo_router(
+                todo_usecase.clone(),
+                idempotency_usecase,
+                conn.clone(),
+            ),
+        )
+        .nest(
+            "/todos/{id}/tags",
+            presentation::tag_handler::create_todo_tag_router(tag_usecase.clone(), conn.clone()),
         )
+        .nest(
+            "/todos/{id}/shares",
+            presentation::share_handler::create_todo_share_router(
+                share_usecase.clone(),
+                conn.clone(),
+            ),
+        )
+        .nest(
+            "/todos/{id}/history",
+            presentation::audit_handler::create_todo_history_router(
+                audit_usecase.clone(),
+                conn.clone(),
+            ),
+        )
+        .nest(
+            "/todos/{id}",
+            presentation::dependency_handler::create_dependency_router(
+                Arc::new(dependency_usecase),
+                conn.clone(),
+            ),
+        )
+        .nest(
+            "/audit",
+            presentation::audit_handler::create_audit_router(audit_usecase, conn.clone()),
+        )
+        .nest(
+            "/tags",
+            presentation::tag_handler::create_tag_router(tag_usecase, conn.clone()),
+        )
+        .nest(
+            "/projects/{id}/shares",
+            presentation::share_handler::create_project_share_router(share_usecase, conn.clone()),
+        )
+        .nest(
+            "/projects",
+            presentation::project_handler::create_project_router(
+                Arc::new(project_usecase),
+                conn.clone(),
+            ),
+        )
+        .layer(
+            ServiceBuilder::new()
+                // `timeout` will produce an error if the handler takes
+                // too long so we must handle those
+                .layer(HandleErrorLayer::new(async |err: BoxError| -> AppError {
+                    if err.is::<tower::timeout::error::Elapsed>() {
+                        AppError::Timeout
+                    } else {
+                        AppError::Internal(ErrorBody {
+                            code: "500",
+                            message: format!("Unhandled internal error: {}", err),
+                        })
+                    }
+                }))
+                .timeout(Duration::from_secs(10)),
+        )
+        // Added after the timeout so that it only bounds ordinary requests:
+        // WebSocket connections stay open for as long as the client wants.
+        .nest(
+            "/ws",
+            presentation::ws_handler::create_ws_router(todo_usecase, conn.clone()),
+        )
+        .layer(Extension(token_verifier))
+        .layer(middleware::from_fn_with_state(
+            api_key_auth,
+            authenticate_api_key,
+        ))
+}
+
+/// Drains the outbox for as long as the server runs. A full batch means more
+/// messages are probably waiting, so the next one starts right away.
+async fn dispatch_outbox<U, C>(outbox_usecase: U, conn: Arc<C>)
+where
+    U: OutboxUsecase,
+    C: Conn,
+{
+    loop {
+        match outbox_usecase
+            .dispatch_pending(conn.as_ref(), OUTBOX_BATCH_SIZE)
+            .await
+        {
+            Ok(dispatched) if dispatched as u64 == OUTBOX_BATCH_SIZE => continue,
+            Ok(_) => {}
+            Err(err) => eprintln!("Failed to dispatch the outbox: {}", err),
+        }
+        tokio::time::sleep(OUTBOX_POLL_INTERVAL).await;
+    }
+}
+
+/// Sends due webhook deliveries for as long as the server runs, in the same
+/// way as [`dispatch_outbox`].
+async fn deliver_webhooks<U, C>(webhook_usecase: U, conn: Arc<C>)
+where
+    U: WebhookUsecase,
+    C: Conn,
+{
+    loop {
+        match webhook_usecase
+            .deliver_pending(conn.as_ref(), WEBHOOK_BATCH_SIZE)
+            .await
+        {
+            Ok(delivered) if delivered as u64 == WEBHOOK_BATCH_SIZE => continue,
+            Ok(_) => {}
+            Err(err) => eprintln!("Failed to deliver webhooks: {}", err),
+        }
+        tokio::time::sleep(WEBHOOK_POLL_INTERVAL).await;
+    }
+}
+
+/// Clears expired idempotency keys for as long as the server runs. Expired
+/// keys are ignored anyway; this only keeps the table small.
+async fn purge_idempotency_keys<U, C>(idempotency_usecase: Arc<U>, conn: Arc<C>)
+where
+    U: IdempotencyUsecase,
+    C: Conn,
+{
+    loop {
+        if let Err(err) = idempotency_usecase.purge_expired(conn.as_ref()).await {
+            eprintln!("Failed to purge idempotency keys: {}", err);
+        }
+        tokio::time::sleep(IDEMPOTENCY_PURGE_INTERVAL).await;
+    }
 }
 
 async fn shutdown_signal() {
@@ -1,6 +1,18 @@
+pub mod api_key_handler;
+pub mod audit_handler;
+pub mod auth;
+pub mod auth_handler;
+pub mod dependency_handler;
 pub mod errors;
 pub mod health_handler;
 pub mod hello_handler;
+pub mod idempotency;
+pub mod precondition;
+pub mod project_handler;
+pub mod share_handler;
+pub mod tag_handler;
 pub mod todo_handler;
 pub mod validator;
 pub mod wait_handler;
+pub mod webhook_handler;
+pub mod ws_handler;
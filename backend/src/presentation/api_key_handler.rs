@@ -0,0 +1,209 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get},
+};
+use axum_extra::extract::WithRejection;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application_service::usecase::api_key_usecase::{ApiKeyInput, ApiKeyUsecase, CreatedApiKey},
+    domain::{
+        models::api_key::{API_KEY_NAME_MAX_LENGTH, API_KEY_NAME_MIN_LENGTH, ApiKey, ApiKeyScope},
+        repositories::conn::Conn,
+    },
+    presentation::{auth::AuthUser, errors::AppError, validator::ValidatedJson},
+};
+
+pub struct AppState<C, U> {
+    api_key_usecase: Arc<U>,
+    db: Arc<C>,
+}
+
+impl<C, U> Clone for AppState<C, U> {
+    fn clone(&self) -> Self {
+        Self {
+            api_key_usecase: Arc::clone(&self.api_key_usecase),
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+/// Key management needs an interactive login; API keys cannot manage keys.
+pub fn create_api_key_router<C, U>(api_key_usecase: Arc<U>, db: Arc<C>) -> Router
+where
+    C: Conn + 'static,
+    U: ApiKeyUsecase + Send + Sync + 'static,
+{
+    let app_state: AppState<C, U> = AppState {
+        api_key_usecase,
+        db,
+    };
+
+    Router::new()
+        .route("/", get(get_api_keys::<C, U>).post(post_api_key::<C, U>))
+        .route("/{id}", delete(revoke_api_key::<C, U>))
+        .with_state(app_state)
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+enum ScopeParam {
+    #[serde(rename = "todos:read")]
+    TodosRead,
+    #[serde(rename = "todos:write")]
+    TodosWrite,
+}
+
+impl From<ScopeParam> for ApiKeyScope {
+    fn from(scope: ScopeParam) -> Self {
+        match scope {
+            ScopeParam::TodosRead => ApiKeyScope::TodosRead,
+            ScopeParam::TodosWrite => ApiKeyScope::TodosWrite,
+        }
+    }
+}
+
+impl From<ApiKeyScope> for ScopeParam {
+    fn from(scope: ApiKeyScope) -> Self {
+        match scope {
+            ApiKeyScope::TodosRead => ScopeParam::TodosRead,
+            ApiKeyScope::TodosWrite => ScopeParam::TodosWrite,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ApiKeyResponse {
+    id: Uuid,
+    name: String,
+    prefix: String,
+    scopes: Vec<ScopeParam>,
+    expires_at: Option<DateTime<Utc>>,
+    last_used_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(api_key: ApiKey) -> Self {
+        Self {
+            id: api_key.id,
+            name: api_key.name,
+            prefix: api_key.prefix,
+            scopes: api_key.scopes.into_iter().map(ScopeParam::from).collect(),
+            expires_at: api_key.expires_at,
+            last_used_at: api_key.last_used_at,
+            revoked_at: api_key.revoked_at,
+            created_at: api_key.created_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CreatedApiKeyResponse {
+    #[serde(flatten)]
+    api_key: ApiKeyResponse,
+    /// The secret key; it cannot be retrieved again.
+    key: String,
+}
+
+impl From<CreatedApiKey> for CreatedApiKeyResponse {
+    fn from(created: CreatedApiKey) -> Self {
+        Self {
+            api_key: ApiKeyResponse::from(created.api_key),
+            key: created.key,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ApiKeyListResponse {
+    api_keys: Vec<ApiKeyResponse>,
+}
+
+impl From<Vec<ApiKey>> for ApiKeyListResponse {
+    fn from(api_keys: Vec<ApiKey>) -> Self {
+        Self {
+            api_keys: api_keys.into_iter().map(ApiKeyResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Deserialize, Validate)]
+struct ApiKeyRequest {
+    #[validate(length(min = API_KEY_NAME_MIN_LENGTH, max = API_KEY_NAME_MAX_LENGTH))]
+    name: String,
+    #[validate(length(min = 1, message = "At least one scope is required"))]
+    scopes: Vec<ScopeParam>,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<ApiKeyRequest> for ApiKeyInput {
+    fn from(request: ApiKeyRequest) -> Self {
+        Self {
+            name: request.name,
+            scopes: request.scopes.into_iter().map(ApiKeyScope::from).collect(),
+            expires_at: request.expires_at,
+        }
+    }
+}
+
+async fn get_api_keys<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: ApiKeyUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let api_keys = app_state
+        .api_key_usecase
+        .get_api_keys(conn, user.user_id)
+        .await?;
+    Ok((StatusCode::OK, Json(ApiKeyListResponse::from(api_keys))))
+}
+
+async fn post_api_key<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    ValidatedJson(input): ValidatedJson<ApiKeyRequest>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: ApiKeyUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let created = app_state
+        .api_key_usecase
+        .create_api_key(conn, user.user_id, input.into())
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedApiKeyResponse::from(created)),
+    ))
+}
+
+async fn revoke_api_key<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: ApiKeyUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    app_state
+        .api_key_usecase
+        .revoke_api_key(conn, user.user_id, id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,209 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+};
+use axum_extra::extract::WithRejection;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application_service::usecase::audit_usecase::{AuditPage, AuditUsecase},
+    domain::{
+        models::audit::{AuditAction, AuditEvent, AuditQuery},
+        repositories::conn::Conn,
+    },
+    presentation::{
+        auth::AuthUser, errors::AppError, todo_handler::DEFAULT_PAGE_LIMIT,
+        validator::ValidatedQuery,
+    },
+};
+
+pub struct AppState<C, U> {
+    audit_usecase: Arc<U>,
+    db: Arc<C>,
+}
+
+impl<C, U> Clone for AppState<C, U> {
+    fn clone(&self) -> Self {
+        Self {
+            audit_usecase: Arc::clone(&self.audit_usecase),
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+/// Routes for the history of a single todo, meant to be nested under `/todos/{id}/history`.
+pub fn create_todo_history_router<C, U>(audit_usecase: Arc<U>, db: Arc<C>) -> Router
+where
+    C: Conn + 'static,
+    U: AuditUsecase + Send + Sync + 'static,
+{
+    let app_state: AppState<C, U> = AppState { audit_usecase, db };
+
+    Router::new()
+        .route("/", get(get_todo_history::<C, U>))
+        .with_state(app_state)
+}
+
+pub fn create_audit_router<C, U>(audit_usecase: Arc<U>, db: Arc<C>) -> Router
+where
+    C: Conn + 'static,
+    U: AuditUsecase + Send + Sync + 'static,
+{
+    let app_state: AppState<C, U> = AppState { audit_usecase, db };
+
+    Router::new()
+        .route("/", get(get_audit_events::<C, U>))
+        .with_state(app_state)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum AuditActionResponse {
+    Created,
+    Updated,
+    Deleted,
+    Completed,
+    Uncompleted,
+    Restored,
+    Purged,
+}
+
+impl From<AuditAction> for AuditActionResponse {
+    fn from(action: AuditAction) -> Self {
+        match action {
+            AuditAction::Created => AuditActionResponse::Created,
+            AuditAction::Updated => AuditActionResponse::Updated,
+            AuditAction::Deleted => AuditActionResponse::Deleted,
+            AuditAction::Completed => AuditActionResponse::Completed,
+            AuditAction::Uncompleted => AuditActionResponse::Uncompleted,
+            AuditAction::Restored => AuditActionResponse::Restored,
+            AuditAction::Purged => AuditActionResponse::Purged,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct AuditEventResponse {
+    id: Uuid,
+    todo_id: Uuid,
+    owner_id: Uuid,
+    actor_id: Uuid,
+    action: AuditActionResponse,
+    before: Option<Value>,
+    after: Option<Value>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<AuditEvent> for AuditEventResponse {
+    fn from(event: AuditEvent) -> Self {
+        Self {
+            id: event.id,
+            todo_id: event.todo_id,
+            owner_id: event.owner_id,
+            actor_id: event.actor_id,
+            action: event.action.into(),
+            before: event.before,
+            after: event.after,
+            created_at: event.created_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct AuditListResponse {
+    events: Vec<AuditEventResponse>,
+    next_cursor: Option<Uuid>,
+}
+
+impl From<AuditPage> for AuditListResponse {
+    fn from(page: AuditPage) -> Self {
+        Self {
+            events: page
+                .events
+                .into_iter()
+                .map(AuditEventResponse::from)
+                .collect(),
+            next_cursor: page.next_cursor,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate)]
+struct HistoryParams {
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    limit: Option<u64>,
+    after: Option<Uuid>,
+}
+
+#[derive(Deserialize, Validate)]
+struct AuditParams {
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    limit: Option<u64>,
+    after: Option<Uuid>,
+    actor_id: Option<Uuid>,
+    /// Inclusive start of the time range.
+    from: Option<DateTime<Utc>>,
+    /// Exclusive end of the time range.
+    to: Option<DateTime<Utc>>,
+}
+
+async fn get_todo_history<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    ValidatedQuery(params): ValidatedQuery<HistoryParams>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: AuditUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let page = app_state
+        .audit_usecase
+        .get_todo_history(
+            conn,
+            user.user_id,
+            id,
+            params.after,
+            params.limit.unwrap_or(DEFAULT_PAGE_LIMIT),
+        )
+        .await?;
+    Ok((StatusCode::OK, Json(AuditListResponse::from(page))))
+}
+
+async fn get_audit_events<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    ValidatedQuery(params): ValidatedQuery<AuditParams>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: AuditUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let query = AuditQuery {
+        actor_id: params.actor_id,
+        from: params.from,
+        to: params.to,
+    };
+    let page = app_state
+        .audit_usecase
+        .get_audit_events(
+            conn,
+            user.user_id,
+            query,
+            params.after,
+            params.limit.unwrap_or(DEFAULT_PAGE_LIMIT),
+        )
+        .await?;
+    Ok((StatusCode::OK, Json(AuditListResponse::from(page))))
+}
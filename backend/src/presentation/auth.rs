@@ -0,0 +1,152 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+use crate::{
+    application_service::{
+        service::token_service::{TokenKind, TokenService},
+        usecase::api_key_usecase::ApiKeyUsecase,
+    },
+    domain::{models::api_key::ApiKeyScope, repositories::conn::Conn},
+    presentation::errors::{AppError, ErrorBody},
+};
+
+/// Verifies access tokens for [`AuthUser`]; installed as a request extension in `app()`.
+pub type TokenVerifier = Arc<dyn TokenService>;
+
+/// The user authenticated by the `Authorization: Bearer <access token>` header,
+/// or by an API key on routes that accept them.
+#[derive(Debug, Clone, Copy)]
+pub struct AuthUser {
+    pub user_id: Uuid,
+}
+
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(identity) = parts.extensions.get::<ApiKeyIdentity>() {
+            if !identity.granted {
+                return Err(forbidden("API keys are not accepted on this route"));
+            }
+            return Ok(AuthUser {
+                user_id: identity.user_id,
+            });
+        }
+        let verifier = parts
+            .extensions
+            .get::<TokenVerifier>()
+            .ok_or(AppError::Internal(ErrorBody {
+                code: "500",
+                message: "Token verification is not configured".to_string(),
+            }))?;
+        let value = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .ok_or(unauthorized("Missing bearer token"))?
+            .to_str()
+            .map_err(|_| unauthorized("Authorization must be visible ASCII"))?;
+        // The scheme name is case-insensitive.
+        let token = value
+            .split_once(' ')
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("Bearer"))
+            .map(|(_, token)| token.trim())
+            .ok_or(unauthorized("Authorization must use the Bearer scheme"))?;
+        let user_id = verifier.verify(token, TokenKind::Access)?;
+        Ok(AuthUser { user_id })
+    }
+}
+
+/// Attached to requests authenticated with `Authorization: ApiKey <key>`.
+#[derive(Debug, Clone)]
+pub struct ApiKeyIdentity {
+    pub user_id: Uuid,
+    pub scopes: Vec<ApiKeyScope>,
+    /// Set by [`require_scope`]; routes that never check scopes refuse API keys.
+    granted: bool,
+}
+
+pub struct ApiKeyAuthState<C, U> {
+    pub api_key_usecase: Arc<U>,
+    pub db: Arc<C>,
+}
+
+impl<C, U> Clone for ApiKeyAuthState<C, U> {
+    fn clone(&self) -> Self {
+        Self {
+            api_key_usecase: Arc::clone(&self.api_key_usecase),
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+/// Middleware resolving `Authorization: ApiKey <key>` into an [`ApiKeyIdentity`].
+/// Requests using any other scheme pass through untouched.
+pub async fn authenticate_api_key<C, U>(
+    State(state): State<ApiKeyAuthState<C, U>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError>
+where
+    C: Conn + 'static,
+    U: ApiKeyUsecase + Send + Sync + 'static,
+{
+    let key = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("ApiKey"))
+        .map(|(_, key)| key.trim().to_string());
+    if let Some(key) = key {
+        let api_key = state
+            .api_key_usecase
+            .authenticate(state.db.as_ref(), &key)
+            .await?;
+        request.extensions_mut().insert(ApiKeyIdentity {
+            user_id: api_key.user_id,
+            scopes: api_key.scopes,
+            granted: false,
+        });
+    }
+    Ok(next.run(request).await)
+}
+
+/// Route middleware letting API keys through only when they carry `scope`.
+/// Requests authenticated by other means are not affected.
+pub async fn require_scope(
+    State(scope): State<ApiKeyScope>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if let Some(identity) = request.extensions_mut().get_mut::<ApiKeyIdentity>() {
+        if !identity.scopes.contains(&scope) {
+            return Err(forbidden(&format!("API key lacks the {} scope", scope)));
+        }
+        identity.granted = true;
+    }
+    Ok(next.run(request).await)
+}
+
+fn forbidden(message: &str) -> AppError {
+    AppError::Forbidden(ErrorBody {
+        code: "403",
+        message: format!("Forbidden: {}", message),
+    })
+}
+
+fn unauthorized(message: &str) -> AppError {
+    AppError::Unauthorized(ErrorBody {
+        code: "401",
+        message: format!("Unauthorized: {}", message),
+    })
+}
@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::{Json, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::post,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application_service::{service::token_service::TokenPair, usecase::auth_usecase::AuthUsecase},
+    domain::{
+        models::user::{EMAIL_MAX_LENGTH, PASSWORD_MAX_LENGTH, PASSWORD_MIN_LENGTH, User},
+        repositories::conn::Conn,
+    },
+    presentation::{errors::AppError, validator::ValidatedJson},
+};
+
+pub struct AppState<C, U> {
+    auth_usecase: Arc<U>,
+    db: Arc<C>,
+}
+
+impl<C, U> Clone for AppState<C, U> {
+    fn clone(&self) -> Self {
+        Self {
+            auth_usecase: Arc::clone(&self.auth_usecase),
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+pub fn create_auth_router<C, U>(auth_usecase: Arc<U>, db: Arc<C>) -> Router
+where
+    C: Conn + 'static,
+    U: AuthUsecase + Send + Sync + 'static,
+{
+    let app_state: AppState<C, U> = AppState { auth_usecase, db };
+
+    Router::new()
+        .route("/register", post(register::<C, U>))
+        .route("/login", post(login::<C, U>))
+        .route("/refresh", post(refresh::<C, U>))
+        .with_state(app_state)
+}
+
+#[derive(Serialize)]
+struct UserResponse {
+    id: Uuid,
+    email: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            email: user.email,
+            created_at: user.created_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: String,
+    token_type: &'static str,
+    /// Seconds until the access token expires.
+    expires_in: u64,
+}
+
+impl From<TokenPair> for TokenResponse {
+    fn from(pair: TokenPair) -> Self {
+        Self {
+            access_token: pair.access_token,
+            refresh_token: pair.refresh_token,
+            token_type: "Bearer",
+            expires_in: pair.expires_in,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate)]
+struct RegisterRequest {
+    #[validate(
+        email(message = "Email must be a valid email address"),
+        length(max = EMAIL_MAX_LENGTH)
+    )]
+    email: String,
+    #[validate(length(
+        min = PASSWORD_MIN_LENGTH,
+        max = PASSWORD_MAX_LENGTH,
+        message = "Password must be between 8 and 128 characters"
+    ))]
+    password: String,
+}
+
+#[derive(Deserialize, Validate)]
+struct LoginRequest {
+    #[validate(length(min = 1, max = EMAIL_MAX_LENGTH))]
+    email: String,
+    #[validate(length(min = 1, max = PASSWORD_MAX_LENGTH))]
+    password: String,
+}
+
+#[derive(Deserialize, Validate)]
+struct RefreshRequest {
+    #[validate(length(min = 1))]
+    refresh_token: String,
+}
+
+async fn register<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    ValidatedJson(input): ValidatedJson<RegisterRequest>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: AuthUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let user = app_state
+        .auth_usecase
+        .register(conn, input.email, input.password)
+        .await?;
+    Ok((StatusCode::CREATED, Json(UserResponse::from(user))))
+}
+
+async fn login<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    ValidatedJson(input): ValidatedJson<LoginRequest>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: AuthUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let tokens = app_state
+        .auth_usecase
+        .login(conn, input.email, input.password)
+        .await?;
+    Ok((StatusCode::OK, Json(TokenResponse::from(tokens))))
+}
+
+async fn refresh<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    ValidatedJson(input): ValidatedJson<RefreshRequest>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: AuthUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let tokens = app_state
+        .auth_usecase
+        .refresh(conn, input.refresh_token)
+        .await?;
+    Ok((StatusCode::OK, Json(TokenResponse::from(tokens))))
+}
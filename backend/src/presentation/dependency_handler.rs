@@ -0,0 +1,182 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get},
+};
+use axum_extra::extract::WithRejection;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    application_service::usecase::dependency_usecase::DependencyUsecase,
+    domain::{
+        models::{
+            dependency::{Dependency, DependencyGraph},
+            todo::Todo,
+        },
+        repositories::conn::Conn,
+    },
+    presentation::{auth::AuthUser, errors::AppError, todo_handler::TodoResponse},
+};
+
+pub struct AppState<C, U> {
+    dependency_usecase: Arc<U>,
+    db: Arc<C>,
+}
+
+impl<C, U> Clone for AppState<C, U> {
+    fn clone(&self) -> Self {
+        Self {
+            dependency_usecase: Arc::clone(&self.dependency_usecase),
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+/// Routes for the dependencies of a single todo, meant to be nested under `/todos/{id}`.
+pub fn create_dependency_router<C, U>(dependency_usecase: Arc<U>, db: Arc<C>) -> Router
+where
+    C: Conn + 'static,
+    U: DependencyUsecase + Send + Sync + 'static,
+{
+    let app_state: AppState<C, U> = AppState {
+        dependency_usecase,
+        db,
+    };
+
+    Router::new()
+        .route(
+            "/blockers",
+            get(get_blockers::<C, U>).post(add_blocker::<C, U>),
+        )
+        .route("/blockers/{blocker_id}", delete(remove_blocker::<C, U>))
+        .route("/graph", get(get_graph::<C, U>))
+        .with_state(app_state)
+}
+
+#[derive(Serialize)]
+struct BlockerListResponse {
+    blockers: Vec<TodoResponse>,
+}
+
+impl From<Vec<Todo>> for BlockerListResponse {
+    fn from(blockers: Vec<Todo>) -> Self {
+        Self {
+            blockers: blockers.into_iter().map(TodoResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DependencyResponse {
+    todo_id: Uuid,
+    blocker_id: Uuid,
+}
+
+impl From<Dependency> for DependencyResponse {
+    fn from(dependency: Dependency) -> Self {
+        Self {
+            todo_id: dependency.todo_id,
+            blocker_id: dependency.blocker_id,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct GraphResponse {
+    root: Uuid,
+    nodes: Vec<TodoResponse>,
+    edges: Vec<DependencyResponse>,
+}
+
+impl From<DependencyGraph> for GraphResponse {
+    fn from(graph: DependencyGraph) -> Self {
+        Self {
+            root: graph.root,
+            nodes: graph.todos.into_iter().map(TodoResponse::from).collect(),
+            edges: graph
+                .edges
+                .into_iter()
+                .map(DependencyResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct AddBlockerRequest {
+    blocker_id: Uuid,
+}
+
+async fn get_blockers<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(todo_id), _): WithRejection<Path<Uuid>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: DependencyUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let blockers = app_state
+        .dependency_usecase
+        .get_blockers(conn, user.user_id, todo_id)
+        .await?;
+    Ok((StatusCode::OK, Json(BlockerListResponse::from(blockers))))
+}
+
+async fn add_blocker<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(todo_id), _): WithRejection<Path<Uuid>, AppError>,
+    WithRejection(Json(input), _): WithRejection<Json<AddBlockerRequest>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: DependencyUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let blockers = app_state
+        .dependency_usecase
+        .add_blocker(conn, user.user_id, todo_id, input.blocker_id)
+        .await?;
+    Ok((StatusCode::OK, Json(BlockerListResponse::from(blockers))))
+}
+
+async fn remove_blocker<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path((todo_id, blocker_id)), _): WithRejection<Path<(Uuid, Uuid)>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: DependencyUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    app_state
+        .dependency_usecase
+        .remove_blocker(conn, user.user_id, todo_id, blocker_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_graph<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(todo_id), _): WithRejection<Path<Uuid>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: DependencyUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let graph = app_state
+        .dependency_usecase
+        .get_graph(conn, user.user_id, todo_id)
+        .await?;
+    Ok((StatusCode::OK, Json(GraphResponse::from(graph))))
+}
@@ -1,4 +1,8 @@
-use axum::{Json, http::StatusCode, response::IntoResponse};
+use axum::{
+    Json,
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
 use serde::Serialize;
 
 use crate::application_service::usecase::errors::UsecaseError;
@@ -13,8 +17,12 @@ pub struct ErrorBody {
 pub enum AppError {
     Timeout,
     BadRequest(ErrorBody),
+    Unauthorized(ErrorBody),
+    Forbidden(ErrorBody),
     NotFound(ErrorBody),
     Conflict(ErrorBody),
+    PreconditionFailed(ErrorBody),
+    UnprocessableEntity(ErrorBody),
     Internal(ErrorBody),
 }
 
@@ -29,6 +37,22 @@ impl From<UsecaseError> for AppError {
                 code: "409",
                 message: format!("Conflict: {}", msg),
             }),
+            UsecaseError::Validation(msg) => AppError::BadRequest(ErrorBody {
+                code: "400",
+                message: format!("Validation failed: {}", msg),
+            }),
+            UsecaseError::PreconditionFailed(msg) => AppError::PreconditionFailed(ErrorBody {
+                code: "412",
+                message: format!("Precondition Failed: {}", msg),
+            }),
+            UsecaseError::Unauthorized(msg) => AppError::Unauthorized(ErrorBody {
+                code: "401",
+                message: format!("Unauthorized: {}", msg),
+            }),
+            UsecaseError::Forbidden(msg) => AppError::Forbidden(ErrorBody {
+                code: "403",
+                message: format!("Forbidden: {}", msg),
+            }),
             UsecaseError::Unexpected(msg) => AppError::Internal(ErrorBody {
                 code: "500",
                 message: format!("Internal Server Error: {}", msg),
@@ -37,23 +61,36 @@ impl From<UsecaseError> for AppError {
     }
 }
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> axum::response::Response {
+impl AppError {
+    pub fn into_parts(self) -> (StatusCode, ErrorBody) {
         match self {
             AppError::Timeout => (
                 StatusCode::REQUEST_TIMEOUT,
-                Json(ErrorBody {
+                ErrorBody {
                     code: "408",
                     message: "Request took too long".to_string(),
-                }),
-            )
-                .into_response(),
-            AppError::BadRequest(body) => (StatusCode::BAD_REQUEST, Json(body)).into_response(),
-            AppError::NotFound(body) => (StatusCode::NOT_FOUND, Json(body)).into_response(),
-            AppError::Conflict(body) => (StatusCode::CONFLICT, Json(body)).into_response(),
-            AppError::Internal(body) => {
-                (StatusCode::INTERNAL_SERVER_ERROR, Json(body)).into_response()
-            }
+                },
+            ),
+            AppError::BadRequest(body) => (StatusCode::BAD_REQUEST, body),
+            AppError::Unauthorized(body) => (StatusCode::UNAUTHORIZED, body),
+            AppError::Forbidden(body) => (StatusCode::FORBIDDEN, body),
+            AppError::NotFound(body) => (StatusCode::NOT_FOUND, body),
+            AppError::Conflict(body) => (StatusCode::CONFLICT, body),
+            AppError::PreconditionFailed(body) => (StatusCode::PRECONDITION_FAILED, body),
+            AppError::UnprocessableEntity(body) => (StatusCode::UNPROCESSABLE_ENTITY, body),
+            AppError::Internal(body) => (StatusCode::INTERNAL_SERVER_ERROR, body),
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> axum::response::Response {
+        let unauthorized = matches!(self, AppError::Unauthorized(_));
+        let (status, body) = self.into_parts();
+        if unauthorized {
+            // RFC 6750: tell the client which authentication scheme to use.
+            return (status, [(header::WWW_AUTHENTICATE, "Bearer")], Json(body)).into_response();
         }
+        (status, Json(body)).into_response()
     }
 }
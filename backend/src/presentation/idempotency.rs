@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::{FromRequestParts, Request, State},
+    http::{HeaderName, header, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    application_service::usecase::idempotency_usecase::{IdempotencyOutcome, IdempotencyUsecase},
+    domain::{models::idempotency::StoredResponse, repositories::conn::Conn},
+    presentation::{
+        auth::AuthUser,
+        errors::{AppError, ErrorBody},
+    },
+};
+
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+/// Set on responses sent again for a repeated request.
+const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+/// Requests with a key are buffered to fingerprint them.
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+pub struct IdempotencyState<C, I> {
+    pub idempotency_usecase: Arc<I>,
+    pub db: Arc<C>,
+}
+
+impl<C, I> Clone for IdempotencyState<C, I> {
+    fn clone(&self) -> Self {
+        Self {
+            idempotency_usecase: Arc::clone(&self.idempotency_usecase),
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+/// Route middleware honouring the `Idempotency-Key` header. The first request
+/// with a key runs; repeating it with the same method, URI, `If-Match` and
+/// body replays the response, while reusing the key for anything else is
+/// refused. Server errors are not kept, so those requests can be retried; a
+/// request that never finished keeps its key, as its change may have been made.
+pub async fn idempotent<C, I>(
+    State(state): State<IdempotencyState<C, I>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError>
+where
+    C: Conn + 'static,
+    I: IdempotencyUsecase,
+{
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY) else {
+        return Ok(next.run(request).await);
+    };
+    let key = key
+        .to_str()
+        .map_err(|_| bad_request("Idempotency-Key must be visible ASCII"))?
+        .to_string();
+    let (mut parts, body) = request.into_parts();
+    let user = AuthUser::from_request_parts(&mut parts, &()).await?;
+    let body = to_bytes(body, MAX_BODY_SIZE)
+        .await
+        .map_err(|_| bad_request("Request body is too large"))?;
+
+    let conn = state.db.as_ref();
+    let usecase = state.idempotency_usecase.as_ref();
+    match usecase
+        .begin(conn, user.user_id, key.clone(), fingerprint(&parts, &body))
+        .await?
+    {
+        IdempotencyOutcome::Proceed => {}
+        IdempotencyOutcome::Replay(response) => return replay(response),
+        IdempotencyOutcome::InProgress => {
+            return Err(AppError::Conflict(ErrorBody {
+                code: "409",
+                message: "Conflict: A request with this Idempotency-Key is still in progress or did not finish"
+                    .to_string(),
+            }));
+        }
+        IdempotencyOutcome::Mismatch => {
+            return Err(AppError::UnprocessableEntity(ErrorBody {
+                code: "422",
+                message:
+                    "Unprocessable Entity: Idempotency-Key was already used for a different request"
+                        .to_string(),
+            }));
+        }
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    if response.status().is_server_error() {
+        usecase.abandon(conn, user.user_id, &key).await?;
+        return Ok(response);
+    }
+    let (parts, body) = response.into_parts();
+    let body = to_bytes(body, usize::MAX).await.map_err(|err| {
+        AppError::Internal(ErrorBody {
+            code: "500",
+            message: format!("Internal Server Error: {}", err),
+        })
+    })?;
+    let stored = StoredResponse {
+        status: parts.status.as_u16(),
+        headers: parts
+            .headers
+            .iter()
+            .filter(|(name, _)| *name != header::CONTENT_LENGTH)
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect(),
+        body: body.to_vec(),
+    };
+    // The change is made either way; failing the request now would only
+    // invite a retry. The key stays claimed, so that retry is refused rather
+    // than run again.
+    if let Err(err) = usecase.complete(conn, user.user_id, &key, stored).await {
+        eprintln!(
+            "Failed to store the response for an Idempotency-Key: {}",
+            err
+        );
+    }
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+fn fingerprint(parts: &Parts, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(parts.method.as_str());
+    hasher.update(b" ");
+    hasher.update(parts.uri.path_and_query().map_or("", |uri| uri.as_str()));
+    hasher.update(b"\n");
+    if let Some(if_match) = parts.headers.get(header::IF_MATCH) {
+        hasher.update(if_match.as_bytes());
+    }
+    hasher.update(b"\n");
+    hasher.update(body);
+    format!("{:x}", hasher.finalize())
+}
+
+fn replay(response: StoredResponse) -> Result<Response, AppError> {
+    let mut builder = Response::builder().status(response.status);
+    for (name, value) in response.headers {
+        builder = builder.header(name, value);
+    }
+    builder
+        .header(IDEMPOTENT_REPLAYED, "true")
+        .body(Body::from(response.body))
+        .map_err(|err| {
+            AppError::Internal(ErrorBody {
+                code: "500",
+                message: format!("Internal Server Error: {}", err),
+            })
+        })
+}
+
+fn bad_request(message: &str) -> AppError {
+    AppError::BadRequest(ErrorBody {
+        code: "400",
+        message: message.to_string(),
+    })
+}
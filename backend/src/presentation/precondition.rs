@@ -0,0 +1,56 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts},
+};
+
+use crate::presentation::errors::{AppError, ErrorBody};
+
+/// Strong entity tag for a todo at the given version.
+pub fn etag(version: i32) -> String {
+    format!("\"{}\"", version)
+}
+
+/// Version requested by the `If-Match` header, or `None` when the header is absent or `*`.
+pub struct IfMatch(pub Option<i32>);
+
+impl<S> FromRequestParts<S> for IfMatch
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(header::IF_MATCH) else {
+            return Ok(IfMatch(None));
+        };
+        let value = value
+            .to_str()
+            .map_err(|_| invalid_if_match("If-Match must be visible ASCII"))?
+            .trim();
+        if value == "*" {
+            return Ok(IfMatch(None));
+        }
+        if value.contains(',') {
+            return Err(invalid_if_match(
+                "If-Match must contain a single entity tag",
+            ));
+        }
+        // Weak or foreign tags can never pass the strong comparison If-Match requires.
+        value
+            .strip_prefix('"')
+            .and_then(|tag| tag.strip_suffix('"'))
+            .and_then(|tag| tag.parse::<i32>().ok())
+            .map(|version| IfMatch(Some(version)))
+            .ok_or(AppError::PreconditionFailed(ErrorBody {
+                code: "412",
+                message: format!("Precondition Failed: If-Match {} does not match", value),
+            }))
+    }
+}
+
+fn invalid_if_match(message: &str) -> AppError {
+    AppError::BadRequest(ErrorBody {
+        code: "400",
+        message: message.to_string(),
+    })
+}
@@ -0,0 +1,238 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+};
+use axum_extra::extract::WithRejection;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application_service::usecase::project_usecase::{ProjectInput, ProjectUsecase},
+    domain::{
+        models::project::{PROJECT_NAME_MAX_LENGTH, PROJECT_NAME_MIN_LENGTH, Project},
+        repositories::{conn::Conn, todo_repository::TodoQuery},
+    },
+    presentation::{
+        auth::AuthUser,
+        errors::AppError,
+        todo_handler::{DEFAULT_PAGE_LIMIT, ListTodosParams, TodoListResponse},
+        validator::{ValidatedJson, ValidatedQuery},
+    },
+};
+
+pub struct AppState<C, U> {
+    project_usecase: Arc<U>,
+    db: Arc<C>,
+}
+
+impl<C, U> Clone for AppState<C, U> {
+    fn clone(&self) -> Self {
+        Self {
+            project_usecase: Arc::clone(&self.project_usecase),
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+pub fn create_project_router<C, U>(project_usecase: Arc<U>, db: Arc<C>) -> Router
+where
+    C: Conn + 'static,
+    U: ProjectUsecase + Send + Sync + 'static,
+{
+    let app_state: AppState<C, U> = AppState {
+        project_usecase,
+        db,
+    };
+
+    Router::new()
+        .route(
+            "/",
+            get(get_all_projects::<C, U>).post(post_project::<C, U>),
+        )
+        .route(
+            "/{id}",
+            get(get_project_by_id::<C, U>)
+                .put(update_project::<C, U>)
+                .delete(delete_project::<C, U>),
+        )
+        .route("/{id}/todos", get(get_project_todos::<C, U>))
+        .with_state(app_state)
+}
+
+#[derive(Serialize)]
+struct ProjectResponse {
+    id: Uuid,
+    name: String,
+    color: Option<String>,
+    archived: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<Project> for ProjectResponse {
+    fn from(project: Project) -> Self {
+        Self {
+            id: project.id,
+            name: project.name,
+            color: project.color,
+            archived: project.archived,
+            created_at: project.created_at,
+            updated_at: project.updated_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ProjectListResponse {
+    projects: Vec<ProjectResponse>,
+}
+
+impl From<Vec<Project>> for ProjectListResponse {
+    fn from(projects: Vec<Project>) -> Self {
+        Self {
+            projects: projects.into_iter().map(ProjectResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Deserialize, Validate)]
+struct ProjectRequest {
+    #[validate(length(min = PROJECT_NAME_MIN_LENGTH, max = PROJECT_NAME_MAX_LENGTH))]
+    name: String,
+    color: Option<String>,
+    #[serde(default)]
+    archived: bool,
+}
+
+impl From<ProjectRequest> for ProjectInput {
+    fn from(request: ProjectRequest) -> Self {
+        Self {
+            name: request.name,
+            color: request.color,
+            archived: request.archived,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate)]
+struct ListProjectsParams {
+    archived: Option<bool>,
+}
+
+async fn get_all_projects<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    ValidatedQuery(params): ValidatedQuery<ListProjectsParams>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: ProjectUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let projects = app_state
+        .project_usecase
+        .get_all_projects(conn, user.user_id, params.archived)
+        .await?;
+    Ok((StatusCode::OK, Json(ProjectListResponse::from(projects))))
+}
+
+async fn get_project_by_id<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: ProjectUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let project = app_state
+        .project_usecase
+        .get_project_by_id(conn, user.user_id, id)
+        .await?;
+    Ok((StatusCode::OK, Json(ProjectResponse::from(project))))
+}
+
+async fn post_project<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    ValidatedJson(input): ValidatedJson<ProjectRequest>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: ProjectUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let project = app_state
+        .project_usecase
+        .create_project(conn, user.user_id, ProjectInput::from(input))
+        .await?;
+    Ok((StatusCode::CREATED, Json(ProjectResponse::from(project))))
+}
+
+async fn update_project<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    ValidatedJson(input): ValidatedJson<ProjectRequest>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: ProjectUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let project = app_state
+        .project_usecase
+        .update_project(conn, user.user_id, id, ProjectInput::from(input))
+        .await?;
+    Ok((StatusCode::OK, Json(ProjectResponse::from(project))))
+}
+
+async fn delete_project<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: ProjectUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    app_state
+        .project_usecase
+        .delete_project(conn, user.user_id, id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_project_todos<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    ValidatedQuery(params): ValidatedQuery<ListTodosParams>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: ProjectUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let page = app_state
+        .project_usecase
+        .get_project_todos(
+            conn,
+            user.user_id,
+            id,
+            TodoQuery::from(&params),
+            params.after,
+            params.limit.unwrap_or(DEFAULT_PAGE_LIMIT),
+        )
+        .await?;
+    Ok((StatusCode::OK, Json(TodoListResponse::from(page))))
+}
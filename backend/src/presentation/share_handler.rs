@@ -0,0 +1,246 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, patch},
+};
+use axum_extra::extract::WithRejection;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    application_service::usecase::share_usecase::ShareUsecase,
+    domain::{
+        models::share::{ShareGrant, ShareRole, ShareTarget},
+        repositories::conn::Conn,
+    },
+    presentation::{auth::AuthUser, errors::AppError},
+};
+
+pub struct AppState<C, U> {
+    share_usecase: Arc<U>,
+    db: Arc<C>,
+    /// Builds the target from the id in the path the router is nested under.
+    target: fn(Uuid) -> ShareTarget,
+}
+
+impl<C, U> Clone for AppState<C, U> {
+    fn clone(&self) -> Self {
+        Self {
+            share_usecase: Arc::clone(&self.share_usecase),
+            db: Arc::clone(&self.db),
+            target: self.target,
+        }
+    }
+}
+
+/// Routes for the grants on a single todo, meant to be nested under `/todos/{id}/shares`.
+pub fn create_todo_share_router<C, U>(share_usecase: Arc<U>, db: Arc<C>) -> Router
+where
+    C: Conn + 'static,
+    U: ShareUsecase + Send + Sync + 'static,
+{
+    create_share_router(share_usecase, db, ShareTarget::Todo)
+}
+
+/// Routes for the grants on a project, meant to be nested under `/projects/{id}/shares`.
+pub fn create_project_share_router<C, U>(share_usecase: Arc<U>, db: Arc<C>) -> Router
+where
+    C: Conn + 'static,
+    U: ShareUsecase + Send + Sync + 'static,
+{
+    create_share_router(share_usecase, db, ShareTarget::Project)
+}
+
+fn create_share_router<C, U>(
+    share_usecase: Arc<U>,
+    db: Arc<C>,
+    target: fn(Uuid) -> ShareTarget,
+) -> Router
+where
+    C: Conn + 'static,
+    U: ShareUsecase + Send + Sync + 'static,
+{
+    let app_state: AppState<C, U> = AppState {
+        share_usecase,
+        db,
+        target,
+    };
+
+    Router::new()
+        .route("/", get(get_shares::<C, U>).post(post_share::<C, U>))
+        .route(
+            "/{share_id}",
+            patch(patch_share::<C, U>).delete(revoke_share::<C, U>),
+        )
+        .with_state(app_state)
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum RoleParam {
+    Viewer,
+    Editor,
+    Owner,
+}
+
+impl From<RoleParam> for ShareRole {
+    fn from(role: RoleParam) -> Self {
+        match role {
+            RoleParam::Viewer => ShareRole::Viewer,
+            RoleParam::Editor => ShareRole::Editor,
+            RoleParam::Owner => ShareRole::Owner,
+        }
+    }
+}
+
+impl From<ShareRole> for RoleParam {
+    fn from(role: ShareRole) -> Self {
+        match role {
+            ShareRole::Viewer => RoleParam::Viewer,
+            ShareRole::Editor => RoleParam::Editor,
+            ShareRole::Owner => RoleParam::Owner,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ShareResponse {
+    id: Uuid,
+    owner_id: Uuid,
+    user_id: Uuid,
+    todo_id: Option<Uuid>,
+    project_id: Option<Uuid>,
+    role: RoleParam,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<ShareGrant> for ShareResponse {
+    fn from(grant: ShareGrant) -> Self {
+        let (todo_id, project_id) = match grant.target {
+            ShareTarget::Todo(todo_id) => (Some(todo_id), None),
+            ShareTarget::Project(project_id) => (None, Some(project_id)),
+        };
+        Self {
+            id: grant.id,
+            owner_id: grant.owner_id,
+            user_id: grant.user_id,
+            todo_id,
+            project_id,
+            role: grant.role.into(),
+            created_at: grant.created_at,
+            updated_at: grant.updated_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ShareListResponse {
+    shares: Vec<ShareResponse>,
+}
+
+impl From<Vec<ShareGrant>> for ShareListResponse {
+    fn from(grants: Vec<ShareGrant>) -> Self {
+        Self {
+            shares: grants.into_iter().map(ShareResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ShareRequest {
+    user_id: Uuid,
+    role: RoleParam,
+}
+
+#[derive(Deserialize)]
+struct ShareRoleRequest {
+    role: RoleParam,
+}
+
+async fn get_shares<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: ShareUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let grants = app_state
+        .share_usecase
+        .get_shares(conn, user.user_id, (app_state.target)(id))
+        .await?;
+    Ok((StatusCode::OK, Json(ShareListResponse::from(grants))))
+}
+
+async fn post_share<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    WithRejection(Json(input), _): WithRejection<Json<ShareRequest>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: ShareUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let grant = app_state
+        .share_usecase
+        .share(
+            conn,
+            user.user_id,
+            (app_state.target)(id),
+            input.user_id,
+            input.role.into(),
+        )
+        .await?;
+    Ok((StatusCode::CREATED, Json(ShareResponse::from(grant))))
+}
+
+async fn patch_share<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path((id, share_id)), _): WithRejection<Path<(Uuid, Uuid)>, AppError>,
+    WithRejection(Json(input), _): WithRejection<Json<ShareRoleRequest>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: ShareUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let grant = app_state
+        .share_usecase
+        .change_share_role(
+            conn,
+            user.user_id,
+            (app_state.target)(id),
+            share_id,
+            input.role.into(),
+        )
+        .await?;
+    Ok((StatusCode::OK, Json(ShareResponse::from(grant))))
+}
+
+async fn revoke_share<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path((id, share_id)), _): WithRejection<Path<(Uuid, Uuid)>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: ShareUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    app_state
+        .share_usecase
+        .revoke_share(conn, user.user_id, (app_state.target)(id), share_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
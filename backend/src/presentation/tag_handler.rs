@@ -0,0 +1,261 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get},
+};
+use axum_extra::extract::WithRejection;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application_service::usecase::tag_usecase::{TagInput, TagUsecase},
+    domain::{
+        models::tag::{TAG_NAME_MAX_LENGTH, TAG_NAME_MIN_LENGTH, Tag},
+        repositories::conn::Conn,
+    },
+    presentation::{auth::AuthUser, errors::AppError, validator::ValidatedJson},
+};
+
+pub struct AppState<C, U> {
+    tag_usecase: Arc<U>,
+    db: Arc<C>,
+}
+
+impl<C, U> Clone for AppState<C, U> {
+    fn clone(&self) -> Self {
+        Self {
+            tag_usecase: Arc::clone(&self.tag_usecase),
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+pub fn create_tag_router<C, U>(tag_usecase: Arc<U>, db: Arc<C>) -> Router
+where
+    C: Conn + 'static,
+    U: TagUsecase + Send + Sync + 'static,
+{
+    let app_state: AppState<C, U> = AppState { tag_usecase, db };
+
+    Router::new()
+        .route("/", get(get_all_tags::<C, U>).post(post_tag::<C, U>))
+        .route(
+            "/{id}",
+            get(get_tag_by_id::<C, U>)
+                .put(update_tag::<C, U>)
+                .delete(delete_tag::<C, U>),
+        )
+        .with_state(app_state)
+}
+
+/// Routes for the tags of a single todo, meant to be nested under `/todos/{id}/tags`.
+pub fn create_todo_tag_router<C, U>(tag_usecase: Arc<U>, db: Arc<C>) -> Router
+where
+    C: Conn + 'static,
+    U: TagUsecase + Send + Sync + 'static,
+{
+    let app_state: AppState<C, U> = AppState { tag_usecase, db };
+
+    Router::new()
+        .route("/", get(get_todo_tags::<C, U>).post(attach_tag::<C, U>))
+        .route("/{tag_id}", delete(detach_tag::<C, U>))
+        .with_state(app_state)
+}
+
+#[derive(Serialize)]
+struct TagResponse {
+    id: Uuid,
+    name: String,
+    color: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<Tag> for TagResponse {
+    fn from(tag: Tag) -> Self {
+        Self {
+            id: tag.id,
+            name: tag.name,
+            color: tag.color,
+            created_at: tag.created_at,
+            updated_at: tag.updated_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct TagListResponse {
+    tags: Vec<TagResponse>,
+}
+
+impl From<Vec<Tag>> for TagListResponse {
+    fn from(tags: Vec<Tag>) -> Self {
+        Self {
+            tags: tags.into_iter().map(TagResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Deserialize, Validate)]
+struct TagRequest {
+    #[validate(length(min = TAG_NAME_MIN_LENGTH, max = TAG_NAME_MAX_LENGTH))]
+    name: String,
+    color: Option<String>,
+}
+
+impl From<TagRequest> for TagInput {
+    fn from(request: TagRequest) -> Self {
+        Self {
+            name: request.name,
+            color: request.color,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct AttachTagRequest {
+    tag_id: Uuid,
+}
+
+async fn get_all_tags<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TagUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let tags = app_state
+        .tag_usecase
+        .get_all_tags(conn, user.user_id)
+        .await?;
+    Ok((StatusCode::OK, Json(TagListResponse::from(tags))))
+}
+
+async fn get_tag_by_id<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TagUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let tag = app_state
+        .tag_usecase
+        .get_tag_by_id(conn, user.user_id, id)
+        .await?;
+    Ok((StatusCode::OK, Json(TagResponse::from(tag))))
+}
+
+async fn post_tag<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    ValidatedJson(input): ValidatedJson<TagRequest>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TagUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let tag = app_state
+        .tag_usecase
+        .create_tag(conn, user.user_id, TagInput::from(input))
+        .await?;
+    Ok((StatusCode::CREATED, Json(TagResponse::from(tag))))
+}
+
+async fn update_tag<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    ValidatedJson(input): ValidatedJson<TagRequest>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TagUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let tag = app_state
+        .tag_usecase
+        .update_tag(conn, user.user_id, id, TagInput::from(input))
+        .await?;
+    Ok((StatusCode::OK, Json(TagResponse::from(tag))))
+}
+
+async fn delete_tag<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TagUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    app_state
+        .tag_usecase
+        .delete_tag(conn, user.user_id, id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_todo_tags<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(todo_id), _): WithRejection<Path<Uuid>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TagUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let tags = app_state
+        .tag_usecase
+        .get_todo_tags(conn, user.user_id, todo_id)
+        .await?;
+    Ok((StatusCode::OK, Json(TagListResponse::from(tags))))
+}
+
+async fn attach_tag<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(todo_id), _): WithRejection<Path<Uuid>, AppError>,
+    WithRejection(Json(input), _): WithRejection<Json<AttachTagRequest>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TagUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let tags = app_state
+        .tag_usecase
+        .attach_tag(conn, user.user_id, todo_id, input.tag_id)
+        .await?;
+    Ok((StatusCode::OK, Json(TagListResponse::from(tags))))
+}
+
+async fn detach_tag<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path((todo_id, tag_id)), _): WithRejection<Path<(Uuid, Uuid)>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TagUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    app_state
+        .tag_usecase
+        .detach_tag(conn, user.user_id, todo_id, tag_id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,23 +1,60 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use axum::{
     Router,
-    extract::{Json, Path, State},
-    http::StatusCode,
-    response::IntoResponse,
-    routing::{get, put},
+    extract::{FromRequestParts, Json, Path, Query, State},
+    http::{StatusCode, header, request::Parts},
+    middleware,
+    response::{
+        IntoResponse,
+        sse::{Event, KeepAlive, Sse},
+    },
+    routing::{delete, get, post, put},
 };
 use axum_extra::extract::WithRejection;
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use tower::ServiceBuilder;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationError, ValidationErrors};
 
 use crate::{
-    application_service::usecase::todo_usecase::TodoUsecase,
-    domain::{models::todo::Todo, repositories::conn::Conn},
-    presentation::{errors::AppError, validator::ValidatedJson},
+    application_service::usecase::{
+        idempotency_usecase::IdempotencyUsecase,
+        todo_usecase::{BatchOperation, SearchPage, TodoInput, TodoPage, TodoUsecase},
+    },
+    domain::{
+        models::{
+            api_key::ApiKeyScope,
+            checklist_item::{ChecklistItem, ITEM_TITLE_MAX_LENGTH, ITEM_TITLE_MIN_LENGTH},
+            recurrence::RECURRENCE_MAX_LENGTH,
+            tag::{TAG_NAME_MAX_LENGTH, TAG_NAME_MIN_LENGTH},
+            todo::{
+                DESCRIPTION_MAX_LENGTH, Priority, TITLE_MAX_LENGTH, TITLE_MIN_LENGTH, Todo,
+                TodoPatch,
+            },
+        },
+        repositories::{
+            conn::Conn,
+            todo_repository::{SortOrder, TodoQuery, TodoSortKey},
+        },
+    },
+    presentation::{
+        auth::{AuthUser, require_scope},
+        errors::{AppError, ErrorBody},
+        idempotency::{IdempotencyState, idempotent},
+        precondition::{IfMatch, etag},
+        validator::{ValidatedJson, ValidatedQuery},
+    },
 };
 
+pub(crate) const DEFAULT_PAGE_LIMIT: u64 = 20;
+const MAX_BATCH_OPERATIONS: usize = 100;
+/// Keeps proxies and clients that drop idle connections from closing quiet
+/// streams. The request timeout does not apply: it only bounds the wait for
+/// the response to start, not its body.
+const EVENT_STREAM_KEEP_ALIVE: Duration = Duration::from_secs(5);
+
 pub struct AppState<C, U> {
     todo_usecase: Arc<U>,
     db: Arc<C>,
@@ -32,95 +69,725 @@ impl<C, U> Clone for AppState<C, U> {
     }
 }
 
-pub fn create_todo_router<C, U>(todo_usecase: Arc<U>, db: Arc<C>) -> Router
+pub fn create_todo_router<C, U, I>(
+    todo_usecase: Arc<U>,
+    idempotency_usecase: Arc<I>,
+    db: Arc<C>,
+) -> Router
 where
     C: Conn + 'static,
     U: TodoUsecase + Send + Sync + 'static,
+    I: IdempotencyUsecase,
 {
+    let idempotency = IdempotencyState {
+        idempotency_usecase,
+        db: db.clone(),
+    };
     let app_state: AppState<C, U> = AppState { todo_usecase, db };
+    // API keys need `todos:read` for reads and `todos:write` for everything else.
+    // Writes may also be made idempotent with an `Idempotency-Key`.
+    let read = || middleware::from_fn_with_state(ApiKeyScope::TodosRead, require_scope);
+    let write = || {
+        ServiceBuilder::new()
+            .layer(middleware::from_fn_with_state(
+                ApiKeyScope::TodosWrite,
+                require_scope,
+            ))
+            .layer(middleware::from_fn_with_state(
+                idempotency.clone(),
+                idempotent::<C, I>,
+            ))
+    };
 
     Router::new()
-        .route("/", get(get_all_todos::<C, U>).post(post_todo::<C, U>))
+        .route(
+            "/",
+            get(get_all_todos::<C, U>)
+                .route_layer(read())
+                .merge(post(post_todo::<C, U>).route_layer(write())),
+        )
+        .route("/trash", get(get_trashed_todos::<C, U>).route_layer(read()))
+        .route(
+            "/overdue",
+            get(get_overdue_todos::<C, U>).route_layer(read()),
+        )
+        .route("/search", get(search_todos::<C, U>).route_layer(read()))
+        .route("/batch", post(batch_todos::<C, U>).route_layer(write()))
+        .route("/events", get(get_todo_events::<C, U>).route_layer(read()))
         .route(
             "/{id}",
-            get(get_todo_by_id::<C, U>)
-                .put(update_todo::<C, U>)
-                .delete(delete_todo::<C, U>),
+            get(get_todo_by_id::<C, U>).route_layer(read()).merge(
+                put(update_todo::<C, U>)
+                    .patch(patch_todo::<C, U>)
+                    .delete(delete_todo::<C, U>)
+                    .route_layer(write()),
+            ),
+        )
+        .route(
+            "/{id}/complete",
+            put(mark_todo_completed::<C, U>).route_layer(write()),
+        )
+        .route(
+            "/{id}/uncomplete",
+            put(unmark_todo_completed::<C, U>).route_layer(write()),
+        )
+        .route("/{id}/move", put(move_todo::<C, U>).route_layer(write()))
+        .route(
+            "/{id}/project",
+            put(move_todo_to_project::<C, U>).route_layer(write()),
+        )
+        .route(
+            "/{id}/items",
+            get(get_checklist_items::<C, U>)
+                .route_layer(read())
+                .merge(post(add_checklist_item::<C, U>).route_layer(write())),
+        )
+        .route(
+            "/{id}/items/{item_id}",
+            put(update_checklist_item::<C, U>)
+                .delete(remove_checklist_item::<C, U>)
+                .route_layer(write()),
+        )
+        .route(
+            "/{id}/restore",
+            post(restore_todo::<C, U>).route_layer(write()),
+        )
+        .route(
+            "/{id}/purge",
+            delete(purge_todo::<C, U>).route_layer(write()),
         )
-        .route("/{id}/complete", put(mark_todo_completed::<C, U>))
-        .route("/{id}/uncomplete", put(unmark_todo_completed::<C, U>))
         .with_state(app_state)
 }
 #[derive(Serialize)]
-struct TodoResponse {
+pub(crate) struct TodoResponse {
     id: Uuid,
     title: String,
     description: Option<String>,
     completed: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i32,
+    deleted_at: Option<DateTime<Utc>>,
+    due_at: Option<DateTime<Utc>>,
+    remind_at: Option<DateTime<Utc>>,
+    priority: PriorityParam,
+    position: i64,
+    progress: ProgressResponse,
+    project_id: Option<Uuid>,
+    recurrence: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ProgressResponse {
+    completed: usize,
+    total: usize,
 }
 
 impl From<Todo> for TodoResponse {
     fn from(todo: Todo) -> Self {
+        let (completed, total) = todo.progress();
         Self {
             id: todo.id,
             title: todo.title,
             description: todo.description,
             completed: todo.completed,
+            created_at: todo.created_at,
+            updated_at: todo.updated_at,
+            version: todo.version,
+            deleted_at: todo.deleted_at,
+            due_at: todo.due_at,
+            remind_at: todo.remind_at,
+            priority: todo.priority.into(),
+            position: todo.position,
+            progress: ProgressResponse { completed, total },
+            project_id: todo.project_id,
+            recurrence: todo.recurrence.map(|rule| rule.to_string()),
+        }
+    }
+}
+
+fn todo_response(status: StatusCode, todo: Todo) -> impl IntoResponse {
+    (
+        status,
+        [(header::ETAG, etag(todo.version))],
+        Json(TodoResponse::from(todo)),
+    )
+}
+
+#[derive(Serialize)]
+struct ChecklistItemResponse {
+    id: Uuid,
+    title: String,
+    completed: bool,
+    position: i32,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<ChecklistItem> for ChecklistItemResponse {
+    fn from(item: ChecklistItem) -> Self {
+        Self {
+            id: item.id,
+            title: item.title,
+            completed: item.completed,
+            position: item.position,
+            created_at: item.created_at,
+            updated_at: item.updated_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ChecklistItemListResponse {
+    items: Vec<ChecklistItemResponse>,
+}
+
+/// Checklist responses carry the ETag of the owning todo, whose version guards them.
+fn checklist_item_response(
+    status: StatusCode,
+    todo: Todo,
+    item_id: Uuid,
+) -> Result<impl IntoResponse, AppError> {
+    let etag = etag(todo.version);
+    let item = todo
+        .items
+        .into_iter()
+        .find(|item| item.id == item_id)
+        .ok_or(AppError::Internal(ErrorBody {
+            code: "500",
+            message: format!("Checklist item with id {} vanished after saving", item_id),
+        }))?;
+    Ok((
+        status,
+        [(header::ETAG, etag)],
+        Json(ChecklistItemResponse::from(item)),
+    ))
+}
+
+#[derive(Serialize)]
+pub(crate) struct TodoListResponse {
+    todos: Vec<TodoResponse>,
+    next_cursor: Option<Uuid>,
+}
+
+impl From<TodoPage> for TodoListResponse {
+    fn from(page: TodoPage) -> Self {
+        Self {
+            todos: page.todos.into_iter().map(TodoResponse::from).collect(),
+            next_cursor: page.next_cursor,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SearchHitResponse {
+    todo: TodoResponse,
+    rank: f32,
+    /// Matching fragments with the matched terms wrapped in `<mark>` tags.
+    snippet: String,
+}
+
+#[derive(Serialize)]
+struct SearchResponse {
+    results: Vec<SearchHitResponse>,
+    next_cursor: Option<Uuid>,
+}
+
+impl From<SearchPage> for SearchResponse {
+    fn from(page: SearchPage) -> Self {
+        Self {
+            results: page
+                .hits
+                .into_iter()
+                .map(|hit| SearchHitResponse {
+                    todo: TodoResponse::from(hit.todo),
+                    rank: hit.rank,
+                    snippet: hit.snippet,
+                })
+                .collect(),
+            next_cursor: page.next_cursor,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+enum PriorityParam {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Urgent,
+}
+
+impl From<PriorityParam> for Priority {
+    fn from(priority: PriorityParam) -> Self {
+        match priority {
+            PriorityParam::Low => Priority::Low,
+            PriorityParam::Medium => Priority::Medium,
+            PriorityParam::High => Priority::High,
+            PriorityParam::Urgent => Priority::Urgent,
+        }
+    }
+}
+
+impl From<Priority> for PriorityParam {
+    fn from(priority: Priority) -> Self {
+        match priority {
+            Priority::Low => PriorityParam::Low,
+            Priority::Medium => PriorityParam::Medium,
+            Priority::High => PriorityParam::High,
+            Priority::Urgent => PriorityParam::Urgent,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+enum SortParam {
+    #[default]
+    Position,
+    CreatedAt,
+    UpdatedAt,
+    Title,
+}
+
+impl From<SortParam> for TodoSortKey {
+    fn from(sort: SortParam) -> Self {
+        match sort {
+            SortParam::Position => TodoSortKey::Position,
+            SortParam::CreatedAt => TodoSortKey::CreatedAt,
+            SortParam::UpdatedAt => TodoSortKey::UpdatedAt,
+            SortParam::Title => TodoSortKey::Title,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+enum OrderParam {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl From<OrderParam> for SortOrder {
+    fn from(order: OrderParam) -> Self {
+        match order {
+            OrderParam::Asc => SortOrder::Asc,
+            OrderParam::Desc => SortOrder::Desc,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate)]
+pub(crate) struct ListTodosParams {
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    pub(crate) limit: Option<u64>,
+    pub(crate) after: Option<Uuid>,
+    completed: Option<bool>,
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Text must be between 1 and 100 characters"
+    ))]
+    text: Option<String>,
+    created_after: Option<DateTime<Utc>>,
+    created_before: Option<DateTime<Utc>>,
+    overdue: Option<bool>,
+    due_after: Option<DateTime<Utc>>,
+    due_before: Option<DateTime<Utc>>,
+    #[validate(length(min = TAG_NAME_MIN_LENGTH, max = TAG_NAME_MAX_LENGTH))]
+    tag: Option<String>,
+    project_id: Option<Uuid>,
+    #[serde(default)]
+    sort: SortParam,
+    #[serde(default)]
+    order: OrderParam,
+}
+
+impl From<&ListTodosParams> for TodoQuery {
+    fn from(params: &ListTodosParams) -> Self {
+        Self {
+            completed: params.completed,
+            text: params.text.clone(),
+            created_after: params.created_after,
+            created_before: params.created_before,
+            overdue: params.overdue,
+            due_after: params.due_after,
+            due_before: params.due_before,
+            tag: params.tag.clone(),
+            project_id: params.project_id,
+            sort_key: params.sort.into(),
+            sort_order: params.order.into(),
         }
     }
 }
 
 #[derive(Deserialize, Validate)]
-struct CreateTodoRequest {
-    #[validate(length(min = 2, max = 100))]
+struct PageParams {
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    limit: Option<u64>,
+    after: Option<Uuid>,
+}
+
+#[derive(Deserialize, Validate)]
+struct SearchParams {
+    /// Web search syntax: quoted phrases, `or` and `-` for exclusion.
+    #[validate(length(
+        min = 1,
+        max = 200,
+        message = "Query must be between 1 and 200 characters"
+    ))]
+    q: String,
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    limit: Option<u64>,
+    after: Option<Uuid>,
+}
+
+#[derive(Deserialize, Validate)]
+pub(crate) struct CreateTodoRequest {
+    #[validate(length(min = TITLE_MIN_LENGTH, max = TITLE_MAX_LENGTH))]
     title: String,
-    #[validate(length(max = 255))]
+    #[validate(length(max = DESCRIPTION_MAX_LENGTH))]
     description: Option<String>,
+    due_at: Option<DateTime<Utc>>,
+    remind_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    priority: PriorityParam,
+    project_id: Option<Uuid>,
+    #[validate(length(max = RECURRENCE_MAX_LENGTH))]
+    recurrence: Option<String>,
+}
+
+impl From<CreateTodoRequest> for TodoInput {
+    fn from(request: CreateTodoRequest) -> Self {
+        Self {
+            title: request.title,
+            description: request.description,
+            due_at: request.due_at,
+            remind_at: request.remind_at,
+            priority: request.priority.into(),
+            project_id: request.project_id,
+            recurrence: request.recurrence,
+        }
+    }
 }
 
 #[derive(Deserialize, Validate)]
-struct UpdateTodoRequest {
-    #[validate(length(min = 2, max = 100))]
+pub(crate) struct UpdateTodoRequest {
+    #[validate(length(min = TITLE_MIN_LENGTH, max = TITLE_MAX_LENGTH))]
     title: String,
-    #[validate(length(max = 255))]
+    #[validate(length(max = DESCRIPTION_MAX_LENGTH))]
     description: Option<String>,
+    due_at: Option<DateTime<Utc>>,
+    remind_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    priority: PriorityParam,
+    #[validate(length(max = RECURRENCE_MAX_LENGTH))]
+    recurrence: Option<String>,
+}
+
+impl From<UpdateTodoRequest> for TodoInput {
+    fn from(request: UpdateTodoRequest) -> Self {
+        Self {
+            title: request.title,
+            description: request.description,
+            due_at: request.due_at,
+            remind_at: request.remind_at,
+            priority: request.priority.into(),
+            project_id: None,
+            recurrence: request.recurrence,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate)]
+#[validate(schema(function = "validate_operation_count"))]
+struct BatchRequest {
+    /// Roll back every operation when any of them fails.
+    #[serde(default)]
+    strict: bool,
+    #[validate(nested)]
+    operations: Vec<BatchOperationRequest>,
+}
+
+fn validate_operation_count(request: &BatchRequest) -> Result<(), ValidationError> {
+    if (1..=MAX_BATCH_OPERATIONS).contains(&request.operations.len()) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("length").with_message(
+            format!(
+                "Operations must contain between 1 and {} items",
+                MAX_BATCH_OPERATIONS
+            )
+            .into(),
+        ))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum BatchOperationRequest {
+    Create(CreateTodoRequest),
+    Update(BatchUpdateRequest),
+    Complete(BatchCompleteRequest),
+    Uncomplete(BatchTargetRequest),
+    Delete(BatchTargetRequest),
+}
+
+impl Validate for BatchOperationRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            BatchOperationRequest::Create(request) => request.validate(),
+            BatchOperationRequest::Update(request) => request.validate(),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl BatchOperationRequest {
+    fn success_status(&self) -> StatusCode {
+        match self {
+            BatchOperationRequest::Create(_) => StatusCode::CREATED,
+            _ => StatusCode::OK,
+        }
+    }
+}
+
+impl From<BatchOperationRequest> for BatchOperation {
+    fn from(request: BatchOperationRequest) -> Self {
+        match request {
+            BatchOperationRequest::Create(request) => {
+                BatchOperation::Create(TodoInput::from(request))
+            }
+            BatchOperationRequest::Update(request) => BatchOperation::Update {
+                id: request.id,
+                input: TodoInput::from(request.input),
+                expected_version: request.version,
+            },
+            BatchOperationRequest::Complete(target) => BatchOperation::Complete {
+                id: target.id,
+                strict: target.strict,
+                expected_version: target.version,
+            },
+            BatchOperationRequest::Uncomplete(target) => BatchOperation::Uncomplete {
+                id: target.id,
+                expected_version: target.version,
+            },
+            BatchOperationRequest::Delete(target) => BatchOperation::Delete {
+                id: target.id,
+                expected_version: target.version,
+            },
+        }
+    }
+}
+
+#[derive(Deserialize, Validate)]
+struct BatchUpdateRequest {
+    id: Uuid,
+    /// Plays the role of `If-Match` for this item.
+    version: Option<i32>,
+    #[serde(flatten)]
+    #[validate(nested)]
+    input: UpdateTodoRequest,
+}
+
+#[derive(Deserialize)]
+struct BatchTargetRequest {
+    id: Uuid,
+    version: Option<i32>,
+}
+
+#[derive(Deserialize)]
+struct BatchCompleteRequest {
+    id: Uuid,
+    version: Option<i32>,
+    #[serde(default)]
+    strict: bool,
+}
+
+#[derive(Serialize)]
+struct BatchItemResponse {
+    status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    todo: Option<TodoResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ErrorBody>,
+}
+
+#[derive(Serialize)]
+struct BatchResponse {
+    results: Vec<BatchItemResponse>,
+}
+
+/// Body of a JSON Merge Patch (RFC 7396) request; see [`TodoPatch`] for the tri-state fields.
+#[derive(Deserialize)]
+pub(crate) struct PatchTodoRequest {
+    #[serde(default, deserialize_with = "deserialize_present")]
+    title: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    due_at: Option<Option<DateTime<Utc>>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    remind_at: Option<Option<DateTime<Utc>>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    priority: Option<Option<PriorityParam>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    recurrence: Option<Option<String>>,
+}
+
+impl From<PatchTodoRequest> for TodoPatch {
+    fn from(request: PatchTodoRequest) -> Self {
+        Self {
+            title: request.title,
+            description: request.description,
+            due_at: request.due_at,
+            remind_at: request.remind_at,
+            priority: request
+                .priority
+                .map(|priority| priority.map(Priority::from)),
+            recurrence: request.recurrence,
+        }
+    }
+}
+
+/// Target project of a move; `null` takes the todo out of its project.
+#[derive(Deserialize)]
+struct MoveToProjectRequest {
+    project_id: Option<Uuid>,
+}
+
+/// Neighbours to place the todo between; at least one of them is required.
+#[derive(Deserialize)]
+struct MoveTodoRequest {
+    before: Option<Uuid>,
+    after: Option<Uuid>,
+}
+
+#[derive(Deserialize)]
+struct CompleteTodoParams {
+    /// Refuse completion while checklist items are still open.
+    #[serde(default)]
+    strict: bool,
+}
+
+#[derive(Deserialize, Validate)]
+struct CreateChecklistItemRequest {
+    #[validate(length(min = ITEM_TITLE_MIN_LENGTH, max = ITEM_TITLE_MAX_LENGTH))]
+    title: String,
+}
+
+#[derive(Deserialize, Validate)]
+struct UpdateChecklistItemRequest {
+    #[validate(length(min = ITEM_TITLE_MIN_LENGTH, max = ITEM_TITLE_MAX_LENGTH))]
+    title: String,
+    completed: bool,
+}
+
+/// Wraps a present field in `Some`, so an explicit `null` becomes `Some(None)`.
+fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 async fn get_all_todos<C, U>(
     State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    ValidatedQuery(params): ValidatedQuery<ListTodosParams>,
 ) -> Result<impl IntoResponse, AppError>
 where
     C: Conn + 'static,
     U: TodoUsecase + Send + Sync + 'static,
 {
     let conn = app_state.db.as_ref();
-    let todos = app_state.todo_usecase.get_all_todos(conn).await?;
-    Ok((
-        StatusCode::OK,
-        Json(
-            todos
-                .into_iter()
-                .map(TodoResponse::from)
-                .collect::<Vec<_>>(),
-        ),
-    ))
+    let page = app_state
+        .todo_usecase
+        .get_all_todos(
+            conn,
+            user.user_id,
+            TodoQuery::from(&params),
+            params.after,
+            params.limit.unwrap_or(DEFAULT_PAGE_LIMIT),
+        )
+        .await?;
+    Ok((StatusCode::OK, Json(TodoListResponse::from(page))))
+}
+
+async fn get_overdue_todos<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    ValidatedQuery(params): ValidatedQuery<PageParams>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let query = TodoQuery {
+        overdue: Some(true),
+        ..TodoQuery::default()
+    };
+    let page = app_state
+        .todo_usecase
+        .get_all_todos(
+            conn,
+            user.user_id,
+            query,
+            params.after,
+            params.limit.unwrap_or(DEFAULT_PAGE_LIMIT),
+        )
+        .await?;
+    Ok((StatusCode::OK, Json(TodoListResponse::from(page))))
+}
+
+async fn search_todos<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    ValidatedQuery(params): ValidatedQuery<SearchParams>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let page = app_state
+        .todo_usecase
+        .search_todos(
+            conn,
+            user.user_id,
+            &params.q,
+            params.after,
+            params.limit.unwrap_or(DEFAULT_PAGE_LIMIT),
+        )
+        .await?;
+    Ok((StatusCode::OK, Json(SearchResponse::from(page))))
 }
 
 async fn get_todo_by_id<C, U>(
     State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
     WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
-) -> Result<Json<TodoResponse>, AppError>
+) -> Result<impl IntoResponse, AppError>
 where
     C: Conn + 'static,
     U: TodoUsecase + Send + Sync + 'static,
 {
     let conn = app_state.db.as_ref();
-    let todo = app_state.todo_usecase.get_todo_by_id(conn, id).await?;
-    Ok(Json(TodoResponse::from(todo)))
+    let todo = app_state
+        .todo_usecase
+        .get_todo_by_id(conn, user.user_id, id)
+        .await?;
+    Ok(todo_response(StatusCode::OK, todo))
 }
 
 async fn post_todo<C, U>(
     State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
     ValidatedJson(input): ValidatedJson<CreateTodoRequest>,
 ) -> Result<impl IntoResponse, AppError>
 where
@@ -130,14 +797,16 @@ where
     let conn = app_state.db.as_ref();
     let todo = app_state
         .todo_usecase
-        .create_todo(conn, input.title, input.description)
+        .create_todo(conn, user.user_id, TodoInput::from(input))
         .await?;
-    Ok((StatusCode::CREATED, Json(TodoResponse::from(todo))))
+    Ok(todo_response(StatusCode::CREATED, todo))
 }
 
 async fn update_todo<C, U>(
     State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
     WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    IfMatch(expected_version): IfMatch,
     ValidatedJson(input): ValidatedJson<UpdateTodoRequest>,
 ) -> Result<impl IntoResponse, AppError>
 where
@@ -147,40 +816,129 @@ where
     let conn = app_state.db.as_ref();
     let todo = app_state
         .todo_usecase
-        .update_todo(conn, id, input.title, input.description)
+        .update_todo(
+            conn,
+            user.user_id,
+            id,
+            TodoInput::from(input),
+            expected_version,
+        )
         .await?;
-    Ok((StatusCode::OK, Json(TodoResponse::from(todo))))
+    Ok(todo_response(StatusCode::OK, todo))
+}
+
+async fn patch_todo<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    IfMatch(expected_version): IfMatch,
+    WithRejection(Json(input), _): WithRejection<Json<PatchTodoRequest>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let todo = app_state
+        .todo_usecase
+        .patch_todo(
+            conn,
+            user.user_id,
+            id,
+            TodoPatch::from(input),
+            expected_version,
+        )
+        .await?;
+    Ok(todo_response(StatusCode::OK, todo))
 }
 
 async fn delete_todo<C, U>(
     State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
     WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    IfMatch(expected_version): IfMatch,
 ) -> Result<impl IntoResponse, AppError>
 where
     C: Conn + 'static,
     U: TodoUsecase + Send + Sync + 'static,
 {
     let conn = app_state.db.as_ref();
-    app_state.todo_usecase.delete_todo(conn, id).await?;
+    app_state
+        .todo_usecase
+        .delete_todo(conn, user.user_id, id, expected_version)
+        .await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
+async fn move_todo<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    IfMatch(expected_version): IfMatch,
+    WithRejection(Json(input), _): WithRejection<Json<MoveTodoRequest>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let todo = app_state
+        .todo_usecase
+        .move_todo(
+            conn,
+            user.user_id,
+            id,
+            input.before,
+            input.after,
+            expected_version,
+        )
+        .await?;
+    Ok(todo_response(StatusCode::OK, todo))
+}
+
+async fn move_todo_to_project<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    IfMatch(expected_version): IfMatch,
+    WithRejection(Json(input), _): WithRejection<Json<MoveToProjectRequest>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let todo = app_state
+        .todo_usecase
+        .move_todo_to_project(conn, user.user_id, id, input.project_id, expected_version)
+        .await?;
+    Ok(todo_response(StatusCode::OK, todo))
+}
+
 async fn mark_todo_completed<C, U>(
     State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
     WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    WithRejection(Query(params), _): WithRejection<Query<CompleteTodoParams>, AppError>,
+    IfMatch(expected_version): IfMatch,
 ) -> Result<impl IntoResponse, AppError>
 where
     C: Conn + 'static,
     U: TodoUsecase + Send + Sync + 'static,
 {
     let conn = app_state.db.as_ref();
-    let todo = app_state.todo_usecase.mark_todo_completed(conn, id).await?;
-    Ok((StatusCode::OK, Json(TodoResponse::from(todo))))
+    let todo = app_state
+        .todo_usecase
+        .mark_todo_completed(conn, user.user_id, id, params.strict, expected_version)
+        .await?;
+    Ok(todo_response(StatusCode::OK, todo))
 }
 
 async fn unmark_todo_completed<C, U>(
     State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
     WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    IfMatch(expected_version): IfMatch,
 ) -> Result<impl IntoResponse, AppError>
 where
     C: Conn + 'static,
@@ -189,7 +947,258 @@ where
     let conn = app_state.db.as_ref();
     let todo = app_state
         .todo_usecase
-        .unmark_todo_completed(conn, id)
+        .unmark_todo_completed(conn, user.user_id, id, expected_version)
+        .await?;
+    Ok(todo_response(StatusCode::OK, todo))
+}
+
+async fn batch_todos<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    ValidatedJson(input): ValidatedJson<BatchRequest>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let success_statuses = input
+        .operations
+        .iter()
+        .map(BatchOperationRequest::success_status)
+        .collect::<Vec<_>>();
+    let operations = input
+        .operations
+        .into_iter()
+        .map(BatchOperation::from)
+        .collect();
+    let results = app_state
+        .todo_usecase
+        .batch_todos(conn, user.user_id, operations, input.strict)
+        .await?;
+    let results = results
+        .into_iter()
+        .zip(success_statuses)
+        .map(|(result, success_status)| match result {
+            Ok(todo) => BatchItemResponse {
+                status: success_status.as_u16(),
+                todo: Some(TodoResponse::from(todo)),
+                error: None,
+            },
+            Err(err) => {
+                let (status, body) = AppError::from(err).into_parts();
+                BatchItemResponse {
+                    status: status.as_u16(),
+                    todo: None,
+                    error: Some(body),
+                }
+            }
+        })
+        .collect();
+    Ok((StatusCode::OK, Json(BatchResponse { results })))
+}
+
+async fn get_trashed_todos<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    ValidatedQuery(params): ValidatedQuery<PageParams>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let page = app_state
+        .todo_usecase
+        .get_trashed_todos(
+            conn,
+            user.user_id,
+            params.after,
+            params.limit.unwrap_or(DEFAULT_PAGE_LIMIT),
+        )
+        .await?;
+    Ok((StatusCode::OK, Json(TodoListResponse::from(page))))
+}
+
+async fn restore_todo<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    IfMatch(expected_version): IfMatch,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let todo = app_state
+        .todo_usecase
+        .restore_todo(conn, user.user_id, id, expected_version)
+        .await?;
+    Ok(todo_response(StatusCode::OK, todo))
+}
+
+async fn purge_todo<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    IfMatch(expected_version): IfMatch,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    app_state
+        .todo_usecase
+        .purge_todo(conn, user.user_id, id, expected_version)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_checklist_items<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let todo = app_state
+        .todo_usecase
+        .get_todo_by_id(conn, user.user_id, id)
+        .await?;
+    Ok((
+        StatusCode::OK,
+        [(header::ETAG, etag(todo.version))],
+        Json(ChecklistItemListResponse {
+            items: todo
+                .items
+                .into_iter()
+                .map(ChecklistItemResponse::from)
+                .collect(),
+        }),
+    ))
+}
+
+async fn add_checklist_item<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    IfMatch(expected_version): IfMatch,
+    ValidatedJson(input): ValidatedJson<CreateChecklistItemRequest>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let (todo, item_id) = app_state
+        .todo_usecase
+        .add_checklist_item(conn, user.user_id, id, input.title, expected_version)
+        .await?;
+    checklist_item_response(StatusCode::CREATED, todo, item_id)
+}
+
+async fn update_checklist_item<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path((id, item_id)), _): WithRejection<Path<(Uuid, Uuid)>, AppError>,
+    IfMatch(expected_version): IfMatch,
+    ValidatedJson(input): ValidatedJson<UpdateChecklistItemRequest>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let todo = app_state
+        .todo_usecase
+        .update_checklist_item(
+            conn,
+            user.user_id,
+            id,
+            item_id,
+            input.title,
+            input.completed,
+            expected_version,
+        )
+        .await?;
+    checklist_item_response(StatusCode::OK, todo, item_id)
+}
+
+async fn remove_checklist_item<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path((id, item_id)), _): WithRejection<Path<(Uuid, Uuid)>, AppError>,
+    IfMatch(expected_version): IfMatch,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    app_state
+        .todo_usecase
+        .remove_checklist_item(conn, user.user_id, id, item_id, expected_version)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The id of the last event a reconnecting `EventSource` saw.
+struct LastEventId(Option<Uuid>);
+
+impl<S> FromRequestParts<S> for LastEventId
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get("last-event-id") else {
+            return Ok(LastEventId(None));
+        };
+        value
+            .to_str()
+            .ok()
+            .and_then(|value| Uuid::parse_str(value.trim()).ok())
+            .map(|id| LastEventId(Some(id)))
+            .ok_or(AppError::BadRequest(ErrorBody {
+                code: "400",
+                message: "Last-Event-ID must be the id of an event".to_string(),
+            }))
+    }
+}
+
+/// Streams changes to the todos visible to the user as server-sent events
+/// named after the event type. The stream ends when the client falls too far behind; it then
+/// reconnects with `Last-Event-ID` to pick up what it missed.
+async fn get_todo_events<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    LastEventId(last_event_id): LastEventId,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let feed = app_state
+        .todo_usecase
+        .subscribe_events(app_state.db.as_ref(), user.user_id, None, last_event_id)
         .await?;
-    Ok((StatusCode::OK, Json(TodoResponse::from(todo))))
+    let stream =
+        futures_util::stream::unfold((app_state, feed), |(app_state, mut feed)| async move {
+            let event = app_state
+                .todo_usecase
+                .next_event(app_state.db.as_ref(), &mut feed)
+                .await?;
+            let sse = Event::default()
+                .id(event.id.to_string())
+                .event(event.kind.to_string())
+                .json_data(event.payload());
+            Some((sse, (app_state, feed)))
+        });
+    Ok(Sse::new(stream).keep_alive(KeepAlive::new().interval(EVENT_STREAM_KEEP_ALIVE)))
 }
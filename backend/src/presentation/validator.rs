@@ -15,6 +15,30 @@ impl From<axum::extract::rejection::JsonRejection> for AppError {
     }
 }
 
+/// Runs `value`'s validation rules, reporting every violated one.
+pub(crate) fn validate<T: Validate>(value: &T) -> Result<(), AppError> {
+    if let Err(e) = value.validate() {
+        let violations = e
+            .field_errors()
+            .into_values()
+            .flat_map(|errs| {
+                errs.iter()
+                    .filter_map(|err| err.message.clone().map(|m| m.to_string()))
+            })
+            .collect::<Vec<_>>();
+
+        return Err(AppError::BadRequest(ErrorBody {
+            code: "400",
+            message: if violations.is_empty() {
+                "Validation failed".into()
+            } else {
+                format!("Validation failed: [{}]", violations.join(", "))
+            },
+        }));
+    }
+    Ok(())
+}
+
 pub struct ValidatedJson<T>(pub T);
 
 impl<S, T> FromRequest<S> for ValidatedJson<T>
@@ -29,26 +53,7 @@ where
             .await
             .map_err(AppError::from)?;
 
-        if let Err(e) = value.validate() {
-            let violations = e
-                .field_errors()
-                .into_iter()
-                .flat_map(|(_, errs)| {
-                    errs.iter()
-                        .filter_map(|err| err.message.clone().map(|m| m.to_string()))
-                })
-                .collect::<Vec<_>>();
-
-            return Err(AppError::BadRequest(ErrorBody {
-                code: "400",
-                message: if violations.is_empty() {
-                    "Validation failed".into()
-                } else {
-                    format!("Validation failed: [{}]", violations.join(", "))
-                },
-            }));
-        }
-
+        validate(&value)?;
         Ok(ValidatedJson(value))
     }
 }
@@ -76,26 +81,7 @@ where
             .await
             .map_err(AppError::from)?;
 
-        if let Err(e) = value.validate() {
-            let violations = e
-                .field_errors()
-                .into_iter()
-                .flat_map(|(_, errs)| {
-                    errs.iter()
-                        .filter_map(|err| err.message.clone().map(|m| m.to_string()))
-                })
-                .collect::<Vec<_>>();
-
-            return Err(AppError::BadRequest(ErrorBody {
-                code: "400",
-                message: if violations.is_empty() {
-                    "Validation failed".into()
-                } else {
-                    format!("Validation failed: [{}]", violations.join(", "))
-                },
-            }));
-        }
-
+        validate(&value)?;
         Ok(ValidatedQuery(value))
     }
 }
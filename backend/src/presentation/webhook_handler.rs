@@ -0,0 +1,446 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+};
+use axum_extra::extract::WithRejection;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application_service::usecase::webhook_usecase::{
+        DeliveryDetails, DeliveryPage, WebhookInput, WebhookUsecase,
+    },
+    domain::{
+        models::{
+            todo_event::TodoEventKind,
+            webhook::{
+                DeliveryAttempt, DeliveryStatus, WEBHOOK_SECRET_MAX_LENGTH,
+                WEBHOOK_SECRET_MIN_LENGTH, WEBHOOK_URL_MAX_LENGTH, Webhook, WebhookDelivery,
+            },
+        },
+        repositories::conn::Conn,
+    },
+    presentation::{
+        auth::AuthUser,
+        errors::AppError,
+        todo_handler::DEFAULT_PAGE_LIMIT,
+        validator::{ValidatedJson, ValidatedQuery},
+    },
+};
+
+pub struct AppState<C, U> {
+    webhook_usecase: Arc<U>,
+    db: Arc<C>,
+}
+
+impl<C, U> Clone for AppState<C, U> {
+    fn clone(&self) -> Self {
+        Self {
+            webhook_usecase: Arc::clone(&self.webhook_usecase),
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+/// Webhook management needs an interactive login; API keys cannot manage webhooks.
+pub fn create_webhook_router<C, U>(webhook_usecase: Arc<U>, db: Arc<C>) -> Router
+where
+    C: Conn + 'static,
+    U: WebhookUsecase + Send + Sync + 'static,
+{
+    let app_state: AppState<C, U> = AppState {
+        webhook_usecase,
+        db,
+    };
+
+    Router::new()
+        .route("/", get(get_webhooks::<C, U>).post(post_webhook::<C, U>))
+        .route(
+            "/{id}",
+            get(get_webhook::<C, U>)
+                .put(put_webhook::<C, U>)
+                .delete(delete_webhook::<C, U>),
+        )
+        .route("/{id}/deliveries", get(get_deliveries::<C, U>))
+        .route("/{id}/deliveries/{delivery_id}", get(get_delivery::<C, U>))
+        .route(
+            "/{id}/deliveries/{delivery_id}/replay",
+            post(replay_delivery::<C, U>),
+        )
+        .with_state(app_state)
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+enum EventParam {
+    #[serde(rename = "todo.created")]
+    Created,
+    #[serde(rename = "todo.updated")]
+    Updated,
+    #[serde(rename = "todo.completed")]
+    Completed,
+    #[serde(rename = "todo.uncompleted")]
+    Uncompleted,
+    #[serde(rename = "todo.deleted")]
+    Deleted,
+}
+
+impl From<EventParam> for TodoEventKind {
+    fn from(event: EventParam) -> Self {
+        match event {
+            EventParam::Created => TodoEventKind::TodoCreated,
+            EventParam::Updated => TodoEventKind::TodoUpdated,
+            EventParam::Completed => TodoEventKind::TodoCompleted,
+            EventParam::Uncompleted => TodoEventKind::TodoUncompleted,
+            EventParam::Deleted => TodoEventKind::TodoDeleted,
+        }
+    }
+}
+
+impl From<TodoEventKind> for EventParam {
+    fn from(kind: TodoEventKind) -> Self {
+        match kind {
+            TodoEventKind::TodoCreated => EventParam::Created,
+            TodoEventKind::TodoUpdated => EventParam::Updated,
+            TodoEventKind::TodoCompleted => EventParam::Completed,
+            TodoEventKind::TodoUncompleted => EventParam::Uncompleted,
+            TodoEventKind::TodoDeleted => EventParam::Deleted,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum DeliveryStatusResponse {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+impl From<DeliveryStatus> for DeliveryStatusResponse {
+    fn from(status: DeliveryStatus) -> Self {
+        match status {
+            DeliveryStatus::Pending => DeliveryStatusResponse::Pending,
+            DeliveryStatus::Delivered => DeliveryStatusResponse::Delivered,
+            DeliveryStatus::Failed => DeliveryStatusResponse::Failed,
+        }
+    }
+}
+
+/// The secret is write-only and never returned.
+#[derive(Serialize)]
+struct WebhookResponse {
+    id: Uuid,
+    url: String,
+    events: Vec<EventParam>,
+    active: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<Webhook> for WebhookResponse {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            id: webhook.id,
+            url: webhook.url,
+            events: webhook.events.into_iter().map(EventParam::from).collect(),
+            active: webhook.active,
+            created_at: webhook.created_at,
+            updated_at: webhook.updated_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct WebhookListResponse {
+    webhooks: Vec<WebhookResponse>,
+}
+
+impl From<Vec<Webhook>> for WebhookListResponse {
+    fn from(webhooks: Vec<Webhook>) -> Self {
+        Self {
+            webhooks: webhooks.into_iter().map(WebhookResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DeliveryResponse {
+    id: Uuid,
+    webhook_id: Uuid,
+    event_id: Uuid,
+    event_type: EventParam,
+    status: DeliveryStatusResponse,
+    payload: Value,
+    attempts: i32,
+    next_attempt_at: Option<DateTime<Utc>>,
+    delivered_at: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<WebhookDelivery> for DeliveryResponse {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id,
+            webhook_id: delivery.webhook_id,
+            event_id: delivery.event_id,
+            event_type: delivery.event_type.into(),
+            status: delivery.status().into(),
+            payload: delivery.payload,
+            attempts: delivery.attempts,
+            next_attempt_at: delivery.next_attempt_at,
+            delivered_at: delivery.delivered_at,
+            last_error: delivery.last_error,
+            created_at: delivery.created_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DeliveryListResponse {
+    deliveries: Vec<DeliveryResponse>,
+    next_cursor: Option<Uuid>,
+}
+
+impl From<DeliveryPage> for DeliveryListResponse {
+    fn from(page: DeliveryPage) -> Self {
+        Self {
+            deliveries: page
+                .deliveries
+                .into_iter()
+                .map(DeliveryResponse::from)
+                .collect(),
+            next_cursor: page.next_cursor,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct AttemptResponse {
+    id: Uuid,
+    status_code: Option<i32>,
+    error: Option<String>,
+    attempted_at: DateTime<Utc>,
+}
+
+impl From<DeliveryAttempt> for AttemptResponse {
+    fn from(attempt: DeliveryAttempt) -> Self {
+        Self {
+            id: attempt.id,
+            status_code: attempt.status_code,
+            error: attempt.error,
+            attempted_at: attempt.attempted_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DeliveryDetailsResponse {
+    #[serde(flatten)]
+    delivery: DeliveryResponse,
+    attempt_log: Vec<AttemptResponse>,
+}
+
+impl From<DeliveryDetails> for DeliveryDetailsResponse {
+    fn from(details: DeliveryDetails) -> Self {
+        Self {
+            delivery: DeliveryResponse::from(details.delivery),
+            attempt_log: details
+                .attempts
+                .into_iter()
+                .map(AttemptResponse::from)
+                .collect(),
+        }
+    }
+}
+
+fn default_active() -> bool {
+    true
+}
+
+#[derive(Deserialize, Validate)]
+struct WebhookRequest {
+    #[validate(length(min = 1, max = WEBHOOK_URL_MAX_LENGTH))]
+    url: String,
+    /// Omitted or empty subscribes to every event.
+    #[serde(default)]
+    events: Vec<EventParam>,
+    #[validate(length(min = WEBHOOK_SECRET_MIN_LENGTH, max = WEBHOOK_SECRET_MAX_LENGTH))]
+    secret: String,
+    #[serde(default = "default_active")]
+    active: bool,
+}
+
+impl From<WebhookRequest> for WebhookInput {
+    fn from(request: WebhookRequest) -> Self {
+        Self {
+            url: request.url,
+            events: request
+                .events
+                .into_iter()
+                .map(TodoEventKind::from)
+                .collect(),
+            secret: request.secret,
+            active: request.active,
+        }
+    }
+}
+
+#[derive(Deserialize, Validate)]
+struct DeliveryParams {
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    limit: Option<u64>,
+    after: Option<Uuid>,
+}
+
+async fn get_webhooks<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: WebhookUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let webhooks = app_state
+        .webhook_usecase
+        .get_webhooks(conn, user.user_id)
+        .await?;
+    Ok((StatusCode::OK, Json(WebhookListResponse::from(webhooks))))
+}
+
+async fn get_webhook<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: WebhookUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let webhook = app_state
+        .webhook_usecase
+        .get_webhook(conn, user.user_id, id)
+        .await?;
+    Ok((StatusCode::OK, Json(WebhookResponse::from(webhook))))
+}
+
+async fn post_webhook<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    ValidatedJson(input): ValidatedJson<WebhookRequest>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: WebhookUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let webhook = app_state
+        .webhook_usecase
+        .create_webhook(conn, user.user_id, input.into())
+        .await?;
+    Ok((StatusCode::CREATED, Json(WebhookResponse::from(webhook))))
+}
+
+async fn put_webhook<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    ValidatedJson(input): ValidatedJson<WebhookRequest>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: WebhookUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let webhook = app_state
+        .webhook_usecase
+        .update_webhook(conn, user.user_id, id, input.into())
+        .await?;
+    Ok((StatusCode::OK, Json(WebhookResponse::from(webhook))))
+}
+
+async fn delete_webhook<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: WebhookUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    app_state
+        .webhook_usecase
+        .delete_webhook(conn, user.user_id, id)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_deliveries<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path(id), _): WithRejection<Path<Uuid>, AppError>,
+    ValidatedQuery(params): ValidatedQuery<DeliveryParams>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: WebhookUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let page = app_state
+        .webhook_usecase
+        .get_deliveries(
+            conn,
+            user.user_id,
+            id,
+            params.after,
+            params.limit.unwrap_or(DEFAULT_PAGE_LIMIT),
+        )
+        .await?;
+    Ok((StatusCode::OK, Json(DeliveryListResponse::from(page))))
+}
+
+async fn get_delivery<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path((id, delivery_id)), _): WithRejection<Path<(Uuid, Uuid)>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: WebhookUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let details = app_state
+        .webhook_usecase
+        .get_delivery(conn, user.user_id, id, delivery_id)
+        .await?;
+    Ok((StatusCode::OK, Json(DeliveryDetailsResponse::from(details))))
+}
+
+/// Accepted rather than done: the delivery is sent by the next dispatch.
+async fn replay_delivery<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    WithRejection(Path((id, delivery_id)), _): WithRejection<Path<(Uuid, Uuid)>, AppError>,
+) -> Result<impl IntoResponse, AppError>
+where
+    C: Conn + 'static,
+    U: WebhookUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let delivery = app_state
+        .webhook_usecase
+        .replay_delivery(conn, user.user_id, id, delivery_id)
+        .await?;
+    Ok((StatusCode::ACCEPTED, Json(DeliveryResponse::from(delivery))))
+}
@@ -0,0 +1,419 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Router,
+    extract::{
+        State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    middleware,
+    response::IntoResponse,
+    routing::get,
+};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use uuid::Uuid;
+
+use crate::{
+    application_service::usecase::todo_usecase::{TodoEventFeed, TodoInput, TodoUsecase},
+    domain::{
+        models::{api_key::ApiKeyScope, todo::TodoPatch, todo_event::TodoEvent},
+        repositories::conn::Conn,
+    },
+    presentation::{
+        auth::{ApiKeyIdentity, AuthUser, require_scope},
+        errors::{AppError, ErrorBody},
+        todo_handler::{CreateTodoRequest, PatchTodoRequest, TodoResponse, UpdateTodoRequest},
+        validator::validate,
+    },
+};
+
+/// Largest message a client may send; bigger ones close the connection.
+const MAX_MESSAGE_SIZE: usize = 64 * 1024;
+/// Messages waiting to be written to a client before it counts as too slow.
+const OUTGOING_QUEUE_SIZE: usize = 64;
+
+pub struct AppState<C, U> {
+    todo_usecase: Arc<U>,
+    db: Arc<C>,
+}
+
+impl<C, U> Clone for AppState<C, U> {
+    fn clone(&self) -> Self {
+        Self {
+            todo_usecase: Arc::clone(&self.todo_usecase),
+            db: Arc::clone(&self.db),
+        }
+    }
+}
+
+pub fn create_ws_router<C, U>(todo_usecase: Arc<U>, db: Arc<C>) -> Router
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let app_state: AppState<C, U> = AppState { todo_usecase, db };
+    // API keys need `todos:read` to connect; mutations also check `todos:write`.
+    let read = middleware::from_fn_with_state(ApiKeyScope::TodosRead, require_scope);
+
+    Router::new()
+        .route("/", get(connect::<C, U>).route_layer(read))
+        .with_state(app_state)
+}
+
+/// Sent by the client. Mutations may carry an `id`, echoed in the reply.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    /// Replaces the current subscription. Without `project_id` it covers all
+    /// the todos visible to the user, their own and those shared with them;
+    /// `last_event_id` resumes after a lost subscription.
+    Subscribe {
+        project_id: Option<Uuid>,
+        last_event_id: Option<Uuid>,
+    },
+    Unsubscribe,
+    Create {
+        todo: CreateTodoRequest,
+    },
+    Update {
+        todo_id: Uuid,
+        expected_version: Option<i32>,
+        todo: UpdateTodoRequest,
+    },
+    Patch {
+        todo_id: Uuid,
+        expected_version: Option<i32>,
+        patch: PatchTodoRequest,
+    },
+    Move {
+        todo_id: Uuid,
+        expected_version: Option<i32>,
+        before: Option<Uuid>,
+        after: Option<Uuid>,
+    },
+    MoveToProject {
+        todo_id: Uuid,
+        expected_version: Option<i32>,
+        project_id: Option<Uuid>,
+    },
+    Complete {
+        todo_id: Uuid,
+        expected_version: Option<i32>,
+        #[serde(default)]
+        strict: bool,
+    },
+    Uncomplete {
+        todo_id: Uuid,
+        expected_version: Option<i32>,
+    },
+    Delete {
+        todo_id: Uuid,
+        expected_version: Option<i32>,
+    },
+}
+
+impl ClientMessage {
+    fn is_mutation(&self) -> bool {
+        !matches!(
+            self,
+            ClientMessage::Subscribe { .. } | ClientMessage::Unsubscribe
+        )
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    Subscribed {
+        id: Option<Value>,
+        project_id: Option<Uuid>,
+    },
+    /// Also sent unasked, with `lagged` set, when the client fell too far
+    /// behind; it should subscribe again from the last event it saw.
+    Unsubscribed {
+        id: Option<Value>,
+        lagged: bool,
+    },
+    Event {
+        event: Value,
+    },
+    /// The todo after a mutation; `null` once deleted.
+    Result {
+        id: Option<Value>,
+        todo: Option<TodoResponse>,
+    },
+    Error {
+        id: Option<Value>,
+        #[serde(flatten)]
+        error: ErrorBody,
+    },
+}
+
+async fn next_event<C, U>(
+    app_state: &AppState<C, U>,
+    subscription: Option<&mut TodoEventFeed>,
+) -> Option<TodoEvent>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    app_state
+        .todo_usecase
+        .next_event(app_state.db.as_ref(), subscription?)
+        .await
+}
+
+async fn connect<C, U>(
+    State(app_state): State<AppState<C, U>>,
+    user: AuthUser,
+    api_key: Option<Extension<ApiKeyIdentity>>,
+    upgrade: WebSocketUpgrade,
+) -> impl IntoResponse
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let can_write = api_key
+        .is_none_or(|Extension(identity)| identity.scopes.contains(&ApiKeyScope::TodosWrite));
+    upgrade
+        .max_message_size(MAX_MESSAGE_SIZE)
+        .on_upgrade(move |socket| serve(socket, app_state, user, can_write))
+}
+
+/// Runs one connection. Client messages are handled one at a time, and
+/// replies wait for room in the outgoing queue, so a client that stops
+/// reading also stops being read from. Events never wait: when they do not
+/// fit, the subscription ends instead of buffering without bound.
+async fn serve<C, U>(socket: WebSocket, app_state: AppState<C, U>, user: AuthUser, can_write: bool)
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let (mut sink, mut stream) = socket.split();
+    let (outgoing, mut queue) = mpsc::channel::<ServerMessage>(OUTGOING_QUEUE_SIZE);
+    let writer = tokio::spawn(async move {
+        while let Some(message) = queue.recv().await {
+            let text = serde_json::to_string(&message).expect("server messages serialize");
+            if sink.send(Message::Text(text.into())).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut subscription = None;
+    loop {
+        tokio::select! {
+            message = stream.next() => {
+                let reply = match message {
+                    Some(Ok(Message::Text(text))) => {
+                        handle(&app_state, user, can_write, &mut subscription, &text).await
+                    }
+                    Some(Ok(Message::Binary(_))) => ServerMessage::Error {
+                        id: None,
+                        error: bad_request("Only text messages are supported"),
+                    },
+                    // Pings are answered by axum.
+                    Some(Ok(Message::Ping(_) | Message::Pong(_))) => continue,
+                    Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                };
+                if outgoing.send(reply).await.is_err() {
+                    break;
+                }
+            }
+            event = next_event(&app_state, subscription.as_mut()), if subscription.is_some() => {
+                let lagged = match event {
+                    Some(event) => match outgoing.try_send(ServerMessage::Event {
+                        event: event.payload(),
+                    }) {
+                        Ok(()) => false,
+                        Err(TrySendError::Full(_)) => true,
+                        Err(TrySendError::Closed(_)) => break,
+                    },
+                    // The hub dropped the subscription.
+                    None => true,
+                };
+                if lagged {
+                    subscription = None;
+                    let notice = ServerMessage::Unsubscribed { id: None, lagged: true };
+                    if outgoing.send(notice).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    drop(outgoing);
+    let _ = writer.await;
+}
+
+async fn handle<C, U>(
+    app_state: &AppState<C, U>,
+    user: AuthUser,
+    can_write: bool,
+    subscription: &mut Option<TodoEventFeed>,
+    text: &str,
+) -> ServerMessage
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let Ok(value) = serde_json::from_str::<Value>(text) else {
+        return ServerMessage::Error {
+            id: None,
+            error: bad_request("Invalid JSON input"),
+        };
+    };
+    let id = value.get("id").cloned();
+    let Ok(message) = ClientMessage::deserialize(value) else {
+        return ServerMessage::Error {
+            id,
+            error: bad_request("Unknown or malformed message"),
+        };
+    };
+    if message.is_mutation() && !can_write {
+        return ServerMessage::Error {
+            id,
+            error: ErrorBody {
+                code: "403",
+                message: format!(
+                    "Forbidden: API key lacks the {} scope",
+                    ApiKeyScope::TodosWrite
+                ),
+            },
+        };
+    }
+    match execute(app_state, user, subscription, id.clone(), message).await {
+        Ok(reply) => reply,
+        Err(err) => ServerMessage::Error {
+            id,
+            error: err.into_parts().1,
+        },
+    }
+}
+
+async fn execute<C, U>(
+    app_state: &AppState<C, U>,
+    user: AuthUser,
+    subscription: &mut Option<TodoEventFeed>,
+    id: Option<Value>,
+    message: ClientMessage,
+) -> Result<ServerMessage, AppError>
+where
+    C: Conn + 'static,
+    U: TodoUsecase + Send + Sync + 'static,
+{
+    let conn = app_state.db.as_ref();
+    let usecase = app_state.todo_usecase.as_ref();
+    let todo = match message {
+        ClientMessage::Subscribe {
+            project_id,
+            last_event_id,
+        } => {
+            let feed = usecase
+                .subscribe_events(conn, user.user_id, project_id, last_event_id)
+                .await?;
+            *subscription = Some(feed);
+            return Ok(ServerMessage::Subscribed { id, project_id });
+        }
+        ClientMessage::Unsubscribe => {
+            *subscription = None;
+            return Ok(ServerMessage::Unsubscribed { id, lagged: false });
+        }
+        ClientMessage::Create { todo } => {
+            validate(&todo)?;
+            usecase
+                .create_todo(conn, user.user_id, TodoInput::from(todo))
+                .await?
+        }
+        ClientMessage::Update {
+            todo_id,
+            expected_version,
+            todo,
+        } => {
+            validate(&todo)?;
+            usecase
+                .update_todo(
+                    conn,
+                    user.user_id,
+                    todo_id,
+                    TodoInput::from(todo),
+                    expected_version,
+                )
+                .await?
+        }
+        ClientMessage::Patch {
+            todo_id,
+            expected_version,
+            patch,
+        } => {
+            usecase
+                .patch_todo(
+                    conn,
+                    user.user_id,
+                    todo_id,
+                    TodoPatch::from(patch),
+                    expected_version,
+                )
+                .await?
+        }
+        ClientMessage::Move {
+            todo_id,
+            expected_version,
+            before,
+            after,
+        } => {
+            usecase
+                .move_todo(conn, user.user_id, todo_id, before, after, expected_version)
+                .await?
+        }
+        ClientMessage::MoveToProject {
+            todo_id,
+            expected_version,
+            project_id,
+        } => {
+            usecase
+                .move_todo_to_project(conn, user.user_id, todo_id, project_id, expected_version)
+                .await?
+        }
+        ClientMessage::Complete {
+            todo_id,
+            expected_version,
+            strict,
+        } => {
+            usecase
+                .mark_todo_completed(conn, user.user_id, todo_id, strict, expected_version)
+                .await?
+        }
+        ClientMessage::Uncomplete {
+            todo_id,
+            expected_version,
+        } => {
+            usecase
+                .unmark_todo_completed(conn, user.user_id, todo_id, expected_version)
+                .await?
+        }
+        ClientMessage::Delete {
+            todo_id,
+            expected_version,
+        } => {
+            usecase
+                .delete_todo(conn, user.user_id, todo_id, expected_version)
+                .await?;
+            return Ok(ServerMessage::Result { id, todo: None });
+        }
+    };
+    Ok(ServerMessage::Result {
+        id,
+        todo: Some(TodoResponse::from(todo)),
+    })
+}
+
+fn bad_request(message: &str) -> ErrorBody {
+    ErrorBody {
+        code: "400",
+        message: message.to_string(),
+    }
+}